@@ -1,22 +1,26 @@
-use super::{Link, State};
+use super::{escape, Level, Link, State, Substitutions, Target};
 use std::{
     collections::HashMap,
     io::{BufWriter, Write},
 };
 
+/// Writes a link around `content`, which was escaped along with the rest of the text,
+/// while the url of the relationship is left raw for \href
 pub fn hyperlink<W: Write>(
     buf_writer: &mut BufWriter<W>,
-    rels: &HashMap<String, String>,
+    rels: &HashMap<String, Target>,
     hyperlink: (&Link, &String),
 ) -> std::io::Result<State> {
     let (link, content) = hyperlink;
     match link {
         Link::Anchor(anchor) => {
+            let anchor = self::anchor(anchor);
             write!(buf_writer, "\\hyperlink{{{anchor}}}{{{content}}}")?;
             Ok(State::Happy)
         }
         Link::Relationship(rel_id) => {
-            if let Some(url) = rels.get(rel_id) {
+            if let Some(target) = rels.get(rel_id) {
+                let url = self::url(target.value());
                 write!(buf_writer, "\\href{{{url}}}{{{content}}}")?;
                 Ok(State::Happy)
             } else {
@@ -28,18 +32,515 @@ pub fn hyperlink<W: Write>(
     }
 }
 
+/// Escapes the characters of a url that \href would still parse, especially inside of another argument
+pub fn url(raw: &str) -> String {
+    let mut url = String::with_capacity(raw.len());
+    for c in raw.chars() {
+        if matches!(c, '%' | '#' | '&') {
+            url.push('\\');
+        }
+        url.push(c);
+    }
+    url
+}
+
+/// Makes a bookmark name safe for use as a hyperref destination.
+/// Both `\hypertarget` and `\hyperlink` must go through here so that internal links resolve.
+pub fn anchor(raw: &str) -> String {
+    raw.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | ':') {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect()
+}
+
+/// Maps a `w:highlight` value to an xcolor name, "none" and unknown values have no highlight
+pub fn highlight_color(name: &str) -> Option<&'static str> {
+    match name {
+        "black" => Some("black"),
+        "blue" => Some("blue"),
+        "cyan" => Some("cyan"),
+        "green" => Some("green"),
+        "magenta" => Some("magenta"),
+        "red" => Some("red"),
+        "yellow" => Some("yellow"),
+        "white" => Some("white"),
+        "darkBlue" => Some("blue!50!black"),
+        "darkCyan" => Some("teal"),
+        "darkGreen" => Some("green!50!black"),
+        "darkMagenta" => Some("purple"),
+        "darkRed" => Some("red!50!black"),
+        "darkYellow" => Some("olive"),
+        "darkGray" => Some("darkgray"),
+        "lightGray" => Some("lightgray"),
+        _ => None,
+    }
+}
+
+/// Chooses \RL or \LR for a run whose direction differs from the one of its paragraph.
+/// Without `w:rtl`, a run in a right-to-left paragraph is only taken as left-to-right
+/// when it has no right-to-left letters, so that numbers and Latin words keep their order.
+pub fn direction(paragraph_rtl: bool, run_rtl: bool, text: &str) -> Option<&'static str> {
+    let right_to_left = |c: char| matches!(c, '\u{0590}'..='\u{08FF}' | '\u{FB1D}'..='\u{FDFF}' | '\u{FE70}'..='\u{FEFF}');
+    match (paragraph_rtl, run_rtl) {
+        (false, true) => Some("RL"),
+        (true, false)
+            if text.chars().any(char::is_alphanumeric) && !text.chars().any(right_to_left) =>
+        {
+            Some("LR")
+        }
+        _ => None,
+    }
+}
+
+/// Maps a `w:outlineLvl` to a sectioning command, level 9 and others past the fifth are body text
+pub fn heading(level: usize) -> Option<&'static str> {
+    [
+        "section",
+        "subsection",
+        "subsubsection",
+        "paragraph",
+        "subparagraph",
+    ]
+    .get(level)
+    .copied()
+}
+
+/// Converts a `w:spacing` line into a command that sets the line spacing.
+/// "auto" lines are 240ths of a single line, others are twentieths of a point.
+pub fn line_spacing(line: &str, rule: Option<&str>) -> Option<String> {
+    let line = line.parse::<f64>().ok()?;
+    match rule.unwrap_or("auto") {
+        "auto" => Some(format!("\\setstretch{{{}}}", line / 240.0)),
+        "exact" | "atLeast" => Some(format!(
+            "\\setlength{{\\baselineskip}}{{{}pt}}",
+            line / 20.0
+        )),
+        _ => None,
+    }
+}
+
+/// Maps the `m:chr` of a `m:naryPr` to an operator, unknown glyphs keep operator spacing and limits
+pub fn nary(chr: Option<&str>) -> String {
+    let Some(chr) = chr else {
+        return "\\int".to_string();
+    };
+    match chr {
+        "⋀" => "\\bigwedge".to_string(),
+        "⋁" => "\\bigvee".to_string(),
+        "⋂" => "\\bigcap".to_string(),
+        "⋃" => "\\bigcup".to_string(),
+        "∐" => "\\coprod".to_string(),
+        "∏" => "\\prod".to_string(),
+        "∑" => "\\sum".to_string(),
+        "∮" => "\\oint".to_string(),
+        glyph => format!("\\mathop{{{glyph}}}"),
+    }
+}
+
+/// Names that latex and amsmath define as operators
+const FUNCTIONS: &[&str] = &[
+    "arccos", "arcsin", "arctan", "arg", "cos", "cosh", "cot", "coth", "csc", "deg", "det", "dim",
+    "exp", "gcd", "hom", "inf", "ker", "lg", "lim", "liminf", "limsup", "ln", "log", "max", "min",
+    "Pr", "sec", "sin", "sinh", "sup", "tan", "tanh",
+];
+
+/// Maps the base of a limit to an operator, when it is the name of a function such as lim
+pub fn operator(base: &str) -> Option<String> {
+    FUNCTIONS.contains(&base.trim()).then(|| function(base))
+}
+
+/// Maps the text of a `m:fName` to an operator
+pub fn function(name: &str) -> String {
+    let name = name.trim();
+    if FUNCTIONS.contains(&name) {
+        format!("\\{name}")
+    } else {
+        format!("\\operatorname{{{name}}}")
+    }
+}
+
+/// Maps the character of a `m:groupChr` to a command that draws it above or below
+pub fn group_character(chr: &str, top: bool) -> &'static str {
+    match (chr, top) {
+        ("\u{2192}", true) => "overrightarrow",
+        ("\u{2192}", false) => "underrightarrow",
+        ("\u{2190}", true) => "overleftarrow",
+        ("\u{2190}", false) => "underleftarrow",
+        ("\u{2194}", true) => "overleftrightarrow",
+        ("\u{2194}", false) => "underleftrightarrow",
+        (_, true) => "overbrace",
+        (_, false) => "underbrace",
+    }
+}
+
+/// Column specification of a `tabular`, proportioned to the grid when the table width is known
+pub fn columns(count: usize, width: Option<f64>, grid: &[f64]) -> String {
+    let total: f64 = grid.iter().sum();
+    match width {
+        // w:gridCol mirrors the widths of the cells, which are only known after the specification
+        Some(width) if total > 0.0 => {
+            let mut columns = String::new();
+            for column in grid {
+                columns.push_str(&format!("|p{{{:.2}\\textwidth}}", width * column / total));
+            }
+            columns + "|"
+        }
+        _ => "|l".repeat(count) + "|",
+    }
+}
+
+/// Most columns a table in Word can have
+const MAX_COLUMNS: usize = 63;
+
+/// Column specification of a `tabular` with no grid, for rows of any number of cells.
+/// Columns that no row reaches take up no space, and neither do their rules.
+pub fn open_columns() -> String {
+    format!("|*{{{MAX_COLUMNS}}}{{l|}}")
+}
+
+/// Column specification of a `tabular` from the shares of its cells in a table that is `width` of the text width.
+/// A table without a width fills the text width
+pub fn cell_columns(shares: &[f64], width: Option<f64>) -> String {
+    let mut columns = String::new();
+    for share in shares {
+        columns.push_str(&format!(
+            "|p{{{:.2}\\textwidth}}",
+            width.unwrap_or(1.0) * share
+        ));
+    }
+    columns + "|"
+}
+
+/// Column specification of a `tabular` whose columns keep the widths of the grid, in twentieths of a point
+pub fn fixed_columns(grid: &[f64]) -> String {
+    let mut columns = String::new();
+    for column in grid {
+        columns.push_str(&format!("|p{{{:.2}pt}}", column / 20.0));
+    }
+    columns + "|"
+}
+
+/// Counters of the levels of nested `enumerate` environments
+const ENUMERATE_COUNTERS: [&str; 4] = ["enumi", "enumii", "enumiii", "enumiv"];
+
+/// Maps a `w:numFmt` to the command that formats a counter
+fn number_format(format: &str) -> &'static str {
+    match format {
+        "lowerLetter" => "alph",
+        "upperLetter" => "Alph",
+        "lowerRoman" => "roman",
+        "upperRoman" => "Roman",
+        _ => "arabic",
+    }
+}
+
+/// Turns on the line numbers of lineno the way `w:lnNumType` numbers the lines of a section,
+/// which is counting anew on every page unless `restart` says otherwise
+pub fn line_numbers(count_by: Option<u64>, restart: Option<&str>) -> String {
+    let mut command = match count_by {
+        Some(count_by) if count_by > 1 => format!("\\modulolinenumbers[{count_by}]"),
+        _ => String::new(),
+    };
+    command += match restart {
+        Some("continuous") => "\\runninglinenumbers",
+        Some("newSection") => "\\resetlinenumber\\runninglinenumbers",
+        _ => "\\pagewiselinenumbers",
+    };
+    command
+}
+
+/// Converts the `w:lvlText` of a list level into an enumitem label,
+/// when the label also holds the numbers of outer levels, as in 1.1.1
+pub fn list_label(levels: &[Level], level: usize) -> Option<String> {
+    let current = levels.get(level)?;
+    let mut label = String::new();
+    let mut composite = false;
+    let mut chars = current.text.chars();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            label.push_str(&escape(&c.to_string(), &false, &Substitutions::default()));
+            continue;
+        }
+        // "%1" is the number of the first level
+        let referenced = chars.next()?.to_digit(10)?.checked_sub(1)? as usize;
+        let format = match current.legal {
+            true => "arabic",
+            false => number_format(&levels.get(referenced)?.format),
+        };
+        if referenced == level {
+            label.push_str(&format!("\\{format}*"));
+        } else {
+            // bulleted levels are itemize, which leaves the enumerate counters alone
+            let depth = levels
+                .get(..referenced)?
+                .iter()
+                .filter(|level| level.format != "bullet")
+                .count();
+            let counter = ENUMERATE_COUNTERS.get(depth)?;
+            label.push_str(&format!("\\{format}{{{counter}}}"));
+            composite = true;
+        }
+    }
+    composite.then_some(label)
+}
+
+/// Maps a Greek letter to latex for math mode, letters that look Latin map to Latin ones
+pub fn greek(c: char) -> Option<&'static str> {
+    let letter = match c {
+        'α' => "\\alpha",
+        'β' => "\\beta",
+        'γ' => "\\gamma",
+        'δ' => "\\delta",
+        'ε' => "\\varepsilon",
+        'ζ' => "\\zeta",
+        'η' => "\\eta",
+        'θ' => "\\theta",
+        'ι' => "\\iota",
+        'κ' => "\\kappa",
+        'λ' => "\\lambda",
+        'μ' => "\\mu",
+        'ν' => "\\nu",
+        'ξ' => "\\xi",
+        'ο' => "o",
+        'π' => "\\pi",
+        'ρ' => "\\rho",
+        'ς' => "\\varsigma",
+        'σ' => "\\sigma",
+        'τ' => "\\tau",
+        'υ' => "\\upsilon",
+        'φ' => "\\varphi",
+        'χ' => "\\chi",
+        'ψ' => "\\psi",
+        'ω' => "\\omega",
+        'ϵ' => "\\epsilon",
+        'ϑ' => "\\vartheta",
+        'ϕ' => "\\phi",
+        'ϖ' => "\\varpi",
+        'ϱ' => "\\varrho",
+        'Α' => "A",
+        'Β' => "B",
+        'Γ' => "\\Gamma",
+        'Δ' => "\\Delta",
+        'Ε' => "E",
+        'Ζ' => "Z",
+        'Η' => "H",
+        'Θ' => "\\Theta",
+        'Ι' => "I",
+        'Κ' => "K",
+        'Λ' => "\\Lambda",
+        'Μ' => "M",
+        'Ν' => "N",
+        'Ξ' => "\\Xi",
+        'Ο' => "O",
+        'Π' => "\\Pi",
+        'Ρ' => "P",
+        'Σ' => "\\Sigma",
+        'Τ' => "T",
+        'Υ' => "\\Upsilon",
+        'Φ' => "\\Phi",
+        'Χ' => "X",
+        'Ψ' => "\\Psi",
+        'Ω' => "\\Omega",
+        _ => return None,
+    };
+    Some(letter)
+}
+
+/// Maps an arrow or a relation to a command for math mode
+pub fn relation(c: char) -> Option<&'static str> {
+    let relation = match c {
+        '\u{2192}' => "\\rightarrow",
+        '\u{2190}' => "\\leftarrow",
+        '\u{2194}' => "\\leftrightarrow",
+        '\u{21D2}' => "\\Rightarrow",
+        '\u{21D0}' => "\\Leftarrow",
+        '\u{21D4}' => "\\Leftrightarrow",
+        '\u{21A6}' => "\\mapsto",
+        '\u{2264}' => "\\leq",
+        '\u{2265}' => "\\geq",
+        '\u{2260}' => "\\neq",
+        '\u{2248}' => "\\approx",
+        '\u{2261}' => "\\equiv",
+        '\u{221D}' => "\\propto",
+        '\u{2208}' => "\\in",
+        '\u{2209}' => "\\notin",
+        '\u{220B}' => "\\ni",
+        '\u{2282}' => "\\subset",
+        '\u{2283}' => "\\supset",
+        '\u{2286}' => "\\subseteq",
+        '\u{2287}' => "\\supseteq",
+        _ => return None,
+    };
+    Some(relation)
+}
+
+/// Maps a relation typed as two ASCII characters to its symbol, \coloneqq needs mathtools
+pub fn ascii_relation(first: char, second: char) -> Option<&'static str> {
+    let relation = match (first, second) {
+        (':', '=') => "\\coloneqq",
+        ('<', '=') => "\\leq",
+        ('>', '=') => "\\geq",
+        ('!', '=') => "\\neq",
+        ('-', '>') => "\\to",
+        _ => return None,
+    };
+    Some(relation)
+}
+
+/// Maps an opening or closing character of `m:d` to a delimiter for \left and \right
+pub fn delimiter(chr: &str) -> String {
+    match chr {
+        "" => ".".to_string(),
+        "{" => "\\{".to_string(),
+        "}" => "\\}".to_string(),
+        "\u{2016}" => "\\|".to_string(),
+        "\u{27E8}" | "\u{2329}" => "\\langle ".to_string(),
+        "\u{27E9}" | "\u{232A}" => "\\rangle ".to_string(),
+        "\u{230A}" => "\\lfloor ".to_string(),
+        "\u{230B}" => "\\rfloor ".to_string(),
+        "\u{2308}" => "\\lceil ".to_string(),
+        "\u{2309}" => "\\rceil ".to_string(),
+        chr => chr.to_string(),
+    }
+}
+
+/// Maps a character that separates the elements of `m:d` to latex, if it is a separator
+pub fn separator(chr: &str) -> Option<&'static str> {
+    match chr {
+        "|" | "\u{2502}" => Some("|"),
+        "\u{2223}" => Some("\\mid "),
+        "\u{2016}" => Some("\\| "),
+        "," => Some(","),
+        ";" => Some(";"),
+        ":" => Some(":"),
+        _ => None,
+    }
+}
+
+/// Makes a separator grow with the \left and \right around it, when it is a bar
+pub fn middle(separator: &str) -> String {
+    match separator {
+        "|" | "\\mid " => "\\middle|".to_string(),
+        "\\| " => "\\middle\\|".to_string(),
+        separator => separator.to_string(),
+    }
+}
+
+/// Maps the type from `m:fPr` to a command that stacks the numerator over the denominator
+pub fn fraction(kind: &str) -> &'static str {
+    match kind {
+        "skw" => "\\sfrac",
+        // parentheses of a binomial coefficient come from the m:d around the fraction
+        "noBar" => "\\genfrac{}{}{0pt}{}",
+        _ => "\\frac",
+    }
+}
+
+/// Maps a combining character from `m:accPr` to an accent command
+pub fn accent(chr: &str) -> &'static str {
+    match chr {
+        "\u{0302}" => "hat",
+        "\u{0304}" | "\u{0305}" => "bar",
+        "\u{20D7}" => "vec",
+        "\u{0307}" => "dot",
+        "\u{0308}" => "ddot",
+        "\u{0303}" => "tilde",
+        "\u{0301}" => "acute",
+        "\u{0300}" => "grave",
+        "\u{0306}" => "breve",
+        "\u{030C}" => "check",
+        _ => {
+            log::warn!("Unknown accent {chr:?}, using \\hat instead");
+            "hat"
+        }
+    }
+}
+
+/// English Metric Units, in which DrawingML measures lengths
+const EMU_PER_INCH: f64 = 914400.0;
+
+/// Extensions of the files that Word embeds as pictures
+const IMAGE_EXTENSIONS: &[&str] = &[
+    "png", "jpg", "jpeg", "gif", "bmp", "tif", "tiff", "emf", "wmf", "svg", "pdf", "eps",
+];
+
+/// Width of a drawing that is `extent` EMU wide, as a share of the text width when that is known.
+/// Drawings without an extent, and those wider than the text, fill the text width
+pub fn width(extent: Option<u64>, text_width: Option<u64>) -> String {
+    match (extent, text_width) {
+        (Some(extent), Some(text_width)) if extent < text_width => format!(
+            "{}\\textwidth",
+            (extent as f64 / text_width as f64 * 100.0).round() / 100.0
+        ),
+        (Some(extent), None) => format!(
+            "{}in",
+            (extent as f64 / EMU_PER_INCH * 100.0).round() / 100.0
+        ),
+        _ => "\\textwidth".to_string(),
+    }
+}
+
 pub fn drawing<W: Write>(
     buf_writer: &mut BufWriter<W>,
-    rels: &HashMap<String, String>,
+    rels: &HashMap<String, Target>,
     rel: &String,
+    description: Option<&str>,
+    width: &str,
 ) -> std::io::Result<State> {
-    if let Some(path) = rels.get(rel) {
-        let path = std::path::PathBuf::from(path);
+    let description = description.filter(|description| !description.is_empty());
+    if let Some(Target::External(url)) = rels.get(rel) {
+        // a linked picture is not part of the package, so there is no file in media to include
+        log::warn!(
+            "Drawing relationship {rel:?} links to an image outside of the package: {url:?}"
+        );
+        let url = self::url(url);
+        match description {
+            Some(description) => write!(
+                buf_writer,
+                "\\href{{{url}}}{{\\fbox{{[Image: {description}]}}}}"
+            )?,
+            None => write!(buf_writer, "\\href{{{url}}}{{\\fbox{{[Image]}}}}")?,
+        }
+        Ok(State::Happy)
+    } else if let Some(Target::Internal(target)) = rels.get(rel) {
+        let path = std::path::PathBuf::from(target);
+        // a target such as "media/" or ".." names no file, though Path finds one in the former
+        let Some(file_name) = path.file_name().filter(|_| !target.ends_with(['/', '\\'])) else {
+            log::error!("Drawing relationship {rel:?} does not point to a file: {target:?}");
+            writeln!(buf_writer, "% missing image")?;
+            return Ok(State::RelationshipMissing);
+        };
+        // a corrupt relationship may point at another part of the package, such as a header
+        let image = path.extension().is_some_and(|extension| {
+            let extension = extension.to_string_lossy().to_lowercase();
+            IMAGE_EXTENSIONS.contains(&extension.as_str())
+        });
+        if !image {
+            log::warn!("Drawing relationship {rel:?} does not point to an image: {target:?}");
+            match description {
+                Some(description) => write!(buf_writer, "\\fbox{{[Image: {description}]}}")?,
+                None => writeln!(buf_writer, "% missing image")?,
+            }
+            return Ok(State::RelationshipMissing);
+        }
+        // svg files are converted by the svg package, as graphicx can not read them
+        let command = if path.extension().is_some_and(|extension| extension == "svg") {
+            "includesvg"
+        } else {
+            "includegraphics"
+        };
+        // the height follows from the width, so the aspect ratio is kept
+        // main.rs copies word/media into a flat media folder next to the document,
+        // whatever the prefix of the relationship target
         write!(
             buf_writer,
-            "\\includegraphics[width=\\textwidth]{{{:?}}}",
-            path.file_stem()
-                .expect("Rels did not point to an image file")
+            "\\{command}[width={width}]{{media/{}}}",
+            file_name.to_string_lossy()
         )?;
         Ok(State::Happy)
     } else {
@@ -47,12 +548,113 @@ pub fn drawing<W: Write>(
             "Drawing relies on a relationship that does not exist: {:?}",
             rel
         );
+        // the alt text still tells the reader what was there
+        if let Some(description) = description {
+            write!(buf_writer, "\\fbox{{[Image: {description}]}}")?;
+        }
         Ok(State::RelationshipMissing)
     }
 }
 
+/// Braces the commas between digits, which latex would otherwise space as punctuation in math
+pub fn decimal_comma(math: &str) -> String {
+    let chars: Vec<char> = math.chars().collect();
+    let mut latex = String::with_capacity(math.len());
+    for (i, &c) in chars.iter().enumerate() {
+        let between_digits = i > 0
+            && chars[i - 1].is_ascii_digit()
+            && chars.get(i + 1).is_some_and(char::is_ascii_digit);
+        if c == ',' && between_digits {
+            latex.push_str("{,}");
+        } else {
+            latex.push(c);
+        }
+    }
+    latex
+}
+
+/// Latex font families by the names of fonts that stand out from the body text.
+/// Times New Roman and Calibri are left out, as documents name their default fonts on every run
+const FONT_FAMILIES: &[(&str, &str)] = &[
+    ("Consolas", "texttt"),
+    ("Courier", "texttt"),
+    ("Courier New", "texttt"),
+    ("Lucida Console", "texttt"),
+    ("Cascadia Code", "texttt"),
+    ("Cascadia Mono", "texttt"),
+    ("Source Code Pro", "texttt"),
+    ("Menlo", "texttt"),
+    ("Monaco", "texttt"),
+    ("Georgia", "textrm"),
+    ("Garamond", "textrm"),
+    ("Book Antiqua", "textrm"),
+    ("Arial", "textsf"),
+    ("Helvetica", "textsf"),
+    ("Verdana", "textsf"),
+    ("Tahoma", "textsf"),
+    ("Segoe UI", "textsf"),
+];
+
+/// Maps the name of a font from `w:rFonts` to a latex font family, unknown fonts keep the body font
+pub fn font_family(font: &str) -> Option<&'static str> {
+    FONT_FAMILIES
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(font))
+        .map(|&(_, family)| family)
+}
+
+/// Leaves out the separator that Word types between the number of a caption and its text,
+/// such as ": " after "Table 1"
+pub fn caption(text: &str) -> &str {
+    text.trim_start_matches([':', '.', '-', '\u{2013}', '\u{2014}', ' '])
+}
+
+pub fn citation<W: Write>(buf_writer: &mut BufWriter<W>, source: &str) -> std::io::Result<State> {
+    write!(buf_writer, "\\cite{{{source}}}")?;
+    Ok(State::Happy)
+}
+
+/// Bookmarks are labelled with their sanitized anchor, see `anchor`
+pub fn page_reference<W: Write>(
+    buf_writer: &mut BufWriter<W>,
+    bookmark: &str,
+) -> std::io::Result<State> {
+    write!(buf_writer, "\\pageref{{{}}}", anchor(bookmark))?;
+    Ok(State::Happy)
+}
+
+/// Word writes the text of the bookmark, but its label is all latex knows of it
+pub fn reference<W: Write>(
+    buf_writer: &mut BufWriter<W>,
+    bookmark: &str,
+) -> std::io::Result<State> {
+    write!(buf_writer, "\\ref{{{}}}", anchor(bookmark))?;
+    Ok(State::Happy)
+}
+
+/// Sources are only known by their tags, so those double as the entries' text
+pub fn bibliography<W: Write>(
+    buf_writer: &mut BufWriter<W>,
+    sources: &[String],
+) -> std::io::Result<State> {
+    if sources.is_empty() {
+        return Ok(State::Happy);
+    }
+    writeln!(
+        buf_writer,
+        "\\begin{{thebibliography}}{{{}}}",
+        sources.len()
+    )?;
+    for source in sources {
+        writeln!(buf_writer, "\\bibitem{{{source}}} {source}")?;
+    }
+    writeln!(buf_writer, "\\end{{thebibliography}}")?;
+    Ok(State::Happy)
+}
+
 #[cfg(test)]
 mod test {
+    use rstest::rstest;
     use std::io::{Read, Write};
 
     fn drain<W: Write>(buf_writer: &mut std::io::BufWriter<W>) -> std::io::Result<String> {
@@ -77,11 +679,41 @@ mod test {
         assert_eq!(drain(&mut buf_writer).unwrap(), "\\hyperlink{Anchor}{Content}");
     }
 
+    #[test]
+    fn hyperlink_with_anchor_is_sanitized() {
+        let mut buf_writer = super::BufWriter::new(Vec::new());
+        let rels = super::HashMap::new();
+        let link = super::Link::Anchor("Chapter 1#2".to_string());
+        let content = "Content".to_string();
+
+        let state = super::hyperlink(&mut buf_writer, &rels, (&link, &content));
+        assert!(state.is_ok());
+        assert_eq!(state.unwrap(), super::State::Happy);
+
+        assert_eq!(
+            drain(&mut buf_writer).unwrap(),
+            "\\hyperlink{Chapter-1-2}{Content}"
+        );
+    }
+
+    #[test]
+    fn anchor_keeps_safe_characters() {
+        assert_eq!(super::anchor("_Toc123:a.b-c"), "_Toc123:a.b-c");
+    }
+
+    #[test]
+    fn anchor_replaces_unsafe_characters() {
+        assert_eq!(super::anchor("a b%c#d{e}"), "a-b-c-d-e-");
+    }
+
     #[test]
     fn hyperlink_with_present_relationship_works() {
         let mut buf_writer = super::BufWriter::new(Vec::new());
         let mut rels = super::HashMap::new();
-        rels.insert("TestKey".to_string(), "TestValue".to_string());
+        rels.insert(
+            "TestKey".to_string(),
+            super::Target::External("TestValue".to_string()),
+        );
         let link = super::Link::Relationship("TestKey".to_string());
         let content = "Content".to_string();
 
@@ -94,6 +726,33 @@ mod test {
         assert_eq!(drain(&mut buf_writer).unwrap(), "\\href{TestValue}{Content}");
     }
 
+    #[test]
+    fn hyperlink_escapes_url() {
+        let mut buf_writer = super::BufWriter::new(Vec::new());
+        let rels = super::HashMap::from([(
+            "TestKey".to_string(),
+            super::Target::External("https://example.com/a%20b?x=1&y=2#fragment".to_string()),
+        )]);
+        let link = super::Link::Relationship("TestKey".to_string());
+        let content = "Content".to_string();
+
+        let state = super::hyperlink(&mut buf_writer, &rels, (&link, &content));
+        assert_eq!(state.unwrap(), super::State::Happy);
+
+        assert_eq!(
+            drain(&mut buf_writer).unwrap(),
+            "\\href{https://example.com/a\\%20b?x=1\\&y=2\\#fragment}{Content}"
+        );
+    }
+
+    #[rstest]
+    #[case("https://example.com/a%20b", "https://example.com/a\\%20b")]
+    #[case("page.html#fragment", "page.html\\#fragment")]
+    #[case("mailto:me@example.com", "mailto:me@example.com")]
+    fn url_escapes_special_characters(#[case] raw: &str, #[case] url: &str) {
+        assert_eq!(super::url(raw), url);
+    }
+
     #[test]
     fn hyperlink_recognizes_missing_relationship() {
         let mut buf_writer = super::BufWriter::new(Vec::new());
@@ -110,18 +769,438 @@ mod test {
         assert_eq!(drain(&mut buf_writer).unwrap(), "Content");
     }
 
+    #[test]
+    fn highlight_color_maps_word_names() {
+        assert_eq!(super::highlight_color("yellow"), Some("yellow"));
+        assert_eq!(super::highlight_color("darkCyan"), Some("teal"));
+        assert_eq!(super::highlight_color("lightGray"), Some("lightgray"));
+    }
+
+    #[test]
+    fn highlight_color_skips_none() {
+        assert_eq!(super::highlight_color("none"), None);
+        assert_eq!(super::highlight_color("Junk"), None);
+    }
+
+    #[test]
+    fn line_spacing_stretches_auto_lines() {
+        assert_eq!(
+            super::line_spacing("360", Some("auto")).unwrap(),
+            "\\setstretch{1.5}"
+        );
+        assert_eq!(super::line_spacing("240", None).unwrap(), "\\setstretch{1}");
+    }
+
+    #[test]
+    fn line_spacing_sets_exact_points() {
+        assert_eq!(
+            super::line_spacing("280", Some("exact")).unwrap(),
+            "\\setlength{\\baselineskip}{14pt}"
+        );
+        assert_eq!(
+            super::line_spacing("300", Some("atLeast")).unwrap(),
+            "\\setlength{\\baselineskip}{15pt}"
+        );
+    }
+
+    #[test]
+    fn line_spacing_rejects_junk() {
+        assert!(super::line_spacing("Junk", None).is_none());
+        assert!(super::line_spacing("240", Some("Junk")).is_none());
+    }
+
+    #[rstest]
+    #[case('→', Some("\\rightarrow"))]
+    #[case('⇐', Some("\\Leftarrow"))]
+    #[case('≥', Some("\\geq"))]
+    #[case('∉', Some("\\notin"))]
+    #[case('=', None)]
+    fn relation_maps_symbols(#[case] c: char, #[case] latex: Option<&str>) {
+        assert_eq!(super::relation(c), latex);
+    }
+
+    #[rstest]
+    #[case(false, false, "Hello", None)]
+    #[case(false, true, "שלום", Some("RL"))]
+    #[case(true, false, "2024", Some("LR"))]
+    #[case(true, false, "שלום", None)]
+    #[case(true, false, " ", None)]
+    #[case(true, true, "2024", None)]
+    fn direction_opposes_paragraph(
+        #[case] paragraph_rtl: bool,
+        #[case] run_rtl: bool,
+        #[case] text: &str,
+        #[case] direction: Option<&str>,
+    ) {
+        assert_eq!(super::direction(paragraph_rtl, run_rtl, text), direction);
+    }
+
+    #[rstest]
+    #[case(0, Some("section"))]
+    #[case(4, Some("subparagraph"))]
+    #[case(9, None)]
+    fn heading_maps_outline_levels(#[case] level: usize, #[case] command: Option<&str>) {
+        assert_eq!(super::heading(level), command);
+    }
+
+    #[rstest]
+    #[case(':', '=', Some("\\coloneqq"))]
+    #[case('-', '>', Some("\\to"))]
+    #[case('!', '=', Some("\\neq"))]
+    #[case('=', '>', None)]
+    fn ascii_relation_maps_pairs(
+        #[case] first: char,
+        #[case] second: char,
+        #[case] latex: Option<&str>,
+    ) {
+        assert_eq!(super::ascii_relation(first, second), latex);
+    }
+
+    #[rstest]
+    #[case('α', Some("\\alpha"))]
+    #[case('ω', Some("\\omega"))]
+    #[case('Σ', Some("\\Sigma"))]
+    #[case('Α', Some("A"))]
+    #[case('q', None)]
+    fn greek_maps_letters(#[case] c: char, #[case] latex: Option<&str>) {
+        assert_eq!(super::greek(c), latex);
+    }
+
+    #[rstest]
+    #[case(3, None, &[], "|l|l|l|")]
+    #[case(2, Some(1.0), &[3000.0, 7000.0], "|p{0.30\\textwidth}|p{0.70\\textwidth}|")]
+    #[case(2, Some(0.5), &[1.0, 1.0], "|p{0.25\\textwidth}|p{0.25\\textwidth}|")]
+    #[case(2, Some(1.0), &[0.0, 0.0], "|l|l|")]
+    fn columns_are_proportioned(
+        #[case] count: usize,
+        #[case] width: Option<f64>,
+        #[case] grid: &[f64],
+        #[case] latex: &str,
+    ) {
+        assert_eq!(super::columns(count, width, grid), latex);
+    }
+
+    #[rstest]
+    #[case(&["%1.", "%1.%2."], 1, false, Some("\\arabic{enumi}.\\arabic*."))]
+    #[case(&["%1.", "%1.%2.", "%1.%2.%3."], 2, false, Some("\\arabic{enumi}.\\arabic{enumii}.\\arabic*."))]
+    #[case(&["%1.", "%2)"], 1, false, None)]
+    #[case(&["%1.", "%1.%2."], 0, false, None)]
+    #[case(&["%1.", "(%1-%2)"], 1, true, Some("(\\arabic{enumi}-\\arabic*)"))]
+    fn list_label_composes_levels(
+        #[case] texts: &[&str],
+        #[case] level: usize,
+        #[case] legal: bool,
+        #[case] latex: Option<&str>,
+    ) {
+        let levels: Vec<_> = texts
+            .iter()
+            .map(|text| super::Level {
+                text: text.to_string(),
+                legal,
+                ..Default::default()
+            })
+            .collect();
+        assert_eq!(super::list_label(&levels, level).as_deref(), latex);
+    }
+
+    #[test]
+    fn list_label_formats_outer_levels() {
+        let levels = [
+            super::Level {
+                format: "upperRoman".to_string(),
+                text: "%1.".to_string(),
+                legal: false,
+            },
+            super::Level {
+                format: "lowerLetter".to_string(),
+                text: "%1.%2".to_string(),
+                legal: false,
+            },
+        ];
+        assert_eq!(
+            super::list_label(&levels, 1).as_deref(),
+            Some("\\Roman{enumi}.\\alph*")
+        );
+    }
+
+    #[test]
+    fn list_label_skips_bulleted_levels() {
+        let level = |format: &str, text: &str| super::Level {
+            format: format.to_string(),
+            text: text.to_string(),
+            legal: false,
+        };
+        let levels = [
+            level("bullet", "•"),
+            level("decimal", "%2."),
+            level("bullet", "◦"),
+            level("lowerLetter", "%2.%4)"),
+        ];
+        assert_eq!(
+            super::list_label(&levels, 3).as_deref(),
+            Some("\\arabic{enumi}.\\alph*)")
+        );
+    }
+
+    #[rstest]
+    #[case(&[1440.0, 2880.0], "|p{72.00pt}|p{144.00pt}|")]
+    #[case(&[], "|")]
+    fn fixed_columns_keep_grid_widths(#[case] grid: &[f64], #[case] latex: &str) {
+        assert_eq!(super::fixed_columns(grid), latex);
+    }
+
+    #[rstest]
+    #[case("[", "[")]
+    #[case("{", "\\{")]
+    #[case("\u{2016}", "\\|")]
+    #[case("", ".")]
+    fn delimiter_maps_characters(#[case] chr: &str, #[case] latex: &str) {
+        assert_eq!(super::delimiter(chr), latex);
+    }
+
+    #[rstest]
+    #[case(Some("⋀"), "\\bigwedge")]
+    #[case(Some("⋁"), "\\bigvee")]
+    #[case(Some("⋂"), "\\bigcap")]
+    #[case(Some("⋃"), "\\bigcup")]
+    #[case(Some("∐"), "\\coprod")]
+    #[case(Some("∏"), "\\prod")]
+    #[case(Some("∑"), "\\sum")]
+    #[case(Some("∮"), "\\oint")]
+    #[case(Some("⨁"), "\\mathop{⨁}")]
+    #[case(None, "\\int")]
+    fn nary_maps_operators(#[case] chr: Option<&str>, #[case] latex: &str) {
+        assert_eq!(super::nary(chr), latex);
+    }
+
+    #[rstest]
+    #[case("sin", "\\sin")]
+    #[case("cos", "\\cos")]
+    #[case("tan", "\\tan")]
+    #[case("log", "\\log")]
+    #[case("lim", "\\lim")]
+    #[case("sgn", "\\operatorname{sgn}")]
+    fn function_maps_names(#[case] name: &str, #[case] latex: &str) {
+        assert_eq!(super::function(name), latex);
+    }
+
+    #[rstest]
+    #[case("lim", Some("\\lim"))]
+    #[case(" max ", Some("\\max"))]
+    #[case("x", None)]
+    fn operator_maps_function_names_only(#[case] base: &str, #[case] latex: Option<&str>) {
+        assert_eq!(super::operator(base).as_deref(), latex);
+    }
+
+    #[rstest]
+    #[case("\u{23DF}", false, "underbrace")]
+    #[case("\u{23DE}", true, "overbrace")]
+    #[case("\u{2192}", true, "overrightarrow")]
+    #[case("\u{2190}", false, "underleftarrow")]
+    fn group_character_maps_characters(#[case] chr: &str, #[case] top: bool, #[case] latex: &str) {
+        assert_eq!(super::group_character(chr, top), latex);
+    }
+
+    #[test]
+    fn accent_maps_combining_characters() {
+        assert_eq!(super::accent("\u{0302}"), "hat");
+        assert_eq!(super::accent("\u{0304}"), "bar");
+        assert_eq!(super::accent("\u{20D7}"), "vec");
+        assert_eq!(super::accent("\u{0307}"), "dot");
+        assert_eq!(super::accent("\u{0303}"), "tilde");
+    }
+
+    #[test]
+    fn accent_defaults_to_hat() {
+        assert_eq!(super::accent("Junk"), "hat");
+    }
+
     #[test]
     fn drawing_with_present_relationship_works() {
         let mut buf_writer = super::BufWriter::new(Vec::new());
         let mut rels = super::HashMap::new();
-        rels.insert("Key".to_string(), "value.test".to_string());
+        rels.insert(
+            "Key".to_string(),
+            super::Target::Internal("media/image1.png".to_string()),
+        );
 
-        let state = super::drawing(&mut buf_writer, &rels, &"Key".to_string());
+        let state = super::drawing(
+            &mut buf_writer,
+            &rels,
+            &"Key".to_string(),
+            None,
+            "\\textwidth",
+        );
         assert!(state.is_ok());
         let state = state.unwrap();
         assert_eq!(state, super::State::Happy);
 
-        assert_eq!(drain(&mut buf_writer).unwrap(), "\\includegraphics[width=\\textwidth]{\"value\"}");
+        assert_eq!(
+            drain(&mut buf_writer).unwrap(),
+            "\\includegraphics[width=\\textwidth]{media/image1.png}"
+        );
+    }
+
+    #[rstest]
+    #[case("media/image3.jpeg")]
+    #[case("/word/media/image3.jpeg")]
+    #[case("image3.jpeg")]
+    fn drawing_points_into_media_folder(#[case] target: &str) {
+        let mut buf_writer = super::BufWriter::new(Vec::new());
+        let rels = super::HashMap::from([(
+            "Key".to_string(),
+            super::Target::Internal(target.to_string()),
+        )]);
+
+        let state = super::drawing(
+            &mut buf_writer,
+            &rels,
+            &"Key".to_string(),
+            None,
+            "\\textwidth",
+        );
+        assert_eq!(state.unwrap(), super::State::Happy);
+
+        assert_eq!(
+            drain(&mut buf_writer).unwrap(),
+            "\\includegraphics[width=\\textwidth]{media/image3.jpeg}"
+        );
+    }
+
+    #[rstest]
+    #[case(914400, "1in")]
+    #[case(457200, "0.5in")]
+    #[case(2743199, "3in")]
+    fn drawing_keeps_extent_width(#[case] extent: u64, #[case] width: &str) {
+        let mut buf_writer = super::BufWriter::new(Vec::new());
+        let mut rels = super::HashMap::new();
+        rels.insert(
+            "Key".to_string(),
+            super::Target::Internal("media/image1.png".to_string()),
+        );
+
+        let state = super::drawing(
+            &mut buf_writer,
+            &rels,
+            &"Key".to_string(),
+            None,
+            &super::width(Some(extent), None),
+        );
+        assert_eq!(state.unwrap(), super::State::Happy);
+
+        assert_eq!(
+            drain(&mut buf_writer).unwrap(),
+            format!("\\includegraphics[width={width}]{{media/image1.png}}")
+        );
+    }
+
+    #[test]
+    fn drawing_with_svg_uses_svg_package() {
+        let mut buf_writer = super::BufWriter::new(Vec::new());
+        let mut rels = super::HashMap::new();
+        rels.insert(
+            "Key".to_string(),
+            super::Target::Internal("media/image2.svg".to_string()),
+        );
+
+        let state = super::drawing(
+            &mut buf_writer,
+            &rels,
+            &"Key".to_string(),
+            None,
+            "\\textwidth",
+        );
+        assert!(state.is_ok());
+        assert_eq!(state.unwrap(), super::State::Happy);
+
+        assert_eq!(
+            drain(&mut buf_writer).unwrap(),
+            "\\includesvg[width=\\textwidth]{media/image2.svg}"
+        );
+    }
+
+    #[rstest]
+    #[case(Some(2865755), Some(5731510), "0.5\\textwidth")]
+    #[case(Some(1910503), Some(5731510), "0.33\\textwidth")]
+    #[case(Some(7315200), Some(5731510), "\\textwidth")]
+    #[case(Some(914400), None, "1in")]
+    #[case(None, Some(5731510), "\\textwidth")]
+    fn width_is_share_of_text_width(
+        #[case] extent: Option<u64>,
+        #[case] text_width: Option<u64>,
+        #[case] width: &str,
+    ) {
+        assert_eq!(super::width(extent, text_width), width);
+    }
+
+    #[rstest]
+    #[case("3,14", "3{,}14")]
+    #[case("f(x,y)", "f(x,y)")]
+    #[case("1, 2", "1, 2")]
+    #[case("1,5,2", "1{,}5{,}2")]
+    fn decimal_comma_braces_commas_between_digits(#[case] math: &str, #[case] latex: &str) {
+        assert_eq!(super::decimal_comma(math), latex);
+    }
+
+    #[rstest]
+    #[case("Consolas", Some("texttt"))]
+    #[case("courier new", Some("texttt"))]
+    #[case("Arial", Some("textsf"))]
+    #[case("Georgia", Some("textrm"))]
+    #[case("Calibri", None)]
+    fn font_family_maps_known_fonts(#[case] font: &str, #[case] family: Option<&str>) {
+        assert_eq!(super::font_family(font), family);
+    }
+
+    #[rstest]
+    #[case(None, None, "\\pagewiselinenumbers")]
+    #[case(Some(1), Some("continuous"), "\\runninglinenumbers")]
+    #[case(
+        Some(10),
+        Some("newSection"),
+        "\\modulolinenumbers[10]\\resetlinenumber\\runninglinenumbers"
+    )]
+    fn line_numbers_follow_count_and_restart(
+        #[case] count_by: Option<u64>,
+        #[case] restart: Option<&str>,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(super::line_numbers(count_by, restart), expected);
+    }
+
+    #[rstest]
+    #[case(": Results", "Results")]
+    #[case(" \u{2013} Setup", "Setup")]
+    #[case("", "")]
+    #[case("Revenue 2024 by region", "Revenue 2024 by region")]
+    #[case("Results", "Results")]
+    fn caption_leaves_out_separator(#[case] text: &str, #[case] expected: &str) {
+        assert_eq!(super::caption(text), expected);
+    }
+
+    #[rstest]
+    #[case(None, "\\href{https://example.com/cat.png}{\\fbox{[Image]}}")]
+    #[case(
+        Some("A cat"),
+        "\\href{https://example.com/cat.png}{\\fbox{[Image: A cat]}}"
+    )]
+    fn drawing_links_external_image(#[case] description: Option<&str>, #[case] expected: &str) {
+        let mut buf_writer = super::BufWriter::new(Vec::new());
+        let rels = super::HashMap::from([(
+            "Key".to_string(),
+            super::Target::External("https://example.com/cat.png".to_string()),
+        )]);
+
+        let state = super::drawing(
+            &mut buf_writer,
+            &rels,
+            &"Key".to_string(),
+            description,
+            "\\textwidth",
+        );
+        assert_eq!(state.unwrap(), super::State::Happy);
+
+        assert_eq!(drain(&mut buf_writer).unwrap(), expected);
     }
 
     #[test]
@@ -129,11 +1208,143 @@ mod test {
         let mut buf_writer = super::BufWriter::new(Vec::new());
         let rels = super::HashMap::new();
 
-        let state = super::drawing(&mut buf_writer, &rels, &"Key".to_string());
+        let state = super::drawing(
+            &mut buf_writer,
+            &rels,
+            &"Key".to_string(),
+            None,
+            "\\textwidth",
+        );
         assert!(state.is_ok());
         let state = state.unwrap();
         assert_eq!(state, super::State::RelationshipMissing);
 
         assert_eq!(drain(&mut buf_writer).unwrap(), "");
     }
+
+    #[rstest]
+    #[case("media/")]
+    #[case("..")]
+    #[case("")]
+    fn drawing_recognizes_target_without_file(#[case] target: &str) {
+        let mut buf_writer = super::BufWriter::new(Vec::new());
+        let rels = super::HashMap::from([(
+            "Key".to_string(),
+            super::Target::Internal(target.to_string()),
+        )]);
+
+        let state = super::drawing(
+            &mut buf_writer,
+            &rels,
+            &"Key".to_string(),
+            None,
+            "\\textwidth",
+        );
+        assert_eq!(state.unwrap(), super::State::RelationshipMissing);
+
+        assert_eq!(drain(&mut buf_writer).unwrap(), "% missing image\n");
+    }
+
+    #[rstest]
+    #[case("header1.xml", None, "% missing image\n")]
+    #[case("media/image1", None, "% missing image\n")]
+    #[case("../customXml/item1.xml", Some("A cat"), "\\fbox{[Image: A cat]}")]
+    fn drawing_rejects_target_that_is_not_an_image(
+        #[case] target: &str,
+        #[case] description: Option<&str>,
+        #[case] expected: &str,
+    ) {
+        let mut buf_writer = super::BufWriter::new(Vec::new());
+        let rels = super::HashMap::from([(
+            "Key".to_string(),
+            super::Target::Internal(target.to_string()),
+        )]);
+
+        let state = super::drawing(
+            &mut buf_writer,
+            &rels,
+            &"Key".to_string(),
+            description,
+            "\\textwidth",
+        );
+        assert_eq!(state.unwrap(), super::State::RelationshipMissing);
+
+        assert_eq!(drain(&mut buf_writer).unwrap(), expected);
+    }
+
+    #[test]
+    fn drawing_describes_missing_relationship() {
+        let mut buf_writer = super::BufWriter::new(Vec::new());
+        let rels = super::HashMap::new();
+
+        let state = super::drawing(
+            &mut buf_writer,
+            &rels,
+            &"Key".to_string(),
+            Some("A cat"),
+            "\\textwidth",
+        );
+        assert!(state.is_ok());
+        assert_eq!(state.unwrap(), super::State::RelationshipMissing);
+
+        assert_eq!(drain(&mut buf_writer).unwrap(), "\\fbox{[Image: A cat]}");
+    }
+
+    #[test]
+    fn citation_works() {
+        let mut buf_writer = super::BufWriter::new(Vec::new());
+
+        let state = super::citation(&mut buf_writer, "Smi20");
+        assert!(state.is_ok());
+        assert_eq!(state.unwrap(), super::State::Happy);
+
+        assert_eq!(drain(&mut buf_writer).unwrap(), "\\cite{Smi20}");
+    }
+
+    #[test]
+    fn page_reference_is_sanitized() {
+        let mut buf_writer = super::BufWriter::new(Vec::new());
+
+        let state = super::page_reference(&mut buf_writer, "_Toc 1");
+        assert!(state.is_ok());
+        assert_eq!(state.unwrap(), super::State::Happy);
+
+        assert_eq!(drain(&mut buf_writer).unwrap(), "\\pageref{_Toc-1}");
+    }
+
+    #[test]
+    fn reference_is_sanitized() {
+        let mut buf_writer = super::BufWriter::new(Vec::new());
+
+        let state = super::reference(&mut buf_writer, "_Ref 1");
+        assert_eq!(state.unwrap(), super::State::Happy);
+
+        assert_eq!(drain(&mut buf_writer).unwrap(), "\\ref{_Ref-1}");
+    }
+
+    #[test]
+    fn bibliography_lists_sources() {
+        let mut buf_writer = super::BufWriter::new(Vec::new());
+        let sources = vec!["Smi20".to_string(), "Doe21".to_string()];
+
+        let state = super::bibliography(&mut buf_writer, &sources);
+        assert!(state.is_ok());
+        assert_eq!(state.unwrap(), super::State::Happy);
+
+        assert_eq!(
+            drain(&mut buf_writer).unwrap(),
+            "\\begin{thebibliography}{2}\n\\bibitem{Smi20} Smi20\n\\bibitem{Doe21} Doe21\n\\end{thebibliography}\n"
+        );
+    }
+
+    #[test]
+    fn bibliography_skips_no_sources() {
+        let mut buf_writer = super::BufWriter::new(Vec::new());
+
+        let state = super::bibliography(&mut buf_writer, &[]);
+        assert!(state.is_ok());
+        assert_eq!(state.unwrap(), super::State::Happy);
+
+        assert_eq!(drain(&mut buf_writer).unwrap(), "");
+    }
 }
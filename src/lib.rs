@@ -1,7 +1,7 @@
 use std::{
-    collections::HashMap,
-    fs::File,
+    collections::{BTreeSet, HashMap},
     io::{BufReader, BufWriter, Read, Write},
+    ops::Range,
 };
 
 use xml::{
@@ -13,10 +13,21 @@ use xml::{
 mod latex;
 mod ooxml;
 mod peekaboo;
+mod report;
+mod styles;
 mod tag;
 
 use peekaboo::{Boo, Peek};
-use tag::{normalize, InputError, Link, Tag};
+pub use report::{ConversionReport, Diagnostic, DiagnosticKind, Severity};
+pub use styles::{styles, CharacterStyle, ParagraphStyle, Style, StyleId};
+pub use tag::Tag;
+use tag::{normalize, FieldChar, InputError, Link, Spacing, Width};
+
+/// Written for every `w:tab` in a run, as tab stops are not taken into account
+const TAB: &str = "\\hspace{2em}";
+
+/// Length of a twip in EMU, as `w:pgSz` and `w:pgMar` measure in twips
+const EMU_PER_TWIP: u64 = 635;
 
 fn blink(value: bool) -> Option<()> {
     if value {
@@ -26,47 +37,641 @@ fn blink(value: bool) -> Option<()> {
     }
 }
 
+/// Where a relationship points, as told by its `TargetMode`
+#[derive(Clone, Debug, PartialEq)]
+pub enum Target {
+    /// A part of the package, such as "media/image1.png"
+    Internal(String),
+    /// A url outside of the package
+    External(String),
+}
+
+impl Target {
+    /// The path or url, whatever the mode
+    pub fn value(&self) -> &str {
+        match self {
+            Target::Internal(value) | Target::External(value) => value,
+        }
+    }
+}
+
 pub fn relationships<R: Read>(
     parser: &mut EventReader<BufReader<R>>,
-) -> Result<HashMap<String, String>, xml::reader::Error> {
+) -> Result<HashMap<String, Target>, xml::reader::Error> {
     let mut count = 0;
-    let mut rels = HashMap::<String, String>::default();
+    let mut rels = HashMap::<String, Target>::default();
     loop {
         match parser.next() {
             Ok(XmlEvent::StartElement {
                 name, attributes, ..
-            }) => {
-                match name.local_name.as_str() {
-                    "Relationships" => continue,
-                    "Relationship" => {
-                        count += 1;
-                        let id = attributes.iter().find(|&a| normalize(&a.name) == "Id");
-                        let target = attributes.iter().find(|&a| normalize(&a.name) == "Target");
-                        if id.is_none() && target.is_none() {
-                            log::error!(
-                                "Relationship #{count} is missing attributes 'Id' and 'Target'"
-                            );
-                        } else if id.is_none() {
-                            log::error!("Relationship #{count} is missing attribute 'Id'");
-                        } else if target.is_none() {
-                            log::error!("Relationship #{count} is missing attribute 'Target'");
+            }) => match name.local_name.as_str() {
+                "Relationships" => continue,
+                "Relationship" => {
+                    count += 1;
+                    let id = attributes.iter().find(|&a| normalize(&a.name) == "Id");
+                    let target = attributes.iter().find(|&a| normalize(&a.name) == "Target");
+                    if id.is_none() && target.is_none() {
+                        log::error!(
+                            "Relationship #{count} is missing attributes 'Id' and 'Target'"
+                        );
+                    } else if id.is_none() {
+                        log::error!("Relationship #{count} is missing attribute 'Id'");
+                    } else if target.is_none() {
+                        log::error!("Relationship #{count} is missing attribute 'Target'");
+                    } else if let (Some(id), Some(target)) = (id, target) {
+                        let external = attributes
+                            .iter()
+                            .any(|a| normalize(&a.name) == "TargetMode" && a.value == "External");
+                        let target = if external {
+                            Target::External(target.value.clone())
                         } else {
-                            // if id.is_some() && target.is_some()
-                            let id = id.expect("Id was previously confirmed to be Some");
-                            let target =
-                                target.expect("Target was previously confirmed to be Some");
-                            rels.insert(id.value.clone(), target.value.clone());
+                            Target::Internal(target.value.clone())
+                        };
+                        rels.insert(id.value.clone(), target);
+                    }
+                }
+                x => log::warn!("Unknown entry in Relationships: {x:?}"),
+            },
+            Ok(XmlEvent::EndDocument) => break,
+            Ok(_) => continue,
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(rels)
+}
+
+/// A level of a list from `word/numbering.xml`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Level {
+    /// Value of `w:numFmt`, such as "decimal" or "bullet"
+    pub format: String,
+    /// Value of `w:lvlText`, where "%1" stands for the number of the first level
+    pub text: String,
+    /// `w:isLgl` makes every number of the label decimal, as in 1.1.1
+    pub legal: bool,
+}
+
+impl Default for Level {
+    fn default() -> Self {
+        Self {
+            format: "decimal".to_string(),
+            text: String::new(),
+            legal: false,
+        }
+    }
+}
+
+/// Reads `word/numbering.xml` into the levels of every list, by the `w:numId` that paragraphs refer to
+pub fn numbering<R: Read>(
+    parser: &mut EventReader<BufReader<R>>,
+) -> Result<HashMap<String, Vec<Level>>, xml::reader::Error> {
+    let mut abstracts = HashMap::<String, Vec<Level>>::default();
+    let mut nums = HashMap::<String, String>::default();
+    let mut abstract_id = None;
+    let mut level = None;
+    let mut num_id = None;
+    loop {
+        match parser.next() {
+            Ok(XmlEvent::StartElement {
+                name, attributes, ..
+            }) => {
+                let find = |name: &str| {
+                    attributes
+                        .iter()
+                        .find(|&a| normalize(&a.name) == name)
+                        .map(|a| a.value.clone())
+                };
+                let levels = abstract_id.as_ref().and_then(|id| abstracts.get_mut(id));
+                let current = levels
+                    .zip(level)
+                    .and_then(|(levels, level)| levels.get_mut(level));
+                match (normalize(&name).as_str(), current) {
+                    ("w:abstractNum", _) => {
+                        abstract_id = find("w:abstractNumId");
+                        if let Some(id) = &abstract_id {
+                            abstracts.insert(id.clone(), Vec::new());
+                        }
+                    }
+                    ("w:lvl", _) => {
+                        level = find("w:ilvl").and_then(|ilvl| ilvl.parse::<usize>().ok());
+                        let levels = abstract_id.as_ref().and_then(|id| abstracts.get_mut(id));
+                        if let (Some(levels), Some(level)) = (levels, level) {
+                            if levels.len() <= level {
+                                levels.resize(level + 1, Level::default());
+                            }
+                        }
+                    }
+                    ("w:numFmt", Some(current)) => {
+                        if let Some(format) = find("w:val") {
+                            current.format = format;
+                        }
+                    }
+                    ("w:lvlText", Some(current)) => {
+                        if let Some(text) = find("w:val") {
+                            current.text = text;
+                        }
+                    }
+                    ("w:isLgl", Some(current)) => {
+                        current.legal =
+                            !matches!(find("w:val").as_deref(), Some("0" | "false" | "off"));
+                    }
+                    ("w:num", _) => num_id = find("w:numId"),
+                    ("w:abstractNumId", _) => {
+                        if let (Some(id), Some(abstract_id)) = (&num_id, find("w:val")) {
+                            nums.insert(id.clone(), abstract_id);
                         }
                     }
-                    x => log::warn!("Unknown entry in Relationships: {x:?}"),
+                    _ => {}
                 }
             }
-            Ok(XmlEvent::EndDocument { .. }) => break,
+            Ok(XmlEvent::EndElement { name }) => match normalize(&name).as_str() {
+                "w:abstractNum" => abstract_id = None,
+                "w:lvl" => level = None,
+                "w:num" => num_id = None,
+                _ => {}
+            },
+            Ok(XmlEvent::EndDocument) => break,
             Ok(_) => continue,
             Err(e) => return Err(e),
         }
     }
-    Ok(rels)
+    Ok(nums
+        .into_iter()
+        .filter_map(|(num_id, abstract_id)| Some((num_id, abstracts.get(&abstract_id)?.clone())))
+        .collect())
+}
+
+/// Properties of the document from `docProps/core.xml`, escaped for latex
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Metadata {
+    /// Value of `dc:title`
+    pub title: Option<String>,
+    /// Value of `dc:creator`
+    pub author: Option<String>,
+    /// Date part of `dcterms:created`, such as "2024-03-01"
+    pub date: Option<String>,
+}
+
+/// Reads `docProps/core.xml` into the metadata that the title block is made of
+pub fn metadata<R: Read>(
+    parser: &mut EventReader<BufReader<R>>,
+) -> Result<Metadata, xml::reader::Error> {
+    let mut metadata = Metadata::default();
+    let mut current = None;
+    loop {
+        match parser.next() {
+            Ok(XmlEvent::StartElement { name, .. }) => current = Some(normalize(&name)),
+            Ok(XmlEvent::EndElement { .. }) => current = None,
+            Ok(XmlEvent::Characters(content)) => {
+                let content = content.trim();
+                if content.is_empty() {
+                    continue;
+                }
+                let value = escape(content, &false, &Substitutions::default());
+                match current.as_deref() {
+                    Some("dc:title") => metadata.title = Some(value),
+                    Some("dc:creator") => metadata.author = Some(value),
+                    Some("dcterms:created") => {
+                        // W3CDTF, as in 2024-03-01T10:00:00Z
+                        let date = value
+                            .split_once('T')
+                            .map_or(value.as_str(), |(date, _)| date);
+                        metadata.date = Some(date.to_string());
+                    }
+                    _ => {}
+                }
+            }
+            Ok(XmlEvent::EndDocument) => break,
+            Ok(_) => continue,
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(metadata)
+}
+
+/// Markup without a conversion that holds nothing of the content, such as proofing marks,
+/// the geometry of drawings and their placement on the page
+const IGNORABLE: [&str; 28] = [
+    "w:proofErr",
+    "w:lastRenderedPageBreak",
+    "w:noProof",
+    "w:permStart",
+    "w:permEnd",
+    "wp:effectExtent",
+    "wp:simplePos",
+    "wp:positionH",
+    "wp:positionV",
+    "wp:wrapNone",
+    "wp:wrapSquare",
+    "wp:wrapTight",
+    "wp:wrapThrough",
+    "wp:wrapTopAndBottom",
+    "wp14:sizeRelH",
+    "wp14:sizeRelV",
+    "a:stretch",
+    "a:tile",
+    "a:srcRect",
+    "a:fillRect",
+    "a:xfrm",
+    "a:off",
+    "a:ext",
+    "a:extLst",
+    "a:prstGeom",
+    "a:avLst",
+    "a:ln",
+    "a:noFill",
+];
+
+/// Settings that change how a document is converted
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Options {
+    /// Paragraphs in the Title style become \title followed by \maketitle
+    pub title_page: bool,
+    /// Delimiters written around equations
+    pub math_delimiters: MathDelimiters,
+    /// Latex for characters of the document, starting out with the built-in escapes
+    pub substitutions: Substitutions,
+    /// Hyperlinks show their `w:tooltip` through \pdftooltip of the pdfcomment package
+    pub tooltips: bool,
+    /// Runs of two or more spaces in text are kept as control spaces instead of collapsing into one
+    pub preserve_spaces: bool,
+    /// Drawings that are hidden in Word are written all the same
+    pub include_hidden: bool,
+    /// Tables are scaled down to the width of the text by the adjustbox package
+    pub fit_tables: bool,
+    /// An element without a conversion aborts it, unless it only tunes the look of its parent or holds nothing of the content
+    pub strict: bool,
+    /// Paragraphs that hold nothing but a drawing are centered as figures
+    pub figures: bool,
+    /// A comma between digits in an equation is a decimal separator, which latex spaces like a point
+    pub decimal_comma: bool,
+}
+
+impl Options {
+    /// Writes `latex` in place of every `c` of the document, both in text and in equations
+    pub fn substitute(mut self, c: char, latex: impl Into<String>) -> Self {
+        let latex = latex.into();
+        self.substitutions.text.insert(c, latex.clone());
+        self.substitutions.math.insert(c, latex);
+        self
+    }
+}
+
+/// Characters that latex reserves, with their latex in text and in equations
+const ESCAPES: [(char, &str, &str); 15] = [
+    ('∞', "\\infty ", "\\infty "),
+    // a space after a control symbol is typeset, unlike after a control word
+    ('&', "\\&", "\\&"),
+    ('\\', "\\textbackslash ", "\\backslash "),
+    ('<', "\\textless ", "<"),
+    ('>', "\\textgreater ", ">"),
+    ('%', "\\%", "\\%"),
+    ('$', "\\$", "\\$"),
+    ('{', "\\{", "\\{"),
+    ('#', "\\#", "\\#"),
+    ('}', "\\}", "\\}"),
+    // {} ends the command without a space, which would be typeset after the symbol
+    ('~', "\\textasciitilde{}", "\\sim "),
+    ('^', "\\textasciicircum{}", "^"),
+    ('_', "\\_", "\\_"),
+    ('±', "\\pm ", "\\pm "),
+    ('∓', "\\mp ", "\\mp "),
+];
+
+/// Latex for characters of the document, separately for text and for equations
+#[derive(Clone, Debug, PartialEq)]
+pub struct Substitutions {
+    /// Latex for characters of running text
+    pub text: HashMap<char, String>,
+    /// Latex for characters of equations
+    pub math: HashMap<char, String>,
+}
+
+impl Default for Substitutions {
+    fn default() -> Self {
+        let text = ESCAPES.iter().map(|&(c, text, _)| (c, text.to_string()));
+        let math = ESCAPES.iter().map(|&(c, _, math)| (c, math.to_string()));
+        Substitutions {
+            text: text.collect(),
+            math: math.collect(),
+        }
+    }
+}
+
+impl Substitutions {
+    /// Latex for `c`, either in an equation or in text
+    pub fn get(&self, c: char, math_mode: bool) -> Option<&str> {
+        let map = if math_mode { &self.math } else { &self.text };
+        map.get(&c).map(String::as_str)
+    }
+}
+
+/// How equations are delimited in the output
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum MathDelimiters {
+    /// `$$ ... $$` around display equations and `$ ... $` around inline ones
+    #[default]
+    Dollars,
+    /// `\[ ... \]` around display equations and `\( ... \)` around inline ones, as amsmath recommends
+    DisplayMath,
+}
+
+/// Conversion state carried between events of a single `document.xml`
+#[derive(Debug, Default, PartialEq)]
+struct Context {
+    options: Options,
+    /// Number of `m:oMathPara` and `m:oMath` that are currently open
+    math_depth: usize,
+    /// The outermost equation is a `m:oMath` that is not part of a `m:oMathPara`
+    math_inline: bool,
+    /// A `w:br` was seen, but nothing has been written after it yet
+    line_break: bool,
+    /// Fields that are currently open, innermost last
+    fields: Vec<Field>,
+    /// Tags of sources referenced by CITATION fields, in order of first appearance
+    citations: Vec<String>,
+    /// Tables that are currently open, innermost last
+    tables: Vec<Table>,
+    /// Levels of every list from `word/numbering.xml`, by `w:numId`
+    numbering: HashMap<String, Vec<Level>>,
+    /// Lists that are currently open, outermost first
+    lists: Vec<List>,
+    /// Latex of the notes from `word/footnotes.xml`, by `w:id`
+    footnotes: HashMap<String, String>,
+    /// Resolved styles from `word/styles.xml`
+    styles: HashMap<StyleId, Style>,
+    /// Latex packages required by what has been written so far
+    packages: BTreeSet<&'static str>,
+    /// N-ary operators that are currently open, innermost last
+    naries: Vec<Nary>,
+    /// Matrices that are currently open, innermost last
+    matrices: Vec<Matrix>,
+    /// Accent characters of `m:acc` that are currently open, innermost last
+    accents: Vec<String>,
+    /// Types from `m:fPr` of fractions that are currently open, innermost last
+    fractions: Vec<String>,
+    /// Commands of `m:bar` that are currently open, innermost last
+    bars: Vec<&'static str>,
+    /// Group characters that are currently open, innermost last
+    group_characters: Vec<GroupCharacter>,
+    /// Delimiters that are currently open, innermost last
+    delimiters: Vec<Delimiter>,
+    /// For every `m:deg` that is currently open, whether its `[` has been written
+    degrees: Vec<bool>,
+    /// `m:limLow` and `m:limUpp` that are currently open, innermost last
+    limits: Vec<Limit>,
+    /// Escaped `w:tooltip` of the current `w:hyperlink`
+    tooltip: Option<String>,
+    /// Math elements without a conversion, such as `m:box`, that equations were written without
+    unsupported_math: BTreeSet<String>,
+    /// Problems met so far, which `document` hands back along with the packages
+    report: ConversionReport,
+    /// Escaped alt text of the current drawing, from `wp:docPr`
+    description: Option<String>,
+    /// The current drawing is not shown in Word, from the `hidden` attribute of `wp:docPr`
+    hidden: bool,
+    /// Width of the current drawing in EMU, from `wp:extent`
+    extent: Option<u64>,
+    /// Drawings record where their width is, for `document` to turn into a share of the text width
+    section_widths: bool,
+    /// Where drawings wrote their width in the current section, along with their extent in EMU
+    widths: Vec<(Range<usize>, u64)>,
+    /// Relationship of an svg image that replaces the raster of the current `a:blip`
+    svg: Option<String>,
+    /// Properties of the current `w:p`
+    paragraph: Paragraph,
+    /// Properties of the current `w:r`
+    run: Run,
+}
+
+impl Context {
+    fn in_replaced_field(&self) -> bool {
+        self.fields.iter().any(|field| field.replaced)
+    }
+
+    fn color_run(&mut self, color: &str) {
+        if color != "auto" {
+            self.run.color = Some(color.to_string());
+        }
+    }
+
+    fn highlight_run(&mut self, highlight: &str) {
+        if let Some(highlight) = latex::highlight_color(highlight) {
+            self.run.highlight = Some(highlight);
+        }
+    }
+
+    fn align_run(&mut self, vert_align: &str) {
+        self.run.script = match vert_align {
+            "superscript" => Some("textsuperscript"),
+            "subscript" => Some("textsubscript"),
+            // "baseline" undoes the alignment of the run's style
+            _ => None,
+        };
+    }
+}
+
+/// A `m:limLow` or `m:limUpp`, whose limit follows its base in OMML but comes first in latex
+#[derive(Debug, Default, PartialEq)]
+struct Limit {
+    /// The base takes the limit by itself, as a function name or a group character does
+    bare: bool,
+    /// Where the latex of the base begins
+    base: usize,
+    /// Where the latex of the limit begins, once it has
+    limit: usize,
+}
+
+/// A `m:nary`, whose operator is written right before its limits or body
+#[derive(Debug, Default, PartialEq)]
+struct Nary {
+    /// Operator glyph from `m:naryPr`, an integral when absent
+    chr: Option<String>,
+    /// The operator was written
+    written: bool,
+}
+
+/// A `m:d`, whose elements are separated by its separator character
+#[derive(Debug, PartialEq)]
+struct Delimiter {
+    /// Opening character from `m:dPr`
+    begin: Option<String>,
+    /// Closing character from `m:dPr`
+    end: Option<String>,
+    /// Latex for the separator from `m:dPr`
+    separator: String,
+    /// Elements started so far
+    elements: usize,
+    /// A separator is due before anything else is written in the current element
+    pending: bool,
+    /// Something was written in the current element
+    written: bool,
+    /// The previous element held nothing but a separator
+    separated: bool,
+}
+
+impl Default for Delimiter {
+    fn default() -> Self {
+        Self {
+            // a vertical bar is implied when m:dPr has no m:sepChr
+            separator: "|".to_string(),
+            begin: None,
+            end: None,
+            elements: 0,
+            pending: false,
+            written: false,
+            separated: false,
+        }
+    }
+}
+
+impl Delimiter {
+    /// Writes a separator, which grows with the delimiter when it is written with \left and \right
+    fn write_separator<W: Write>(
+        &self,
+        buf_writer: &mut BufWriter<W>,
+        separator: &str,
+    ) -> std::io::Result<()> {
+        if self.begin.is_some() || self.end.is_some() {
+            write!(buf_writer, "{}", latex::middle(separator))
+        } else {
+            write!(buf_writer, "{separator}")
+        }
+    }
+}
+
+/// A `m:groupChr`, such as a brace under an expression
+#[derive(Debug, PartialEq)]
+struct GroupCharacter {
+    chr: String,
+    /// Drawn above the expression rather than below it
+    top: bool,
+}
+
+impl Default for GroupCharacter {
+    fn default() -> Self {
+        Self {
+            // a bottom curly bracket is implied when m:groupChrPr has no m:chr
+            chr: "\u{23DF}".to_string(),
+            top: false,
+        }
+    }
+}
+
+/// A `m:m` that is being written as a `matrix`
+#[derive(Debug, Default, PartialEq)]
+struct Matrix {
+    /// Rows written so far
+    rows: usize,
+    /// Elements written in the current row
+    elements: usize,
+}
+
+/// Properties from `w:pPr` that apply to the whole paragraph
+#[derive(Debug, Default, PartialEq)]
+struct Paragraph {
+    /// Closings of the groups and environments that the paragraph is wrapped in, innermost last
+    closings: Vec<&'static str>,
+    /// The paragraph is the argument of \title
+    title: bool,
+    /// A tab stop has a dotted leader, as in a table of contents line
+    dotted: bool,
+    /// Level of the list item from `w:ilvl`
+    level: usize,
+    /// The paragraph is an item of a list
+    numbered: bool,
+    /// Level from `w:outlineLvl` of the paragraph or its style, 0 being the top heading
+    outline_level: Option<usize>,
+    /// The paragraph is the argument of a sectioning command
+    heading: bool,
+    /// The paragraph is wrapped in the RTL environment
+    rtl: bool,
+    /// Something was written into the paragraph, so a drawing after it is not a figure
+    content: bool,
+    /// The drawing that opened the paragraph, held back until the paragraph ends
+    /// with nothing else in it, which makes it a figure
+    figure: Option<Drawing>,
+    /// The paragraph has the Caption style, which names a table right before or after it
+    caption: bool,
+    /// Where the text of a caption begins, after the label and the number of its `SEQ` field,
+    /// which \caption writes itself
+    label: Option<usize>,
+    /// Whitespace at the end of the text written so far, left out if the paragraph ends after it
+    trailing: String,
+}
+
+/// Properties from `w:rPr` that apply to the text of a run
+#[derive(Debug, Default, PartialEq)]
+struct Run {
+    /// Hex RGB value, never "auto"
+    color: Option<String>,
+    /// Name of an xcolor color
+    highlight: Option<&'static str>,
+    /// Either \textsuperscript or \textsubscript
+    script: Option<&'static str>,
+    /// Either \texttt, \textrm or \textsf, from the font of the run
+    family: Option<&'static str>,
+    /// The run is a footnote mark, which \footnote numbers by itself
+    footnote_mark: bool,
+    /// The text of the run is right-to-left, from `w:rtl`
+    rtl: bool,
+    /// The current `w:t` has `xml:space="preserve"`, so whitespace around its text is kept
+    preserve_space: bool,
+}
+
+/// A level of a list that is being written as an `enumerate` or `itemize`
+#[derive(Debug, Default, PartialEq)]
+struct List {
+    /// `w:numId` of the paragraphs in the list
+    id: String,
+    environment: &'static str,
+}
+
+/// A `w:tbl` that is being written as a `tabular`
+#[derive(Debug, Default, PartialEq)]
+struct Table {
+    columns: usize,
+    /// Fraction of the text width, when `w:tblW` is a percentage
+    width: Option<f64>,
+    /// Widths of `w:gridCol`, in twentieths of a point
+    grid: Vec<f64>,
+    /// Value of `w:tblLayout`, either "fixed" or "autofit"
+    layout: Option<String>,
+    /// Written as a `tabularx`, whose X columns share the width of the table
+    tabularx: bool,
+    /// Cells written in the current row
+    cells: usize,
+    /// Paragraphs written in the current cell
+    paragraphs: usize,
+    /// Content of the current cell is wrapped in a \rotatebox
+    rotated: bool,
+    /// The `tabular` has been begun, either after `w:tblGrid` or at the first row
+    opened: bool,
+    /// Where the column specification was written, to be replaced once the cells give their widths
+    specification: Option<Range<usize>>,
+    /// Shares of the table width of the cells in the first row, when `w:tcW` is a percentage
+    shares: Vec<f64>,
+    /// Wrapped in an adjustbox, which has to end along with the table
+    adjusted: bool,
+}
+
+/// A field from either `w:fldSimple` or a `w:fldChar` sequence
+#[derive(Debug, Default, PartialEq)]
+struct Field {
+    /// Field code, such as " CITATION Smi20 \l 1033 "
+    instr: String,
+    /// The field code is complete, and what follows is the cached result
+    result: bool,
+    /// The field was converted, so its cached result must not be written
+    replaced: bool,
+}
+
+/// Latex of a drawing that is yet to be written
+#[derive(Debug, Default, PartialEq)]
+struct Drawing {
+    latex: String,
+    /// Where the width is in the latex, along with the extent of the drawing in EMU
+    width: Option<(Range<usize>, u64)>,
 }
 
 #[derive(Debug, PartialEq)]
@@ -74,76 +679,361 @@ enum State {
     OpenedTag(Tag),
     ClosedTag,
     FoundContent(String),
-    AttributesMissing,
     RelationshipMissing,
     Happy,
     End,
 }
 
-fn start_element<W: Write>(
-    buf_writer: &mut BufWriter<W>,
+fn start_element<P: Peek<Item = Tag>>(
+    buf_writer: &mut BufWriter<Vec<u8>>,
+    stack: &P,
     name: &OwnedName,
     attributes: &Vec<OwnedAttribute>,
-    math_mode: &mut bool,
-    nary_has_chr: &mut Option<bool>,
+    context: &mut Context,
 ) -> std::io::Result<State> {
     let tag = Tag::try_from((name, attributes));
 
     if let Err(InputError::MissingAttributes { id, missing }) = &tag {
         log::error!("Tag '{id}' is missing attributes: {missing:?}");
-        return Ok(State::AttributesMissing);
+        let kind = DiagnosticKind::MissingAttributes(missing.clone());
+        context.report.push(Severity::Error, kind, id);
+        // the tag still closes, so it takes its place on the stack without being converted
+        return Ok(State::OpenedTag(Tag::Unknown { id: id.clone() }));
     }
 
     let tag = tag.expect("Error case was handled");
 
+    if matches!(
+        tag,
+        Tag::MDelim
+            | Tag::MRad
+            | Tag::MSub
+            | Tag::MSup
+            | Tag::MNary
+            | Tag::MFraction
+            | Tag::MFunc
+            | Tag::MMatrix
+            | Tag::MAcc
+            | Tag::MBar
+            | Tag::MGroupChr
+            | Tag::MLimLow
+            | Tag::MLimUpp
+            | Tag::MPre
+    ) {
+        // these write before any text of their own, so an element that starts with them is no separator
+        degree_opening(buf_writer, context)?;
+        delimiter_separator(buf_writer, context)?;
+    }
+
+    if matches!(stack.last(), Some(Tag::WParagraph))
+        && !matches!(tag, Tag::WParagraphPr)
+        && !context.paragraph.numbered
+        && context.tables.is_empty()
+    {
+        // a paragraph without w:numPr ends the lists before it
+        close_lists(buf_writer, context, 0)?;
+    }
+
+    if matches!(stack.last(), Some(Tag::MNary))
+        && matches!(tag, Tag::MSub | Tag::MSup | Tag::MElement)
+    {
+        // limits and body of an n-ary are attached to its operator
+        if let Some(nary) = context.naries.last_mut().filter(|nary| !nary.written) {
+            write!(buf_writer, "{}", latex::nary(nary.chr.as_deref()))?;
+            nary.written = true;
+        }
+    }
+
+    if matches!(tag, Tag::MGroupChr) && ooxml::limit_element(stack).is_some() {
+        // a brace that begins the base takes the limit as its label, as in \underbrace{a+b}_{n}
+        let start = offset(buf_writer);
+        if let Some(limit) = context
+            .limits
+            .last_mut()
+            .filter(|limit| limit.base == start)
+        {
+            limit.bare = true;
+        }
+    }
+
     match &tag {
-        Tag::MoMathPara => {
-            if *math_mode {
-                log::error!("Entering Math Mode multiple times");
-            } else {
-                *math_mode = true;
-                write!(buf_writer, "$$")?;
+        Tag::MoMathPara | Tag::MoMath => {
+            // only the outermost one opens math mode, so nested equations stay in a single region
+            if context.math_depth == 0 {
+                paragraph_content(buf_writer, context)?;
+                context.math_inline = matches!(tag, Tag::MoMath);
+                match (context.options.math_delimiters, context.math_inline) {
+                    (MathDelimiters::Dollars, false) => write!(buf_writer, "$$")?,
+                    (MathDelimiters::Dollars, true) => write!(buf_writer, "$")?,
+                    (MathDelimiters::DisplayMath, false) => write!(buf_writer, "\\[")?,
+                    (MathDelimiters::DisplayMath, true) => write!(buf_writer, "\\(")?,
+                }
+            }
+            context.math_depth += 1;
+        }
+        Tag::WHyperlink(_) => {
+            // Link only keeps the target, the tooltip is taken from the attributes directly
+            context.tooltip = attributes
+                .iter()
+                .find(|attribute| normalize(&attribute.name) == "w:tooltip")
+                .map(|tooltip| escape(&tooltip.value, &false, &context.options.substitutions));
+        }
+        Tag::WPExtent { width } => {
+            context.extent = width.as_deref().and_then(|width| width.parse().ok());
+        }
+        Tag::WPDocPr { description } => {
+            context.description = description
+                .as_deref()
+                .map(|description| escape(description, &false, &context.options.substitutions));
+            context.hidden = attributes.iter().any(|attribute| {
+                normalize(&attribute.name) == "hidden"
+                    && matches!(attribute.value.as_str(), "1" | "true")
+            });
+        }
+        Tag::MDelim => context.delimiters.push(Delimiter::default()),
+        Tag::MBegChr { value } if matches!(stack.last(), Some(Tag::MDelimPr)) => {
+            if let Some(delimiter) = context.delimiters.last_mut() {
+                delimiter.begin = Some(value.clone());
+            }
+        }
+        Tag::MEndChr { value } if matches!(stack.last(), Some(Tag::MDelimPr)) => {
+            if let Some(delimiter) = context.delimiters.last_mut() {
+                delimiter.end = Some(value.clone());
+            }
+        }
+        Tag::MSepChr { value } if matches!(stack.last(), Some(Tag::MDelimPr)) => {
+            if let Some(delimiter) = context.delimiters.last_mut() {
+                delimiter.separator = latex::separator(value).map_or_else(
+                    || escape(value, &true, &context.options.substitutions),
+                    |separator| separator.to_string(),
+                );
+            }
+        }
+        Tag::MElement if matches!(stack.last(), Some(Tag::MDelim)) => {
+            if let Some(delimiter) = context.delimiters.last_mut() {
+                if delimiter.elements == 0 {
+                    // m:dPr comes before the first element, so the opening is known by now
+                    match (&delimiter.begin, &delimiter.end) {
+                        (None, None) => write!(buf_writer, "(")?,
+                        (begin, _) => write!(
+                            buf_writer,
+                            "\\left{}",
+                            latex::delimiter(begin.as_deref().unwrap_or("("))
+                        )?,
+                    }
+                }
+                // the separator is held back, as the element may turn out to be a separator itself
+                delimiter.pending = delimiter.elements > 0 && !delimiter.separated;
+                delimiter.separated = false;
+                delimiter.written = false;
+                delimiter.elements += 1;
             }
         }
-        Tag::MDelim => write!(buf_writer, "(")?,
         Tag::MRad => write!(buf_writer, "\\sqrt")?,
-        Tag::MDeg => write!(buf_writer, "[")?,
+        // the bracket waits for the first content, since an empty m:deg is a square root
+        Tag::MDeg => context.degrees.push(false),
         Tag::MSub => write!(buf_writer, "_{{")?,
         Tag::MSup => write!(buf_writer, "^{{")?,
-        Tag::MNaryPr => {
-            if nary_has_chr.is_none() {
-                *nary_has_chr = Some(false);
-            } else {
-                log::error!("Nested <m:naryPr> detected");
+        Tag::MAcc => {
+            // a hat is implied when m:accPr has no m:chr
+            context.accents.push("\u{0302}".to_string());
+        }
+        Tag::MChr { value } if matches!(stack.last(), Some(Tag::MAccPr)) => {
+            if let Some(accent) = context.accents.last_mut() {
+                accent.clone_from(value);
+            }
+        }
+        Tag::MElement if matches!(stack.last(), Some(Tag::MAcc)) => {
+            let accent = context
+                .accents
+                .last()
+                .map_or("hat", |chr| latex::accent(chr));
+            write!(buf_writer, "\\{accent}{{")?;
+        }
+        Tag::MBar => context.bars.push("overline"),
+        Tag::MPos { value } if matches!(stack.last(), Some(Tag::MBarPr)) => {
+            if let Some(bar) = context.bars.last_mut() {
+                *bar = if value == "bot" {
+                    "underline"
+                } else {
+                    "overline"
+                };
             }
         }
-        Tag::MChr { value } => {
-            if let Some(false) = nary_has_chr {
-                *nary_has_chr = Some(true);
-            } else if let Some(true) = nary_has_chr {
-                log::error!("<m:naryPr> has multiple <m:chr> specified");
+        Tag::MGroupChr => context.group_characters.push(GroupCharacter::default()),
+        Tag::MChr { value } if matches!(stack.last(), Some(Tag::MGroupChrPr)) => {
+            if let Some(group) = context.group_characters.last_mut() {
+                group.chr.clone_from(value);
             }
-            write!(
-                buf_writer,
-                "\\{}",
-                match value.as_str() {
-                    "⋀" => "bigwedge",
-                    "⋁" => "bigvee",
-                    "⋂" => "bigcap",
-                    "⋃" => "bigcup",
-                    "∐" => "coprod",
-                    "∏" => "prod",
-                    "∑" => "sum",
-                    "∮" => "oint",
-                    _ => "",
+        }
+        Tag::MPos { value } if matches!(stack.last(), Some(Tag::MGroupChrPr)) => {
+            if let Some(group) = context.group_characters.last_mut() {
+                group.top = value == "top";
+            }
+        }
+        Tag::MElement if matches!(stack.last(), Some(Tag::MGroupChr)) => {
+            let group = context.group_characters.last();
+            let command = group.map_or("underbrace", |group| {
+                latex::group_character(&group.chr, group.top)
+            });
+            write!(buf_writer, "\\{command}{{")?;
+        }
+        Tag::MElement if matches!(stack.last(), Some(Tag::MBar)) => {
+            let bar = context.bars.last().unwrap_or(&"overline");
+            write!(buf_writer, "\\{bar}{{")?;
+        }
+        Tag::MLimLow | Tag::MLimUpp => context.limits.push(Limit {
+            // the name of a function is written as an operator, which takes the limit as a script
+            bare: matches!(stack.last(), Some(Tag::MFName)),
+            base: offset(buf_writer),
+            limit: 0,
+        }),
+        Tag::MLim => match (stack.last(), context.limits.last_mut()) {
+            (Some(Tag::MLimLow), Some(Limit { bare: true, .. })) => write!(buf_writer, "_{{")?,
+            (Some(Tag::MLimUpp), Some(Limit { bare: true, .. })) => write!(buf_writer, "^{{")?,
+            // the base is moved after the limit once the limit ends
+            (Some(Tag::MLimLow | Tag::MLimUpp), Some(limit)) => limit.limit = offset(buf_writer),
+            _ => log::warn!("Limit is outside of m:limLow and m:limUpp"),
+        },
+        Tag::MNary => context.naries.push(Nary::default()),
+        Tag::MChr { value } if matches!(stack.last(), Some(Tag::MNaryPr)) => {
+            if let Some(nary) = context.naries.last_mut() {
+                nary.chr = Some(value.clone());
+            }
+        }
+        Tag::MElement if matches!(stack.last(), Some(Tag::MNary | Tag::MFunc)) => {
+            write!(buf_writer, "{{")?
+        }
+        Tag::WParagraph => {
+            context.paragraph = Paragraph::default();
+            if let Some(table) = context.tables.last_mut() {
+                // paragraphs can not be broken inside of a table cell
+                if table.paragraphs > 0 {
+                    write!(buf_writer, " ")?;
                 }
-            )?;
+                table.paragraphs += 1;
+            }
+        }
+        Tag::WRun => context.run = Run::default(),
+        Tag::WText => {
+            context.run.preserve_space = attributes.iter().any(|attribute| {
+                normalize(&attribute.name) == "xml:space" && attribute.value == "preserve"
+            });
+        }
+        Tag::WTable => {
+            if context.tables.is_empty() {
+                close_lists(buf_writer, context, 0)?;
+            }
+            context.tables.push(Table::default());
+        }
+        Tag::WTableRow => {
+            // a table without a grid is begun at its first row
+            if context.tables.last().is_some_and(|table| !table.opened) {
+                begin_table(buf_writer, context)?;
+            }
+            if let Some(table) = context.tables.last_mut() {
+                table.cells = 0;
+            }
+        }
+        Tag::WTableCell => {
+            if let Some(table) = context.tables.last_mut() {
+                if table.cells > 0 {
+                    write!(buf_writer, " & ")?;
+                }
+                table.cells += 1;
+                table.paragraphs = 0;
+                table.rotated = false;
+            }
+        }
+        Tag::WFootnoteReference { id } => match context.footnotes.get(id).cloned() {
+            Some(note) => {
+                paragraph_content(buf_writer, context)?;
+                write!(buf_writer, "\\footnote{{{note}}}")?
+            }
+            None => {
+                log::error!("Footnote {id:?} is missing from the footnotes");
+                let kind = DiagnosticKind::MissingFootnote(id.clone());
+                context
+                    .report
+                    .push(Severity::Error, kind, "w:footnoteReference");
+            }
+        },
+        Tag::WFieldSimple { instr } => {
+            context.fields.push(Field {
+                instr: instr.clone(),
+                result: true,
+                replaced: false,
+            });
+            field_code(buf_writer, context)?;
+        }
+        Tag::MMatrix => {
+            context.matrices.push(Matrix::default());
+            write!(buf_writer, "\\begin{{matrix}}")?;
+        }
+        Tag::MMatrixRow => {
+            if let Some(matrix) = context.matrices.last_mut() {
+                if matrix.rows > 0 {
+                    write!(buf_writer, " \\\\ ")?;
+                }
+                matrix.rows += 1;
+                matrix.elements = 0;
+            }
+        }
+        Tag::MElement if matches!(stack.last(), Some(Tag::MMatrixRow)) => {
+            if let Some(matrix) = context.matrices.last_mut() {
+                if matrix.elements > 0 {
+                    write!(buf_writer, " & ")?;
+                }
+                matrix.elements += 1;
+            }
+        }
+        // the scripts come before the base in OMML, so they are attached to an empty group
+        // instead of \prescript, which takes the superscript first
+        Tag::MPre => write!(buf_writer, "{{}}")?,
+        // a bar is implied when m:fPr has no m:type
+        Tag::MFraction => context.fractions.push("bar".to_string()),
+        Tag::MType { value } if matches!(stack.last(), Some(Tag::MFractionPr)) => {
+            if let Some(fraction) = context.fractions.last_mut() {
+                fraction.clone_from(value);
+            }
+        }
+        // m:fPr comes before the numerator, so the command is known by now
+        Tag::MNum => match context.fractions.last().map(String::as_str) {
+            Some("lin") => {}
+            Some(kind) => {
+                if kind == "skw" {
+                    context.packages.insert("xfrac");
+                }
+                write!(buf_writer, "{}{{", latex::fraction(kind))?
+            }
+            None => write!(buf_writer, "{{")?,
+        },
+        Tag::MDen => match context.fractions.last().map(String::as_str) {
+            Some("lin") => write!(buf_writer, "/")?,
+            _ => write!(buf_writer, "{{")?,
+        },
+        Tag::WTitlePg => {
+            log::warn!("Headers and footers are not converted, including those of the first page")
         }
-        Tag::MFraction => write!(buf_writer, "\\frac")?,
-        Tag::MNum => write!(buf_writer, "{{")?,
-        Tag::MDen => write!(buf_writer, "{{")?,
         Tag::Unknown { id } => {
-            log::warn!("Ignoring tag '{id}'")
+            log::warn!("Ignoring tag '{id}'");
+            // properties such as m:ctrlPr only tune the look of their parent, so losing them is fine,
+            // and so is losing markup that holds nothing of the content, along with all inside of it
+            let ignorable = |id: &str| id.ends_with("Pr") || IGNORABLE.contains(&id);
+            let mut ancestors = (0..stack.len()).filter_map(|i| match stack.get(i)? {
+                Tag::Unknown { id } => Some(id.clone()),
+                ancestor => ancestor.to_owned().map(|(name, _)| normalize(&name)),
+            });
+            let styling = ignorable(id) || ancestors.any(|id| ignorable(&id));
+            if id.starts_with("m:") && !styling {
+                context.unsupported_math.insert(id.clone());
+            }
+            if context.options.strict && !styling {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!("Unknown tag '{id}'"),
+                ));
+            }
         }
         _ => {}
     };
@@ -151,88 +1041,935 @@ fn start_element<W: Write>(
     Ok(State::OpenedTag(tag))
 }
 
-fn end_element<W: Write, P: Peek<Item = Tag>>(
-    buf_writer: &mut BufWriter<W>,
+fn end_element<P: Peek<Item = Tag>>(
+    buf_writer: &mut BufWriter<Vec<u8>>,
     stack: &P,
-    rels: &HashMap<String, String>,
-    math_mode: &mut bool,
-    nary_has_chr: &mut Option<bool>,
+    rels: &HashMap<String, Target>,
+    context: &mut Context,
 ) -> std::io::Result<State> {
-    if let Some(rel) = ooxml::drawing(stack) {
+    if let Some(instr) = ooxml::instr_text(stack) {
+        // ["w:r", "w:instrText", "code"] -> (field code)
+        match context.fields.last_mut() {
+            Some(field) if !field.result => field.instr.push_str(instr),
+            _ => {
+                log::error!("Field code {instr:?} is outside of a field");
+                let kind = DiagnosticKind::Unbalanced;
+                context.report.push(Severity::Error, kind, "w:instrText");
+            }
+        }
+    } else if context.in_replaced_field()
+        && (ooxml::drawing(stack).is_some()
+            || ooxml::tab(stack).is_some()
+            || stack.last().and_then(Tag::content).is_some())
+    {
+        // cached result of a field that has already been converted
+    } else if let Some(rel) = ooxml::drawing(stack) {
         // ["w:drawing", ("wp:inline"/"wp:anchor"), "a:graphic", "a:graphicData", "pic:pic", "pic:blipFill", "a:blip"]
-        latex::drawing(buf_writer, rels, rel)?;
-    } else if let Some(hyperlink) = ooxml::hyperlink(stack) {
+        let description = context.description.take();
+        let extent = context.extent.take();
+        let width = latex::width(extent, None);
+        let hidden = std::mem::take(&mut context.hidden);
+        let svg = context.svg.take();
+        if hidden && !context.options.include_hidden {
+            log::debug!("Skipping hidden drawing {rel:?}");
+        } else {
+            let (rel, tag) = match &svg {
+                Some(svg) => {
+                    context.packages.insert("svg");
+                    (svg, "asvg:svgBlip")
+                }
+                None => (rel, "a:blip"),
+            };
+            let figure = context.options.figures
+                && !context.paragraph.content
+                && context.tables.is_empty()
+                && context.math_depth == 0;
+            let mut latex = BufWriter::new(Vec::new());
+            let state = latex::drawing(&mut latex, rels, rel, description.as_deref(), &width)?;
+            let latex = latex.into_inner().map_err(|e| e.into_error())?;
+            let latex = String::from_utf8_lossy(&latex).into_owned();
+            // the width is only known in inches until the section gives the text width
+            let width = match extent.zip(latex.find(&format!("[width={width}]"))) {
+                Some((extent, i)) if context.section_widths => {
+                    let start = i + "[width=".len();
+                    Some((start..start + width.len(), extent))
+                }
+                _ => None,
+            };
+            let drawing = Drawing { latex, width };
+            if figure {
+                context.paragraph.figure = Some(drawing);
+                context.paragraph.content = true;
+            } else {
+                paragraph_content(buf_writer, context)?;
+                write_drawing(buf_writer, context, drawing)?;
+            }
+            if state == State::RelationshipMissing {
+                let kind = DiagnosticKind::MissingRelationship(rel.clone());
+                context.report.push(Severity::Error, kind, tag);
+            }
+        }
+    } else if context.run.footnote_mark && ooxml::word_text(stack).is_some() {
+        // ["w:r", "w:t", "1"] in the FootnoteReference style -> (nothing)
+        // a number typed as the mark would be written twice next to the one of \footnote
+    } else if let Some((link, text)) = ooxml::hyperlink(stack) {
         // ["w:hyperlink", "w:r", "w:t", "text"] -> hyperlink(text)
-        latex::hyperlink(buf_writer, rels, hyperlink)?;
+        paragraph_content(buf_writer, context)?;
+        // the text of the link keeps the formatting of its run
+        let mut formatted = BufWriter::new(Vec::new());
+        run_text(&mut formatted, &context.run, &mut context.packages, text)?;
+        let formatted = formatted.into_inner().map_err(|e| e.into_error())?;
+        let formatted = String::from_utf8_lossy(&formatted).into_owned();
+        let hyperlink = (link, &formatted);
+        let state = match context.tooltip.as_deref() {
+            Some(tooltip) if context.options.tooltips => {
+                write!(buf_writer, "\\pdftooltip{{")?;
+                let state = latex::hyperlink(buf_writer, rels, hyperlink)?;
+                write!(buf_writer, "}}{{{tooltip}}}")?;
+                context.packages.insert("pdfcomment");
+                state
+            }
+            _ => latex::hyperlink(buf_writer, rels, hyperlink)?,
+        };
+        if let (State::RelationshipMissing, (Link::Relationship(rel), _)) = (state, hyperlink) {
+            let kind = DiagnosticKind::MissingRelationship(rel.clone());
+            context.report.push(Severity::Error, kind, "w:hyperlink");
+        }
+    } else if let Some(space) = ooxml::word_text(stack).filter(|content| {
+        context.math_depth == 0
+            && !context.options.preserve_spaces
+            && !context.run.preserve_space
+            && content.trim().is_empty()
+    }) {
+        // ["w:r", "w:t", " "] -> held back along with the whitespace before it
+        context.paragraph.trailing.push_str(space);
     } else if let Some(content) = ooxml::word_text(stack) {
         // ["w:r", "w:t", "text"] -> text
-        write!(buf_writer, "{}", content)?;
+        paragraph_content(buf_writer, context)?;
+        if context.math_depth > 0 {
+            // a word run inside of an equation -> \text{text}
+            degree_opening(buf_writer, context)?;
+            delimiter_separator(buf_writer, context)?;
+            write!(buf_writer, "\\text{{")?;
+            run_text(buf_writer, &context.run, &mut context.packages, content)?;
+            write!(buf_writer, "}}")?;
+        } else if let Some(direction) =
+            latex::direction(context.paragraph.rtl, context.run.rtl, content)
+        {
+            // a run against the direction of its paragraph -> \RL{text} or \LR{text}
+            write!(buf_writer, "\\{direction}{{")?;
+            run_text(buf_writer, &context.run, &mut context.packages, content)?;
+            write!(buf_writer, "}}")?;
+            context.packages.insert("bidi");
+        } else if context.options.preserve_spaces || context.run.preserve_space {
+            run_text(buf_writer, &context.run, &mut context.packages, content)?;
+        } else {
+            let text = content.trim_end();
+            run_text(buf_writer, &context.run, &mut context.packages, text)?;
+            context.paragraph.trailing = content[text.len()..].to_string();
+        }
+    } else if ooxml::tab(stack).is_some() {
+        // ["w:r", "w:tab"] -> horizontal space
+        paragraph_content(buf_writer, context)?;
+        if context.paragraph.dotted {
+            write!(buf_writer, " \\dotfill ")?;
+        } else {
+            write!(buf_writer, "{TAB}")?;
+        }
+    } else if let Some(leader) = ooxml::paragraph_tab_stop(stack) {
+        // ["w:p", "w:pPr", "w:tabs", "w:tab"] -> tabs are filled with dots
+        if leader == "dot" {
+            context.paragraph.dotted = true;
+        }
+    } else if let Some(color) = ooxml::run_color(stack) {
+        // ["w:r", "w:rPr", "w:color"] -> \textcolor around the run's text
+        context.color_run(color);
+    } else if let Some(highlight) = ooxml::run_highlight(stack) {
+        // ["w:r", "w:rPr", "w:highlight"] -> \hl around the run's text
+        context.highlight_run(highlight);
+    } else if let Some(vert_align) = ooxml::run_vert_align(stack) {
+        // ["w:r", "w:rPr", "w:vertAlign"] -> \textsuperscript or \textsubscript
+        context.align_run(vert_align);
+    } else if let Some(font) = ooxml::run_fonts(stack) {
+        // ["w:r", "w:rPr", "w:rFonts"] -> \texttt and the like around the run's text
+        context.run.family = latex::font_family(font);
+    } else if let Some(rtl) = ooxml::run_rtl(stack) {
+        // ["w:r", "w:rPr", "w:rtl"] -> direction of the run's text
+        context.run.rtl = rtl;
+    } else if let Some(style) = ooxml::run_style(stack) {
+        // ["w:r", "w:rPr", "w:rStyle"] -> marks of footnotes are left to \footnote
+        context.run.footnote_mark = style == "FootnoteReference";
+        // the style comes first in w:rPr, so direct formatting after it takes precedence
+        if let Some(character) = context.styles.get(style).map(|s| s.character.clone()) {
+            if let Some(color) = &character.color {
+                context.color_run(color);
+            }
+            if let Some(highlight) = &character.highlight {
+                context.highlight_run(highlight);
+            }
+            if let Some(vert_align) = &character.vert_align {
+                context.align_run(vert_align);
+            }
+        }
+    } else if let Some(style) = ooxml::paragraph_style(stack) {
+        // ["w:p", "w:pPr", "w:pStyle"] -> {\centering\LARGE ... \par} or \title{...}
+        // built-in styles are referenced by their ids, which are not localized
+        context.paragraph.outline_level = context
+            .styles
+            .get(style)
+            .and_then(|style| style.paragraph.outline_level);
+        match (style.as_str(), context.tables.is_empty()) {
+            ("Title", true) if context.options.title_page => {
+                close_lists(buf_writer, context, 0)?;
+                write!(buf_writer, "\\title{{")?;
+                context.paragraph.title = true;
+            }
+            ("Title", true) => {
+                close_lists(buf_writer, context, 0)?;
+                write!(buf_writer, "{{\\centering\\LARGE ")?;
+                context.paragraph.closings.push("}");
+            }
+            ("Subtitle", true) => {
+                close_lists(buf_writer, context, 0)?;
+                write!(buf_writer, "{{\\centering\\Large ")?;
+                context.paragraph.closings.push("}");
+            }
+            ("Caption", true) => context.paragraph.caption = true,
+            _ => {}
+        }
+    } else if let Some(level) = ooxml::paragraph_outline_level(stack) {
+        // ["w:p", "w:pPr", "w:outlineLvl"] -> \section{...} once w:pPr ends
+        context.paragraph.outline_level = level.parse().ok();
+    } else if let Some(level) = ooxml::paragraph_level(stack) {
+        // ["w:p", "w:pPr", "w:numPr", "w:ilvl"] -> level of the list item
+        context.paragraph.level = level.parse().unwrap_or_default();
+    } else if let Some(id) = ooxml::paragraph_numbering(stack) {
+        // ["w:p", "w:pPr", "w:numPr", "w:numId"] -> \begin{enumerate} ... \item
+        // "0" removes the numbering that the paragraph style would give
+        if id != "0" && context.tables.is_empty() && !context.paragraph.title {
+            list_item(buf_writer, context, id)?;
+        }
+    } else if let Some(Spacing {
+        line: Some(line),
+        rule,
+    }) = ooxml::paragraph_spacing(stack)
+    {
+        // ["w:p", "w:pPr", "w:spacing"] -> {\setstretch{multiple} ... \par}
+        // a group can not span multiple paragraphs in a table cell
+        // and \title can not hold a \par
+        if let (Some(command), true) = (
+            latex::line_spacing(line, rule.as_deref()),
+            context.tables.is_empty() && !context.paragraph.title,
+        ) {
+            if rule.as_deref().unwrap_or("auto") == "auto" {
+                context.packages.insert("setspace");
+            }
+            write!(buf_writer, "{{{command}")?;
+            context.paragraph.closings.push("}");
+        }
+    } else if ooxml::paragraph_suppress_line_numbers(stack).is_some() {
+        // ["w:p", "w:pPr", "w:suppressLineNumbers"] -> {\nolinenumbers ... \par}
+        // lineno numbers the lines of a paragraph when it ends, so the group has to hold the \par
+        if context.tables.is_empty() && !context.paragraph.title {
+            write!(buf_writer, "{{\\nolinenumbers ")?;
+            context.paragraph.closings.push("}");
+            context.packages.insert("lineno");
+        }
+    } else if let Some(bidi) = ooxml::paragraph_bidi(stack) {
+        // ["w:p", "w:pPr", "w:bidi"] -> \begin{RTL} ... \end{RTL}
+        if bidi && context.tables.is_empty() && !context.paragraph.title {
+            write!(buf_writer, "\\begin{{RTL}}")?;
+            context.paragraph.closings.push("\\end{RTL}");
+            context.paragraph.rtl = true;
+            context.packages.insert("bidi");
+        }
+    } else if let Some(Width {
+        value: Some(value),
+        kind: Some(kind),
+    }) = ooxml::table_width(stack)
+    {
+        // ["w:tbl", "w:tblPr", "w:tblW"] -> columns share a part of the text width
+        if let (Some(table), "pct") = (context.tables.last_mut(), kind.as_str()) {
+            table.width = percentage(value);
+        }
+    } else if let Some(layout) = ooxml::table_layout(stack) {
+        // ["w:tbl", "w:tblPr", "w:tblLayout"] -> fixed or flexible columns
+        if let Some(table) = context.tables.last_mut() {
+            table.layout = Some(layout.clone());
+        }
+    } else if let Some(Width {
+        value: Some(value),
+        kind: Some(kind),
+    }) = ooxml::cell_width(stack)
+    {
+        // ["w:tc", "w:tcPr", "w:tcW"] -> width of the column, when in the first row
+        if let (Some(table), "pct") = (context.tables.last_mut(), kind.as_str()) {
+            if table.shares.len() + 1 == table.cells {
+                table.shares.extend(percentage(value));
+            }
+        }
+    } else if let Some(direction) = ooxml::cell_text_direction(stack) {
+        // ["w:tc", "w:tcPr", "w:textDirection"] -> \rotatebox{angle}{
+        let angle = match direction.as_str() {
+            "btLr" => Some(90),
+            "tbRl" => Some(-90),
+            _ => None,
+        };
+        if let (Some(angle), Some(table)) = (angle, context.tables.last_mut()) {
+            write!(buf_writer, "\\rotatebox{{{angle}}}{{")?;
+            table.rotated = true;
+            context.packages.insert("graphicx");
+        }
+    } else if ooxml::accent_element(stack).is_some() {
+        // ["m:acc", "m:e"] -> }
+        write!(buf_writer, "}}")?;
+    } else if ooxml::nary_element(stack).is_some() {
+        // ["m:nary", "m:e"] -> }
+        write!(buf_writer, "}}")?;
+    } else if let Some(name) = ooxml::base_function_name(stack) {
+        // ["m:fName", ("m:limLow"/"m:sSup"/...), "m:e", "m:r", "m:t", "name"] -> \name
+        // the limit or script that follows is attached to the name, as in \sin^{2}
+        write!(buf_writer, "{}", latex::function(name))?;
+    } else if let Some(name) = ooxml::function_name(stack) {
+        // ["m:fName", "m:r", "m:t", "name"] -> \name
+        write!(buf_writer, "{}", latex::function(name))?;
+    } else if ooxml::function_element(stack).is_some() {
+        // ["m:func", "m:e"] -> }
+        write!(buf_writer, "}}")?;
+    } else if ooxml::group_character_element(stack).is_some() {
+        // ["m:groupChr", "m:e"] -> }
+        write!(buf_writer, "}}")?;
+    } else if ooxml::bar_element(stack).is_some() {
+        // ["m:bar", "m:e"] -> }
+        write!(buf_writer, "}}")?;
+    } else if let Some(content) = ooxml::delimiter_text(stack) {
+        // ["m:d", "m:e", "m:r", "m:t", "text"] -> text
+        degree_opening(buf_writer, context)?;
+        let separator = latex::separator(content.trim());
+        match (context.delimiters.last_mut(), separator) {
+            (Some(delimiter), Some(separator)) if !delimiter.written => {
+                // an element that holds just a separator replaces the one from m:dPr
+                delimiter.pending = false;
+                delimiter.written = true;
+                delimiter.separated = true;
+                delimiter.write_separator(buf_writer, separator)?;
+            }
+            _ => {
+                delimiter_separator(buf_writer, context)?;
+                write!(buf_writer, "{}", content)?;
+            }
+        }
     } else if let Some(content) = ooxml::math_text(stack) {
         // ["m:r", "m:t", "text"] -> text
+        degree_opening(buf_writer, context)?;
+        delimiter_separator(buf_writer, context)?;
         write!(buf_writer, "{}", content)?;
     } else if let Some(tag) = stack.last() {
         // ["w:p"] -> newline
+        // ["w:br"] -> \\ (deferred until more text follows)
         // ["w:bookmarkStart"] -> \hypertarget{anchor}{
         // ["m:d"] -> )
         // ["m:oMathPara"] -> $$
-        // ["m:deg"] -> ]{
-        // [("m:sub"/"m:sup"/"m:num"/"m:den"/"m:rad"/"m:bookmarkEnd")] -> }
+        // ["m:deg"] -> ]{ (or just { when the degree was empty)
+        // [("m:sub"/"m:sup"/"m:num"/"m:den"/"m:rad"/"m:lim"/"m:bookmarkEnd")] -> }
         match tag {
-            Tag::WParagraph => {
-                writeln!(buf_writer)?;
-                writeln!(buf_writer)?;
-            }
-            Tag::WBookmarkStart { anchor } => {
-                write!(buf_writer, "\\hypertarget{{{anchor}}}{{")?;
+            Tag::WParagraphPr if !context.paragraph.numbered && context.tables.is_empty() => {
+                close_lists(buf_writer, context, 0)?;
+                let heading = context.paragraph.outline_level.and_then(latex::heading);
+                if let (Some(command), false) = (heading, context.paragraph.title) {
+                    write!(buf_writer, "\\{command}{{")?;
+                    context.paragraph.heading = true;
+                }
             }
-            Tag::MDelim => {
-                write!(buf_writer, ")")?;
+            Tag::WParagraph => {
+                if !context.paragraph.numbered && context.tables.is_empty() {
+                    close_lists(buf_writer, context, 0)?;
+                }
+                // a \\ right before a paragraph break is an error in latex
+                context.line_break = false;
+                if let Some(drawing) = context.paragraph.figure.take() {
+                    writeln!(buf_writer, "\\begin{{center}}")?;
+                    write_drawing(buf_writer, context, drawing)?;
+                    write!(buf_writer, "\n\\end{{center}}")?;
+                }
+                if context.paragraph.heading {
+                    write!(buf_writer, "}}")?;
+                }
+                if context.paragraph.title {
+                    write!(buf_writer, "}}\\maketitle")?;
+                } else if !context.paragraph.closings.is_empty() {
+                    // \par ends the paragraph while its line spacing is still in effect
+                    write!(buf_writer, "\\par")?;
+                    for closing in context.paragraph.closings.iter().rev() {
+                        write!(buf_writer, "{closing}")?;
+                    }
+                }
+                // the cached result of a field such as TOC may span several paragraphs
+                if context.tables.is_empty() && !context.in_replaced_field() {
+                    writeln!(buf_writer)?;
+                    writeln!(buf_writer)?;
+                }
             }
-            Tag::MoMathPara => {
-                writeln!(buf_writer, "$$")?;
-                if !*math_mode {
-                    log::error!("Exiting Math Mode without entering Math Mode");
+            Tag::WGridColumn(width) => {
+                if let Some(table) = context.tables.last_mut() {
+                    table.columns += 1;
+                    let width = width.value.as_deref().and_then(|w| w.parse().ok());
+                    table.grid.push(width.unwrap_or_default());
                 }
-                *math_mode = false;
             }
-            Tag::MDeg => {
-                write!(buf_writer, "]{{")?;
+            Tag::WTableGrid if context.tables.last().is_some_and(|table| !table.opened) => {
+                begin_table(buf_writer, context)?;
             }
-            Tag::MSub | Tag::MSup | Tag::MNum | Tag::MDen | Tag::MRad | Tag::WBookmarkEnd => {
+            Tag::WTableCell if context.tables.last().is_some_and(|table| table.rotated) => {
                 write!(buf_writer, "}}")?;
             }
-            Tag::MNaryPr => {
-                if let Some(false) = nary_has_chr {
-                    // m:naryPr with no m:chr within are treated as integrals
-                    write!(buf_writer, "\\int")?;
+            // the note is cut off once it ends, so its lists have to end with it
+            Tag::WFootnote { .. } => close_lists(buf_writer, context, 0)?,
+            Tag::WTableRow => {
+                writeln!(buf_writer, " \\\\")?;
+                writeln!(buf_writer, "\\hline")?;
+            }
+            Tag::WTable => {
+                let table = context.tables.pop().unwrap_or_default();
+                // the cells of the first row give widths that were not known at the specification
+                let complete = table.columns == 0 || table.shares.len() == table.columns;
+                if let (Some(range), false) = (&table.specification, table.shares.is_empty()) {
+                    if complete {
+                        let columns = latex::cell_columns(&table.shares, table.width);
+                        buf_writer.flush()?;
+                        let moved = (range.end..usize::MAX, range.start + columns.len());
+                        move_widths(&mut context.widths, &[moved]);
+                        buf_writer.get_mut().splice(range.clone(), columns.bytes());
+                    }
+                }
+                match table {
+                    Table {
+                        opened: true,
+                        tabularx: true,
+                        ..
+                    } => write!(buf_writer, "\\end{{tabularx}}")?,
+                    Table { opened: true, .. } => write!(buf_writer, "\\end{{tabular}}")?,
+                    _ => log::warn!("Table has neither a grid nor rows"),
+                }
+                if table.adjusted {
+                    writeln!(buf_writer)?;
+                    write!(buf_writer, "\\end{{adjustbox}}")?;
+                }
+                if context.tables.is_empty() {
+                    writeln!(buf_writer)?;
+                    writeln!(buf_writer)?;
                 }
-                *nary_has_chr = None;
             }
-            _ => {}
+            Tag::WBreak => {
+                context.line_break = true;
+            }
+            Tag::WFieldChar(FieldChar::Begin) => {
+                context.fields.push(Field::default());
+            }
+            Tag::WFieldChar(FieldChar::Separate) => match context.fields.last_mut() {
+                Some(field) => {
+                    field.result = true;
+                    field_code(buf_writer, context)?;
+                }
+                None => {
+                    log::error!("Field separator is outside of a field");
+                    let kind = DiagnosticKind::Unbalanced;
+                    context.report.push(Severity::Error, kind, "w:fldChar");
+                }
+            },
+            Tag::WFieldChar(FieldChar::End) => match context.fields.last() {
+                Some(field) => {
+                    if !field.result {
+                        // fields without a cached result are converted when they end
+                        field_code(buf_writer, context)?;
+                    }
+                    caption_label(buf_writer, context);
+                    context.fields.pop();
+                }
+                None => {
+                    log::error!("Field end is outside of a field");
+                    let kind = DiagnosticKind::Unbalanced;
+                    context.report.push(Severity::Error, kind, "w:fldChar");
+                }
+            },
+            Tag::WFieldSimple { .. } => {
+                caption_label(buf_writer, context);
+                context.fields.pop();
+            }
+            Tag::WBookmarkStart { anchor } => {
+                // a bookmark is no content of its own, but a drawing before it is no figure either
+                if context.paragraph.figure.is_some() {
+                    paragraph_content(buf_writer, context)?;
+                }
+                let anchor = latex::anchor(anchor);
+                // the label lets PAGEREF fields point at the bookmark's page
+                write!(buf_writer, "\\label{{{anchor}}}\\hypertarget{{{anchor}}}{{")?;
+            }
+            Tag::MDelim => match context.delimiters.pop() {
+                Some(Delimiter {
+                    begin: None,
+                    end: None,
+                    ..
+                })
+                | None => write!(buf_writer, ")")?,
+                Some(Delimiter { end, .. }) => write!(
+                    buf_writer,
+                    "\\right{}",
+                    latex::delimiter(end.as_deref().unwrap_or(")"))
+                )?,
+            },
+            Tag::MoMathPara | Tag::MoMath => match context.math_depth {
+                0 => {
+                    log::error!("Exiting Math Mode without entering Math Mode");
+                    let id = match tag {
+                        Tag::MoMathPara => "m:oMathPara",
+                        _ => "m:oMath",
+                    };
+                    context
+                        .report
+                        .push(Severity::Error, DiagnosticKind::Unbalanced, id);
+                }
+                1 => {
+                    match (context.options.math_delimiters, context.math_inline) {
+                        (MathDelimiters::Dollars, false) => writeln!(buf_writer, "$$")?,
+                        (MathDelimiters::Dollars, true) => write!(buf_writer, "$")?,
+                        (MathDelimiters::DisplayMath, false) => writeln!(buf_writer, "\\]")?,
+                        (MathDelimiters::DisplayMath, true) => write!(buf_writer, "\\)")?,
+                    }
+                    context.math_depth = 0;
+                }
+                _ => context.math_depth -= 1,
+            },
+            Tag::MDeg => match context.degrees.pop() {
+                Some(false) => write!(buf_writer, "{{")?,
+                _ => write!(buf_writer, "]{{")?,
+            },
+            Tag::MAcc => {
+                context.accents.pop();
+            }
+            Tag::MBar => {
+                context.bars.pop();
+            }
+            Tag::MGroupChr => {
+                context.group_characters.pop();
+            }
+            Tag::MMatrix => {
+                context.matrices.pop();
+                write!(buf_writer, "\\end{{matrix}}")?;
+            }
+            Tag::MFraction => {
+                context.fractions.pop();
+            }
+            // a linear fraction is written as num/den, without groups to close
+            Tag::MNum | Tag::MDen
+                if context.fractions.last().map(String::as_str) == Some("lin") => {}
+            Tag::MLim if context.limits.last().is_some_and(|limit| !limit.bare) => {}
+            Tag::MSub | Tag::MNum | Tag::MDen | Tag::MSup | Tag::MRad | Tag::MLim => {
+                write!(buf_writer, "}}")?;
+            }
+            Tag::WBookmarkEnd => {
+                if context.paragraph.figure.is_some() {
+                    paragraph_content(buf_writer, context)?;
+                }
+                write!(buf_writer, "}}")?;
+            }
+            Tag::MNary => {
+                context.naries.pop();
+            }
+            Tag::MLimLow | Tag::MLimUpp => match context.limits.pop() {
+                Some(limit) if !limit.bare => {
+                    // ["m:limLow", "m:e", "m:lim"] -> \underset{lim}{e}, or \lim_{lim} for a function name
+                    let upper = matches!(stack.last(), Some(Tag::MLimUpp));
+                    buf_writer.flush()?;
+                    let written = buf_writer.get_mut();
+                    let end = written.len();
+                    let limit_start = limit.limit.clamp(limit.base, end);
+                    let lim = written.split_off(limit_start);
+                    let base = written.split_off(limit.base);
+                    let lim = String::from_utf8_lossy(&lim).into_owned();
+                    let base = String::from_utf8_lossy(&base).into_owned();
+                    let (command, script) = if upper {
+                        ("overset", "^")
+                    } else {
+                        ("underset", "_")
+                    };
+                    let (opening, closing) = match latex::operator(&base) {
+                        Some(operator) => (format!("{operator}{script}{{"), "}".to_string()),
+                        None => (format!("\\{command}{{"), format!("}}{{{base}}}")),
+                    };
+                    // drawings in the limit or the base move along with them
+                    let moved = [
+                        (limit_start..end, limit.base + opening.len()),
+                        (
+                            limit.base..limit_start,
+                            limit.base + opening.len() + lim.len() + 2,
+                        ),
+                    ];
+                    move_widths(&mut context.widths, &moved);
+                    write!(buf_writer, "{opening}{lim}{closing}")?;
+                }
+                _ => {}
+            },
+            Tag::AsvgSvgBlip { rel } if rels.contains_key(rel) => {
+                // preferred over the raster fallback of the enclosing a:blip
+                context.svg = Some(rel.clone());
+            }
+            _ => {}
         }
     }
 
     Ok(State::ClosedTag)
 }
 
-fn xml_event<W: Write, P: Peek<Item = Tag>>(
+/// Parses a percentage of `w:w`, given either in fiftieths of a percent or as "30%"
+fn percentage(value: &str) -> Option<f64> {
+    match value.strip_suffix('%') {
+        Some(percent) => percent.parse::<f64>().ok().map(|percent| percent / 100.0),
+        None => value
+            .parse::<f64>()
+            .ok()
+            .map(|fiftieths| fiftieths / 5000.0),
+    }
+}
+
+/// Writes latex for the innermost field, if its field code is supported
+fn field_code(buf_writer: &mut BufWriter<Vec<u8>>, context: &mut Context) -> std::io::Result<()> {
+    let Some((_, outer)) = context.fields.split_last() else {
+        return Ok(());
+    };
+    if outer.iter().any(|field| field.replaced) {
+        return Ok(());
+    }
+    paragraph_content(buf_writer, context)?;
+    let Some(field) = context.fields.last_mut() else {
+        return Ok(());
+    };
+    let mut words = field.instr.split_whitespace();
+    match words.next() {
+        Some("CITATION") => {
+            if let Some(source) = words.next() {
+                latex::citation(buf_writer, source)?;
+                if !context.citations.iter().any(|s| s == source) {
+                    context.citations.push(source.to_string());
+                }
+                field.replaced = true;
+            } else {
+                log::error!("CITATION field does not name a source");
+                let kind = DiagnosticKind::IncompleteField("CITATION".to_string());
+                context.report.push(Severity::Error, kind, "w:instrText");
+            }
+        }
+        Some("PAGEREF") => {
+            if let Some(bookmark) = words.next() {
+                latex::page_reference(buf_writer, bookmark)?;
+                field.replaced = true;
+            } else {
+                log::error!("PAGEREF field does not name a bookmark");
+                let kind = DiagnosticKind::IncompleteField("PAGEREF".to_string());
+                context.report.push(Severity::Error, kind, "w:instrText");
+            }
+        }
+        Some("TOC") => {
+            // latex gathers the entries from the headings itself
+            write!(buf_writer, "\\tableofcontents")?;
+            field.replaced = true;
+        }
+        Some("REF") => {
+            if let Some(bookmark) = words.next() {
+                latex::reference(buf_writer, bookmark)?;
+                field.replaced = true;
+            } else {
+                log::error!("REF field does not name a bookmark");
+                let kind = DiagnosticKind::IncompleteField("REF".to_string());
+                context.report.push(Severity::Error, kind, "w:instrText");
+            }
+        }
+        Some(code) => log::debug!("Leaving field {code:?} as is"),
+        None => log::warn!("Empty field code"),
+    }
+    Ok(())
+}
+
+/// Marks the end of the innermost field as the start of the caption text, if it numbers a caption
+fn caption_label(buf_writer: &mut BufWriter<Vec<u8>>, context: &mut Context) {
+    let numbers = context
+        .fields
+        .last()
+        .is_some_and(|field| field.instr.split_whitespace().next() == Some("SEQ"));
+    if numbers && context.paragraph.caption {
+        context.paragraph.label = Some(offset(buf_writer));
+    }
+}
+
+/// Writes an \item of the list `id`, opening and closing lists to reach the level of the paragraph
+fn list_item<W: Write>(
+    buf_writer: &mut BufWriter<W>,
+    context: &mut Context,
+    id: &str,
+) -> std::io::Result<()> {
+    let depth = context.paragraph.level + 1;
+    // another list at the same level is not continued
+    let kept = match context.lists.get(depth - 1) {
+        Some(list) if list.id != id => depth - 1,
+        _ => depth,
+    };
+    close_lists(buf_writer, context, kept)?;
+    while context.lists.len() < depth {
+        let level = context.lists.len();
+        let levels = context.numbering.get(id);
+        let environment = match levels.and_then(|levels| levels.get(level)) {
+            Some(Level { format, .. }) if format == "bullet" => "itemize",
+            _ => "enumerate",
+        };
+        match levels.and_then(|levels| latex::list_label(levels, level)) {
+            Some(label) if environment == "enumerate" => {
+                writeln!(buf_writer, "\\begin{{enumerate}}[label={label}]")?;
+                context.packages.insert("enumitem");
+            }
+            _ => writeln!(buf_writer, "\\begin{{{environment}}}")?,
+        }
+        context.lists.push(List {
+            id: id.to_string(),
+            environment,
+        });
+        if context.lists.len() < depth {
+            // a nested list has to be part of an item
+            writeln!(buf_writer, "\\item[]")?;
+        }
+    }
+    write!(buf_writer, "\\item ")?;
+    context.paragraph.numbered = true;
+    Ok(())
+}
+
+/// Writes the \begin of the innermost table, from its grid when there is one
+fn begin_table(buf_writer: &mut BufWriter<Vec<u8>>, context: &mut Context) -> std::io::Result<()> {
+    // nested tables are scaled along with the outermost one
+    let fit = context.options.fit_tables && context.tables.len() == 1;
+    let Some(table) = context.tables.last_mut() else {
+        return Ok(());
+    };
+    if fit {
+        writeln!(buf_writer, "\\begin{{adjustbox}}{{max width=\\textwidth}}")?;
+        context.packages.insert("adjustbox");
+        table.adjusted = true;
+    }
+    let columns = match &mut *table {
+        // without a grid, the number of columns is not known in advance
+        table if table.columns == 0 => latex::open_columns(),
+        // fixed columns keep the widths of the grid
+        table
+            if table.layout.as_deref() == Some("fixed") && table.grid.iter().sum::<f64>() > 0.0 =>
+        {
+            latex::fixed_columns(&table.grid)
+        }
+        // autofit columns share the width of the table
+        Table {
+            columns,
+            width: Some(width),
+            layout: Some(layout),
+            tabularx,
+            ..
+        } if layout == "autofit" => {
+            *tabularx = true;
+            context.packages.insert("tabularx");
+            let columns = "|X".repeat(*columns) + "|";
+            writeln!(
+                buf_writer,
+                "\\begin{{tabularx}}{{{width:.2}\\textwidth}}{{{columns}}}"
+            )?;
+            table.opened = true;
+            writeln!(buf_writer, "\\hline")?;
+            return Ok(());
+        }
+        table => latex::columns(table.columns, table.width, &table.grid),
+    };
+    write!(buf_writer, "\\begin{{tabular}}{{")?;
+    let start = offset(buf_writer);
+    write!(buf_writer, "{columns}")?;
+    table.specification = Some(start..offset(buf_writer));
+    table.opened = true;
+    writeln!(buf_writer, "}}")?;
+    writeln!(buf_writer, "\\hline")?;
+    Ok(())
+}
+
+/// Number of bytes written so far, including those that are still buffered
+fn offset(buf_writer: &BufWriter<Vec<u8>>) -> usize {
+    buf_writer.get_ref().len() + buf_writer.buffer().len()
+}
+
+/// Ends the innermost lists until `depth` of them are left open
+fn close_lists<W: Write>(
+    buf_writer: &mut BufWriter<W>,
+    context: &mut Context,
+    depth: usize,
+) -> std::io::Result<()> {
+    if context.lists.len() <= depth {
+        return Ok(());
+    }
+    while context.lists.len() > depth {
+        if let Some(list) = context.lists.pop() {
+            writeln!(buf_writer, "\\end{{{}}}", list.environment)?;
+        }
+    }
+    if depth == 0 {
+        writeln!(buf_writer)?;
+    }
+    Ok(())
+}
+
+/// Writes the `[` of the innermost `m:deg` before its first content
+fn degree_opening<W: Write>(
+    buf_writer: &mut BufWriter<W>,
+    context: &mut Context,
+) -> std::io::Result<()> {
+    if let Some(opened @ false) = context.degrees.last_mut() {
+        write!(buf_writer, "[")?;
+        *opened = true;
+    }
+    Ok(())
+}
+
+/// Writes the separator that is due in the innermost `m:d`
+fn delimiter_separator<W: Write>(
+    buf_writer: &mut BufWriter<W>,
+    context: &mut Context,
+) -> std::io::Result<()> {
+    if let Some(delimiter) = context.delimiters.last_mut() {
+        if delimiter.pending {
+            delimiter.write_separator(buf_writer, &delimiter.separator)?;
+            delimiter.pending = false;
+        }
+        delimiter.written = true;
+    }
+    Ok(())
+}
+
+fn run_text<W: Write>(
+    buf_writer: &mut BufWriter<W>,
+    run: &Run,
+    packages: &mut BTreeSet<&'static str>,
+    content: &str,
+) -> std::io::Result<()> {
+    let mut closing = 0;
+    if let Some(highlight) = run.highlight {
+        write!(buf_writer, "\\sethlcolor{{{highlight}}}\\hl{{")?;
+        packages.insert("soul");
+        packages.insert("xcolor");
+        closing += 1;
+    }
+    if let Some(color) = &run.color {
+        write!(buf_writer, "\\textcolor[HTML]{{{color}}}{{")?;
+        packages.insert("xcolor");
+        closing += 1;
+    }
+    if let Some(family) = run.family {
+        write!(buf_writer, "\\{family}{{")?;
+        closing += 1;
+    }
+    if let Some(script) = run.script {
+        write!(buf_writer, "\\{script}{{")?;
+        closing += 1;
+    }
+    write!(buf_writer, "{content}")?;
+    write!(buf_writer, "{}", "}".repeat(closing))
+}
+
+/// Writes what comes before further content of a paragraph, which is the trailing whitespace
+/// of the text so far, the drawing that turns out not to be a figure and the pending line break
+fn paragraph_content(
+    buf_writer: &mut BufWriter<Vec<u8>>,
+    context: &mut Context,
+) -> std::io::Result<()> {
+    write!(
+        buf_writer,
+        "{}",
+        std::mem::take(&mut context.paragraph.trailing)
+    )?;
+    if let Some(drawing) = context.paragraph.figure.take() {
+        write_drawing(buf_writer, context, drawing)?;
+    }
+    context.paragraph.content = true;
+    line_break(buf_writer, context)
+}
+
+/// Writes the latex of a drawing, recording where its width ends up in the section
+fn write_drawing(
+    buf_writer: &mut BufWriter<Vec<u8>>,
+    context: &mut Context,
+    drawing: Drawing,
+) -> std::io::Result<()> {
+    let start = offset(buf_writer);
+    write!(buf_writer, "{}", drawing.latex)?;
+    if let Some((width, extent)) = drawing.width {
+        context
+            .widths
+            .push((start + width.start..start + width.end, extent));
+    }
+    Ok(())
+}
+
+fn line_break<W: Write>(
     buf_writer: &mut BufWriter<W>,
+    context: &mut Context,
+) -> std::io::Result<()> {
+    if context.line_break {
+        writeln!(buf_writer, "\\\\")?;
+        context.line_break = false;
+    }
+    Ok(())
+}
+
+fn xml_event<P: Peek<Item = Tag>>(
+    buf_writer: &mut BufWriter<Vec<u8>>,
     stack: &P,
-    rels: &HashMap<String, String>,
+    rels: &HashMap<String, Target>,
     event: &XmlEvent,
-    math_mode: &mut bool,
-    nary_has_chr: &mut Option<bool>,
+    context: &mut Context,
 ) -> std::io::Result<State> {
     match event {
         XmlEvent::StartElement {
             name, attributes, ..
-        } => start_element(buf_writer, name, attributes, math_mode, nary_has_chr),
-        XmlEvent::EndElement { .. } => {
-            end_element(buf_writer, stack, rels, math_mode, nary_has_chr)
+        } => start_element(buf_writer, stack, name, attributes, context),
+        XmlEvent::EndElement { .. } => end_element(buf_writer, stack, rels, context),
+        XmlEvent::Characters(content) if matches!(stack.last(), Some(Tag::WInstrText)) => {
+            log::debug!("Characters [Field Code] {:?}", content);
+            Ok(State::FoundContent(content.clone()))
+        }
+        XmlEvent::Characters(content) | XmlEvent::Whitespace(content)
+            if matches!(stack.last(), Some(Tag::WText)) =>
+        {
+            log::debug!("Characters [Text] {:?}", content);
+            // like Word, whitespace around the text of w:t is dropped unless it is preserved
+            let content = if context.run.preserve_space {
+                content
+            } else {
+                content.trim()
+            };
+            let substitutions = &context.options.substitutions;
+            let content = if context.options.preserve_spaces {
+                escape_spaces(content, substitutions)
+            } else {
+                escape(content, &false, substitutions)
+            };
+            log::debug!("Characters [Escaped] {:?}", &content);
+            Ok(State::FoundContent(content))
         }
         XmlEvent::Characters(content) => {
             log::debug!("Characters [Raw] {:?}", content);
-            let content = escape(content, math_mode);
+            // text of a word run is set in text mode even inside of an equation
+            let math_mode = context.math_depth > 0 && !matches!(stack.last(), Some(Tag::WText));
+            if math_mode && content.contains(":=") {
+                // for \coloneqq
+                context.packages.insert("mathtools");
+            }
+            let content = escape(content, &math_mode, &context.options.substitutions);
+            let content = if math_mode && context.options.decimal_comma {
+                latex::decimal_comma(&content)
+            } else {
+                content
+            };
             log::debug!("Characters [Escaped] {:?}", &content);
             Ok(State::FoundContent(content))
         }
@@ -255,41 +1992,44 @@ fn xml_event<W: Write, P: Peek<Item = Tag>>(
     }
 }
 
-pub fn document(
-    parser: &mut EventReader<BufReader<File>>,
-    buf_writer: &mut BufWriter<File>,
-    rels: &HashMap<String, String>,
+/// Converts every event of `parser`, handing each tag that ends to `closed`
+fn events<R: Read>(
+    parser: &mut EventReader<R>,
+    buf_writer: &mut BufWriter<Vec<u8>>,
+    rels: &HashMap<String, Target>,
+    context: &mut Context,
+    mut closed: impl FnMut(&mut BufWriter<Vec<u8>>, Tag, &mut Context),
 ) -> std::io::Result<()> {
     let mut stack = Boo::default();
-    let mut math_mode = false;
-    let mut nary_has_chr = None;
     loop {
         match parser.next() {
-            Ok(event) => match xml_event(
-                buf_writer,
-                &stack,
-                rels,
-                &event,
-                &mut math_mode,
-                &mut nary_has_chr,
-            )? {
+            Ok(event) => match xml_event(buf_writer, &stack, rels, &event, context)? {
                 State::OpenedTag(tag) => {
                     stack.push(tag);
                 }
                 State::ClosedTag => {
-                    stack.pop();
+                    if let Some(tag) = stack.pop() {
+                        closed(buf_writer, tag, context);
+                    }
                 }
                 State::FoundContent(content) => {
                     stack.push(Tag::Content(content));
-                    let _ =
-                        end_element(buf_writer, &stack, rels, &mut math_mode, &mut nary_has_chr)?;
+                    let _ = end_element(buf_writer, &stack, rels, context)?;
                     stack.pop();
                 }
-                State::AttributesMissing | State::RelationshipMissing | State::Happy => {}
+                State::RelationshipMissing | State::Happy => {}
                 State::End => break,
             },
             Err(error) => {
                 log::error!("Error: {error}");
+                // what comes after the error can not be read, so the rest of the part is lost
+                let kind = DiagnosticKind::MalformedXml(error.msg().to_string());
+                let id = stack
+                    .last()
+                    .and_then(Tag::to_owned)
+                    .map(|(name, _)| name.to_string());
+                let id = id.unwrap_or_default();
+                context.report.push(Severity::Error, kind, &id);
                 break;
             }
         }
@@ -297,37 +2037,426 @@ pub fn document(
     Ok(())
 }
 
-fn escape(raw: &str, math_mode: &bool) -> String {
-    let mut buf = String::new();
-    for c in raw.chars() {
-        match c {
-            '∞' => buf.push_str("\\infty "),
-            'π' => buf.push_str("\\pi "),
-            '&' => buf.push_str("\\& "),
-            '<' => {
-                if *math_mode {
-                    buf.push('<');
-                } else {
-                    buf.push_str("\\textless ");
+/// Converts the notes of `word/footnotes.xml` into latex, by the `w:id` that references point at,
+/// along with the latex packages they depend on and a report of what could not be converted faithfully
+pub fn footnotes<R: Read>(
+    parser: &mut EventReader<R>,
+    rels: &HashMap<String, Target>,
+    numbering: &HashMap<String, Vec<Level>>,
+    styles: &HashMap<StyleId, Style>,
+    options: &Options,
+) -> std::io::Result<(
+    HashMap<String, String>,
+    BTreeSet<&'static str>,
+    ConversionReport,
+)> {
+    let mut context = Context {
+        options: options.clone(),
+        numbering: numbering.clone(),
+        styles: styles.clone(),
+        ..Context::default()
+    };
+    let mut notes = HashMap::new();
+    let mut buf_writer = BufWriter::new(Vec::new());
+    events(
+        parser,
+        &mut buf_writer,
+        rels,
+        &mut context,
+        |buf_writer, tag, _| {
+            if let Tag::WFootnote { id } = tag {
+                let note = std::mem::replace(buf_writer, BufWriter::new(Vec::new()));
+                let note = note.into_inner().unwrap_or_default();
+                let note = String::from_utf8_lossy(&note).trim().to_string();
+                notes.insert(id, note);
+            }
+        },
+    )?;
+    Ok((notes, context.packages, context.report))
+}
+
+/// Width of the text between the margins of a page, in EMU
+fn text_width(page_width: Option<u64>, margins: (Option<u64>, Option<u64>)) -> Option<u64> {
+    let (left, right) = margins;
+    let twips = page_width?.checked_sub(left? + right?)?;
+    Some(twips * EMU_PER_TWIP).filter(|&width| width > 0)
+}
+
+/// Rewrites the widths of the drawings in `content` as shares of the text width of its section
+fn resolve_widths(
+    content: &mut Vec<u8>,
+    mut widths: Vec<(Range<usize>, u64)>,
+    text_width: Option<u64>,
+) {
+    // from the end, so that the ranges before are not moved
+    widths.sort_by_key(|(range, _)| std::cmp::Reverse(range.start));
+    for (range, extent) in widths {
+        let width = latex::width(Some(extent), text_width);
+        content.splice(range, width.bytes());
+    }
+}
+
+/// Moves the widths recorded in the first range of each pair to start at its second element,
+/// along with the latex that they were written in
+fn move_widths(widths: &mut [(Range<usize>, u64)], moved: &[(Range<usize>, usize)]) {
+    for (range, _) in widths.iter_mut() {
+        if let Some((from, to)) = moved.iter().find(|(from, _)| from.contains(&range.start)) {
+            *range = range.start - from.start + to..range.end - from.start + to;
+        }
+    }
+}
+
+/// Replaces the latex of a table and its caption paragraph, which end the section so far,
+/// with a table float that has the caption on the same side of the table.
+/// The text of the caption starts at `text`, leaving out the label that \caption writes itself
+fn float_table(
+    section: &mut Vec<u8>,
+    widths: &mut Vec<(Range<usize>, u64)>,
+    caption: Range<usize>,
+    text: usize,
+    table: Range<usize>,
+) {
+    let raw = String::from_utf8_lossy(&section[text..caption.end]).into_owned();
+    let name = latex::caption(raw.trim_start());
+    // where the text and the tabular begin in the section, past what is trimmed off
+    let name_start = text + raw.len() - name.len();
+    let name = name.trim_end();
+    let raw = String::from_utf8_lossy(&section[table.clone()]).into_owned();
+    let tabular = raw.trim_start();
+    let tabular_start = table.end - tabular.len();
+    let tabular = tabular.trim_end();
+    let start = caption.start.min(table.start);
+    section.truncate(start);
+    let head = "\\begin{table}[h]\n\\centering\n";
+    let name_line = format!("\\caption{{{name}}}\n");
+    let tabular_line = format!("{tabular}\n");
+    let (float, name_at, tabular_at) = if caption.start < table.start {
+        let float = format!("{head}{name_line}{tabular_line}\\end{{table}}\n\n");
+        (
+            float,
+            start + head.len(),
+            start + head.len() + name_line.len(),
+        )
+    } else {
+        let float = format!("{head}{tabular_line}{name_line}\\end{{table}}\n\n");
+        (
+            float,
+            start + head.len() + tabular_line.len(),
+            start + head.len(),
+        )
+    };
+    section.extend_from_slice(float.as_bytes());
+    // the widths of drawings move with the caption and the table, and leave with what is left out
+    let moved = [
+        (
+            name_start..name_start + name.len(),
+            name_at + "\\caption{".len(),
+        ),
+        (tabular_start..tabular_start + tabular.len(), tabular_at),
+    ];
+    widths.retain(|(range, _)| {
+        range.start < start || moved.iter().any(|(from, _)| from.contains(&range.start))
+    });
+    move_widths(widths, &moved);
+}
+
+/// Writes the body of the document and returns the latex packages it depends on,
+/// along with a report of what could not be converted faithfully
+fn document<R: Read, W: Write>(
+    parser: &mut EventReader<R>,
+    buf_writer: &mut BufWriter<W>,
+    rels: &HashMap<String, Target>,
+    numbering: &HashMap<String, Vec<Level>>,
+    footnotes: &HashMap<String, String>,
+    styles: &HashMap<StyleId, Style>,
+    options: &Options,
+) -> std::io::Result<(BTreeSet<&'static str>, ConversionReport)> {
+    let mut context = Context {
+        options: options.clone(),
+        numbering: numbering.clone(),
+        footnotes: footnotes.clone(),
+        styles: styles.clone(),
+        section_widths: true,
+        ..Context::default()
+    };
+    // a w:sectPr comes after the content of its section, so sections are only written once they end
+    let mut sections = Vec::new();
+    let mut landscape = false;
+    let mut page_width = None;
+    let mut margins = (None, None);
+    let mut line_numbers = None;
+    let mut section_ended = false;
+    let mut section = BufWriter::new(Vec::new());
+    // latex of the last caption and table in the section, to float the table once both are known
+    let mut start = 0;
+    let mut caption: Option<(Range<usize>, usize)> = None;
+    let mut table: Option<Range<usize>> = None;
+    events(
+        parser,
+        &mut section,
+        rels,
+        &mut context,
+        |section, tag, context| {
+            let _ = section.flush();
+            let end = section.get_ref().len();
+            match tag {
+                Tag::WPageSize {
+                    landscape: wide,
+                    width,
+                } => (landscape, page_width) = (wide, width),
+                Tag::WPageMargins { left, right } => margins = (left, right),
+                Tag::WLineNumbers { count_by, restart } => {
+                    line_numbers = Some(latex::line_numbers(count_by, restart.as_deref()));
                 }
+                Tag::WSectionPr => section_ended = true,
+                // the w:sectPr of every section but the last is in the properties of its last paragraph
+                Tag::WParagraph if section_ended => {
+                    let content = std::mem::replace(section, BufWriter::new(Vec::new()));
+                    let content = content.into_inner().unwrap_or_default();
+                    let widths = std::mem::take(&mut context.widths);
+                    let text_width = text_width(page_width, margins);
+                    let numbers = line_numbers.take();
+                    sections.push((content, widths, landscape, text_width, numbers));
+                    landscape = false;
+                    page_width = None;
+                    margins = (None, None);
+                    section_ended = false;
+                    (start, caption, table) = (0, None, None);
+                }
+                Tag::WParagraph if context.tables.is_empty() => {
+                    let text = context.paragraph.label.unwrap_or(start);
+                    match table.take() {
+                        // a caption right below its table
+                        Some(table) if context.paragraph.caption && table.end == start => {
+                            let widths = &mut context.widths;
+                            float_table(section.get_mut(), widths, start..end, text, table);
+                        }
+                        _ if context.paragraph.caption => caption = Some((start..end, text)),
+                        _ => {}
+                    }
+                    start = section.get_ref().len();
+                }
+                Tag::WTable if context.tables.is_empty() => {
+                    match caption.take() {
+                        // a caption right above its table
+                        Some((caption, text)) if caption.end == start => {
+                            let widths = &mut context.widths;
+                            float_table(section.get_mut(), widths, caption, text, start..end);
+                        }
+                        _ => table = Some(start..end),
+                    }
+                    start = section.get_ref().len();
+                }
+                _ => {}
             }
-            '>' => {
-                if *math_mode {
-                    buf.push('>');
-                } else {
-                    buf.push_str("\\textgreater ");
+        },
+    )?;
+    close_lists(&mut section, &mut context, 0)?;
+    let content = section.into_inner().map_err(|e| e.into_error())?;
+    let widths = std::mem::take(&mut context.widths);
+    let text_width = text_width(page_width, margins);
+    sections.push((content, widths, landscape, text_width, line_numbers));
+    for (mut content, widths, landscape, text_width, line_numbers) in sections {
+        resolve_widths(&mut content, widths, text_width);
+        if landscape {
+            // \newgeometry can not turn the paper, so the pages are rotated instead
+            writeln!(buf_writer, "\\begin{{landscape}}")?;
+            context.packages.insert("pdflscape");
+        }
+        if let Some(line_numbers) = &line_numbers {
+            writeln!(buf_writer, "{line_numbers}")?;
+            context.packages.insert("lineno");
+        }
+        buf_writer.write_all(&content)?;
+        if line_numbers.is_some() {
+            writeln!(buf_writer, "\\nolinenumbers")?;
+            writeln!(buf_writer)?;
+        }
+        if landscape {
+            writeln!(buf_writer, "\\end{{landscape}}")?;
+            writeln!(buf_writer)?;
+        }
+    }
+    if !context.unsupported_math.is_empty() {
+        let elements: Vec<_> = context
+            .unsupported_math
+            .iter()
+            .map(String::as_str)
+            .collect();
+        log::warn!(
+            "Equations may be incomplete, these math elements are not supported: {}",
+            elements.join(", ")
+        );
+        for element in &context.unsupported_math {
+            let kind = DiagnosticKind::UnsupportedMath;
+            context.report.push(Severity::Warning, kind, element);
+        }
+    }
+    latex::bibliography(buf_writer, &context.citations)?;
+    Ok((context.packages, context.report))
+}
+
+/// Converts a `document.xml` and its `document.xml.rels` into the latex of the body, without a preamble.
+/// Lists, footnotes and styles are left out, as they are in other parts of the package
+pub fn convert<R: Read, W: Write>(
+    document_xml: R,
+    rels_xml: R,
+    out: W,
+) -> std::io::Result<ConversionReport> {
+    let mut parser = EventReader::new(BufReader::new(rels_xml));
+    let rels = relationships(&mut parser)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+    let mut parser = EventReader::new(BufReader::new(document_xml));
+    let mut buf_writer = BufWriter::new(out);
+    let (_, report) = Prysm::new(rels).document(&mut parser, &mut buf_writer)?;
+    buf_writer.flush()?;
+    Ok(report)
+}
+
+/// A converter for the documents of one package, holding its relationships, numbering, footnotes and styles,
+/// along with the options to convert with.
+/// The state of a single conversion lives in `document`, so one `Prysm` can convert any number of documents
+#[derive(Clone, Debug, Default)]
+pub struct Prysm {
+    rels: HashMap<String, Target>,
+    options: Options,
+    numbering: HashMap<String, Vec<Level>>,
+    footnotes: HashMap<String, String>,
+    styles: HashMap<StyleId, Style>,
+}
+
+impl Prysm {
+    pub fn new(rels: HashMap<String, Target>) -> Self {
+        Prysm {
+            rels,
+            ..Prysm::default()
+        }
+    }
+
+    /// Converts with `options` instead of the defaults
+    pub fn options(mut self, options: Options) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Numbers lists by the levels of `word/numbering.xml`, as read by `numbering`
+    pub fn numbering(mut self, numbering: HashMap<String, Vec<Level>>) -> Self {
+        self.numbering = numbering;
+        self
+    }
+
+    /// Writes footnote references as the notes of `word/footnotes.xml`, as read by `footnotes`
+    pub fn footnotes(mut self, footnotes: HashMap<String, String>) -> Self {
+        self.footnotes = footnotes;
+        self
+    }
+
+    /// Formats paragraphs and runs by the styles of `word/styles.xml`, as read by `styles`
+    pub fn styles(mut self, styles: HashMap<StyleId, Style>) -> Self {
+        self.styles = styles;
+        self
+    }
+
+    /// Writes the body of the document and returns the latex packages it depends on,
+    /// along with a report of what could not be converted faithfully
+    pub fn document<R: Read, W: Write>(
+        &self,
+        parser: &mut EventReader<R>,
+        buf_writer: &mut BufWriter<W>,
+    ) -> std::io::Result<(BTreeSet<&'static str>, ConversionReport)> {
+        document(
+            parser,
+            buf_writer,
+            &self.rels,
+            &self.numbering,
+            &self.footnotes,
+            &self.styles,
+            &self.options,
+        )
+    }
+
+    /// Lists the tags that `document` sees in `parser`, in the order they open, without writing any latex.
+    /// Elements that are not recognized show up as `Tag::Unknown`, and text as `Tag::Content`
+    pub fn tags<R: Read>(
+        &self,
+        parser: &mut EventReader<R>,
+    ) -> Result<Vec<Tag>, xml::reader::Error> {
+        tags(parser)
+    }
+}
+
+fn tags<R: Read>(parser: &mut EventReader<R>) -> Result<Vec<Tag>, xml::reader::Error> {
+    let mut tags = Vec::new();
+    loop {
+        match parser.next()? {
+            XmlEvent::StartElement {
+                name, attributes, ..
+            } => match Tag::try_from((&name, &attributes)) {
+                Ok(tag) => tags.push(tag),
+                Err(InputError::MissingAttributes { id, missing }) => {
+                    log::error!("Tag '{id}' is missing attributes: {missing:?}")
                 }
+            },
+            XmlEvent::Characters(content) => tags.push(Tag::Content(content)),
+            XmlEvent::EndDocument => break,
+            _ => {}
+        }
+    }
+    Ok(tags)
+}
+
+/// Escapes text like `escape`, writing every space of a run of two or more as a control space,
+/// which latex does not collapse
+fn escape_spaces(raw: &str, substitutions: &Substitutions) -> String {
+    let mut buf = String::new();
+    let mut rest = raw;
+    while let Some(start) = rest.find("  ") {
+        let spaces = rest[start..].len() - rest[start..].trim_start_matches(' ').len();
+        buf.push_str(&escape(&rest[..start], &false, substitutions));
+        buf.push_str(&"\\ ".repeat(spaces));
+        rest = &rest[start + spaces..];
+    }
+    buf.push_str(&escape(rest, &false, substitutions));
+    buf
+}
+
+fn escape(raw: &str, math_mode: &bool, substitutions: &Substitutions) -> String {
+    let mut buf = String::new();
+    let mut chars = raw.chars().peekable();
+    while let Some(c) = chars.next() {
+        // equations typed without the equation editor spell relations such as <= in ASCII
+        let relation = chars
+            .peek()
+            .filter(|_| *math_mode)
+            .and_then(|&next| latex::ascii_relation(c, next));
+        if let Some(relation) = relation {
+            chars.next();
+            buf.push_str(relation);
+            buf.push(' ');
+            continue;
+        }
+        if let Some(latex) = substitutions.get(c, *math_mode) {
+            buf.push_str(latex);
+            continue;
+        }
+        match c {
+            // Word writes a manual line break as a vertical tab and a page break as a form feed
+            '\u{000B}' | '\u{000C}' if *math_mode => {
+                log::warn!("Dropping break {c:?} inside of an equation")
             }
-            '%' => buf.push_str("\\% "),
-            '$' => buf.push_str("\\$ "),
-            '{' => buf.push_str("\\{ "),
-            '#' => buf.push_str("\\# "),
-            '}' => buf.push_str("\\} "),
-            '~' => buf.push_str("\\~{} "),
-            '_' => buf.push_str("\\_ "),
-            '±' => buf.push_str("\\pm "),
-            '∓' => buf.push_str("\\mp "),
-            c => buf.push(c),
+            '\u{000B}' => buf.push_str("\\\\\n"),
+            '\u{000C}' => buf.push_str("\\newpage\n"),
+            c => match latex::greek(c).or_else(|| latex::relation(c)) {
+                Some(letter) if letter.starts_with('\\') && *math_mode => {
+                    buf.push_str(letter);
+                    buf.push(' ');
+                }
+                Some(letter) if letter.starts_with('\\') => {
+                    buf.push_str(&format!("\\ensuremath{{{letter}}}"));
+                }
+                Some(letter) => buf.push_str(letter),
+                None => buf.push(c),
+            },
         }
     }
     buf
@@ -336,7 +2465,7 @@ fn escape(raw: &str, math_mode: &bool) -> String {
 #[cfg(test)]
 mod test {
     use std::{
-        collections::HashMap,
+        collections::{BTreeSet, HashMap},
         io::{BufReader, BufWriter, Read, Write},
     };
 
@@ -346,11 +2475,13 @@ mod test {
     use crate::peekaboo::MockPeek;
 
     use super::{
-        blink, end_element, escape,
+        blink, document, end_element, escape, footnotes, metadata, numbering,
         peekaboo::Boo,
         relationships, start_element,
-        tag::{owned_attr, owned_name, Tag},
-        xml_event, State,
+        tag::{normalize, owned_attr, owned_name, Tag},
+        xml_event, CharacterStyle, Context, ConversionReport, DiagnosticKind, Level,
+        MathDelimiters, Metadata, Nary, Options, ParagraphStyle, Prysm, Severity, State, Style,
+        StyleId, Substitutions, Target,
     };
 
     use xml::{namespace::Namespace, reader::XmlEvent};
@@ -369,48 +2500,237 @@ mod test {
 
     #[test]
     fn unconditional_escape_works() {
-        let input = "∞π&%${#}~_±∓ abrakadabra";
-        let actual = escape(input, &false);
-        let expected = "\\infty \\pi \\& \\% \\$ \\{ \\# \\} \\~{} \\_ \\pm \\mp  abrakadabra";
+        let input = "∞&%${#}_±∓\\ abrakadabra";
+        let actual = escape(input, &false, &Substitutions::default());
+        let expected = "\\infty \\&\\%\\$\\{\\#\\}\\_\\pm \\mp \\textbackslash  abrakadabra";
         assert_eq!(actual, expected);
     }
 
-    #[test]
-    fn escape_recognizes_math_mode() {
-        let input = "<>";
-        let on = "<>";
-        let off = "\\textless \\textgreater ";
-        assert_eq!(escape(input, &true), on);
-        assert_eq!(escape(input, &false), off);
+    #[rstest]
+    #[case("<>", "<>", "\\textless \\textgreater ")]
+    #[case("a~b", "a\\sim b", "a\\textasciitilde{}b")]
+    #[case("x^2", "x^2", "x\\textasciicircum{}2")]
+    #[case("A\\B", "A\\backslash B", "A\\textbackslash B")]
+    fn escape_recognizes_math_mode(#[case] input: &str, #[case] on: &str, #[case] off: &str) {
+        assert_eq!(escape(input, &true, &Substitutions::default()), on);
+        assert_eq!(escape(input, &false, &Substitutions::default()), off);
+    }
+
+    #[rstest]
+    #[case("α", "\\alpha ", "\\ensuremath{\\alpha}")]
+    #[case("β", "\\beta ", "\\ensuremath{\\beta}")]
+    #[case("γ", "\\gamma ", "\\ensuremath{\\gamma}")]
+    #[case("δ", "\\delta ", "\\ensuremath{\\delta}")]
+    #[case("θ", "\\theta ", "\\ensuremath{\\theta}")]
+    #[case("λ", "\\lambda ", "\\ensuremath{\\lambda}")]
+    #[case("μ", "\\mu ", "\\ensuremath{\\mu}")]
+    #[case("π", "\\pi ", "\\ensuremath{\\pi}")]
+    #[case("φ", "\\varphi ", "\\ensuremath{\\varphi}")]
+    #[case("Δ", "\\Delta ", "\\ensuremath{\\Delta}")]
+    #[case("Σ", "\\Sigma ", "\\ensuremath{\\Sigma}")]
+    #[case("Ω", "\\Omega ", "\\ensuremath{\\Omega}")]
+    #[case("Β", "B", "B")]
+    fn escape_converts_greek_letters(#[case] input: &str, #[case] on: &str, #[case] off: &str) {
+        assert_eq!(escape(input, &true, &Substitutions::default()), on);
+        assert_eq!(escape(input, &false, &Substitutions::default()), off);
+    }
+
+    #[rstest]
+    #[case("a→b", "a\\rightarrow b", "a\\ensuremath{\\rightarrow}b")]
+    #[case("P⇒Q", "P\\Rightarrow Q", "P\\ensuremath{\\Rightarrow}Q")]
+    #[case("x≤1", "x\\leq 1", "x\\ensuremath{\\leq}1")]
+    #[case("x≠y", "x\\neq y", "x\\ensuremath{\\neq}y")]
+    #[case("π≈3", "\\pi \\approx 3", "\\ensuremath{\\pi}\\ensuremath{\\approx}3")]
+    #[case("x∈A", "x\\in A", "x\\ensuremath{\\in}A")]
+    #[case("A⊆B", "A\\subseteq B", "A\\ensuremath{\\subseteq}B")]
+    fn escape_converts_relations(#[case] input: &str, #[case] on: &str, #[case] off: &str) {
+        assert_eq!(escape(input, &true, &Substitutions::default()), on);
+        assert_eq!(escape(input, &false, &Substitutions::default()), off);
+    }
+
+    #[rstest]
+    #[case("f:=g", "f\\coloneqq g", "f:=g")]
+    #[case("x->0", "x\\to 0", "x-\\textgreater 0")]
+    #[case(
+        "a<=b>=c!=d",
+        "a\\leq b\\geq c\\neq d",
+        "a\\textless =b\\textgreater =c!=d"
+    )]
+    #[case("a<b=c", "a<b=c", "a\\textless b=c")]
+    fn escape_converts_ascii_relations(#[case] input: &str, #[case] on: &str, #[case] off: &str) {
+        assert_eq!(escape(input, &true, &Substitutions::default()), on);
+        assert_eq!(escape(input, &false, &Substitutions::default()), off);
     }
 
     #[test]
-    fn relationships_recognizes_missing_attributes() {
-        let raw = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
-<Relationships>
-    <Relationship Id="rId1" Target="styles.xml"/>
-    <Relationship Id="rId2" Target="https://www.lipsum.com/"/>
-    <Relationship           Target="settings.xml"/>
-    <Relationship Id="rId3"/>
-    <Relationship/>
-    <Junk/>
-</Relationships>
-"#;
-        let mut parser = xml::EventReader::new(BufReader::new(raw.as_bytes()));
-        let rels = relationships(&mut parser);
-        assert!(rels.is_ok());
-        let rels = rels.unwrap();
-        assert_eq!(rels.len(), 2);
+    fn document_loads_mathtools_for_definitions() {
+        let body = r#"<w:p><m:oMathPara><m:oMath><m:r><m:t>f:=x->1</m:t></m:r></m:oMath></m:oMathPara></w:p>"#;
+        let (actual, packages) = convert_with_packages(body, &HashMap::new());
+        assert_eq!(actual, "$$f\\coloneqq x\\to 1$$\n\n\n");
+        assert!(packages.contains("mathtools"));
+    }
 
-        assert!(rels.contains_key("rId1"));
-        assert_eq!(rels.get("rId1").unwrap(), "styles.xml");
+    #[rstest]
+    #[case("a\u{000C}b", "ab", "a\\newpage\nb")]
+    #[case("a\u{000B}b", "ab", "a\\\\\nb")]
+    fn escape_converts_breaks(#[case] input: &str, #[case] on: &str, #[case] off: &str) {
+        assert_eq!(escape(input, &true, &Substitutions::default()), on);
+        assert_eq!(escape(input, &false, &Substitutions::default()), off);
+    }
 
-        assert!(rels.contains_key("rId2"));
-        assert_eq!(rels.get("rId2").unwrap(), "https://www.lipsum.com/");
+    #[test]
+    fn escape_applies_substitutions() {
+        let substitutions = Options::default()
+            .substitute('↯', "\\lightning ")
+            .substitutions;
+        assert_eq!(escape("a↯b", &true, &substitutions), "a\\lightning b");
+        assert_eq!(escape("a↯b", &true, &Substitutions::default()), "a↯b");
     }
 
     #[test]
-    #[should_panic]
+    fn escape_applies_substitutions_of_mode() {
+        let mut substitutions = Substitutions::default();
+        substitutions.math.insert('~', "\\approx ".to_string());
+        assert_eq!(escape("a~b", &true, &substitutions), "a\\approx b");
+        assert_eq!(
+            escape("a~b", &false, &substitutions),
+            "a\\textasciitilde{}b"
+        );
+    }
+
+    #[test]
+    fn document_applies_substitutions() {
+        let body = r#"<w:p><w:r><w:t>A ⇒ B, 100%</w:t></w:r></w:p>"#;
+        let options = Options::default()
+            .substitute('⇒', "$\\Rightarrow$")
+            .substitute('%', "\\,\\%");
+        let (actual, _) = convert_with_options(body, &HashMap::new(), &options);
+        assert_eq!(actual, "A $\\Rightarrow$ B, 100\\,\\%\n\n");
+    }
+
+    #[test]
+    fn convert_writes_body_from_memory() {
+        let document_xml = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships">
+<w:body><w:p><w:hyperlink r:id="rId2"><w:r><w:t>Lorem</w:t></w:r></w:hyperlink><w:hyperlink r:id="rId9"><w:r><w:t>ipsum</w:t></w:r></w:hyperlink></w:p></w:body>
+</w:document>
+"#;
+        let rels_xml = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+    <Relationship Id="rId2" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/hyperlink" Target="https://www.lipsum.com/" TargetMode="External"/>
+</Relationships>
+"#;
+        let mut latex = Vec::new();
+        let report =
+            super::convert(document_xml.as_bytes(), rels_xml.as_bytes(), &mut latex).unwrap();
+        assert_eq!(
+            String::from_utf8(latex).unwrap(),
+            "\\href{https://www.lipsum.com/}{Lorem}ipsum\n\n"
+        );
+        assert_eq!(report.count(Severity::Error), 1);
+    }
+
+    #[rstest]
+    #[case::defaults(Options::default(), "\\href{https://www.lipsum.com/}{Lorem} ipsum\n\n")]
+    #[case::substitutions(
+        Options::default().substitute('m', "M"),
+        "\\href{https://www.lipsum.com/}{LoreM} ipsuM\n\n"
+    )]
+    fn prysm_converts_document(#[case] options: Options, #[case] expected: &str) {
+        let raw = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships">
+<w:body><w:p><w:hyperlink r:id="rId2"><w:r><w:t>Lorem</w:t></w:r></w:hyperlink><w:r><w:t xml:space="preserve"> ipsum</w:t></w:r></w:p></w:body>
+</w:document>
+"#;
+        let prysm = super::Prysm::new(
+            [(
+                "rId2".to_string(),
+                Target::External("https://www.lipsum.com/".to_string()),
+            )]
+            .into(),
+        )
+        .options(options);
+        let mut parser = xml::EventReader::new(raw.as_bytes());
+        let mut buf_writer = BufWriter::new(Vec::new());
+        let (_, report) = prysm.document(&mut parser, &mut buf_writer).unwrap();
+        assert_eq!(
+            String::from_utf8(buf_writer.into_inner().unwrap()).unwrap(),
+            expected
+        );
+        assert!(report.is_clean());
+    }
+
+    #[test]
+    fn prysm_writes_footnotes() {
+        let raw = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">
+<w:body><w:p><w:r><w:t>Claim</w:t></w:r><w:r><w:footnoteReference w:id="1"/></w:r></w:p></w:body>
+</w:document>
+"#;
+        let footnotes = HashMap::from([("1".to_string(), "A note.".to_string())]);
+        let prysm = super::Prysm::new(HashMap::new()).footnotes(footnotes);
+        let mut parser = xml::EventReader::new(raw.as_bytes());
+        let mut buf_writer = BufWriter::new(Vec::new());
+        let (_, report) = prysm.document(&mut parser, &mut buf_writer).unwrap();
+        assert_eq!(
+            String::from_utf8(buf_writer.into_inner().unwrap()).unwrap(),
+            "Claim\\footnote{A note.}\n\n"
+        );
+        assert!(report.is_clean());
+    }
+
+    #[test]
+    fn relationships_recognizes_missing_attributes() {
+        let raw = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships>
+    <Relationship Id="rId1" Target="styles.xml"/>
+    <Relationship Id="rId2" Target="https://www.lipsum.com/"/>
+    <Relationship           Target="settings.xml"/>
+    <Relationship Id="rId3"/>
+    <Relationship/>
+    <Junk/>
+</Relationships>
+"#;
+        let mut parser = xml::EventReader::new(BufReader::new(raw.as_bytes()));
+        let rels = relationships(&mut parser);
+        assert!(rels.is_ok());
+        let rels = rels.unwrap();
+        assert_eq!(rels.len(), 2);
+
+        assert!(rels.contains_key("rId1"));
+        assert_eq!(rels["rId1"], Target::Internal("styles.xml".to_string()));
+
+        assert!(rels.contains_key("rId2"));
+        assert_eq!(
+            rels["rId2"],
+            Target::Internal("https://www.lipsum.com/".to_string())
+        );
+    }
+
+    #[test]
+    fn relationships_reads_target_mode() {
+        let raw = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+    <Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/styles" Target="styles.xml"/>
+    <Relationship Id="rId2" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/hyperlink" Target="https://www.lipsum.com/" TargetMode="External"/>
+    <Relationship Id="rId3" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/image" Target="media/image1.png" TargetMode="Internal"/>
+</Relationships>
+"#;
+        let mut parser = xml::EventReader::new(BufReader::new(raw.as_bytes()));
+        let rels = relationships(&mut parser).unwrap();
+        assert_eq!(rels["rId1"], Target::Internal("styles.xml".to_string()));
+        assert_eq!(
+            rels["rId2"],
+            Target::External("https://www.lipsum.com/".to_string())
+        );
+        assert_eq!(
+            rels["rId3"],
+            Target::Internal("media/image1.png".to_string())
+        );
+    }
+
+    #[test]
+    #[should_panic]
     fn relationships_recognizes_xml_error() {
         let raw = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
 Relationships>
@@ -428,339 +2748,2100 @@ Relationships>
         let _should_panic = rels.unwrap();
     }
 
-    fn drain<W: Write>(buf_writer: &mut BufWriter<W>) -> std::io::Result<String> {
-        let mut s = String::new();
-        buf_writer.buffer().read_to_string(&mut s)?;
-        buf_writer.flush()?;
-        Ok(s)
+    const LEGAL_NUMBERING: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:numbering xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">
+    <w:abstractNum w:abstractNumId="0">
+        <w:lvl w:ilvl="0"><w:start w:val="1"/><w:numFmt w:val="decimal"/><w:lvlText w:val="%1."/></w:lvl>
+        <w:lvl w:ilvl="1"><w:start w:val="1"/><w:isLgl/><w:numFmt w:val="lowerLetter"/><w:lvlText w:val="%1.%2."/></w:lvl>
+    </w:abstractNum>
+    <w:abstractNum w:abstractNumId="1">
+        <w:lvl w:ilvl="0"><w:numFmt w:val="bullet"/><w:lvlText w:val="•"/></w:lvl>
+    </w:abstractNum>
+    <w:num w:numId="1"><w:abstractNumId w:val="0"/></w:num>
+    <w:num w:numId="2"><w:abstractNumId w:val="1"/></w:num>
+    <w:num w:numId="3"><w:abstractNumId w:val="7"/></w:num>
+</w:numbering>
+"#;
+
+    #[test]
+    fn numbering_reads_levels() {
+        let mut parser = xml::EventReader::new(BufReader::new(LEGAL_NUMBERING.as_bytes()));
+        let numbering = numbering(&mut parser).unwrap();
+        assert_eq!(numbering.len(), 2);
+
+        let legal = numbering.get("1").unwrap();
+        assert_eq!(legal.len(), 2);
+        assert_eq!(legal[0].text, "%1.");
+        assert!(!legal[0].legal);
+        assert_eq!(legal[1].format, "lowerLetter");
+        assert_eq!(legal[1].text, "%1.%2.");
+        assert!(legal[1].legal);
+
+        assert_eq!(numbering.get("2").unwrap()[0].format, "bullet");
+    }
+
+    #[test]
+    fn metadata_reads_core_properties() {
+        let core = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<cp:coreProperties xmlns:cp="http://schemas.openxmlformats.org/package/2006/metadata/core-properties" xmlns:dc="http://purl.org/dc/elements/1.1/" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance">
+    <dc:title>Quarterly Report</dc:title>
+    <dc:creator>Jane Doe</dc:creator>
+    <cp:lastModifiedBy>John Doe</cp:lastModifiedBy>
+    <dcterms:created xsi:type="dcterms:W3CDTF">2024-03-01T10:00:00Z</dcterms:created>
+</cp:coreProperties>
+"#;
+        let mut parser = xml::EventReader::new(BufReader::new(core.as_bytes()));
+        assert_eq!(
+            metadata(&mut parser).unwrap(),
+            Metadata {
+                title: Some("Quarterly Report".to_string()),
+                author: Some("Jane Doe".to_string()),
+                date: Some("2024-03-01".to_string()),
+            }
+        );
+
+        let core = r#"<cp:coreProperties xmlns:cp="http://schemas.openxmlformats.org/package/2006/metadata/core-properties" xmlns:dc="http://purl.org/dc/elements/1.1/"><dc:title/></cp:coreProperties>"#;
+        let mut parser = xml::EventReader::new(BufReader::new(core.as_bytes()));
+        assert_eq!(metadata(&mut parser).unwrap(), Metadata::default());
+    }
+
+    #[test]
+    fn tags_lists_tags_without_converting() {
+        let body = r#"<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main" xmlns:asvg="http://schemas.microsoft.com/office/drawing/2016/SVG/main"><w:p><w:r><w:t>Hi</w:t><asvg:svgBlip/></w:r><w:proofErr/></w:p></w:document>"#;
+        let mut parser = xml::EventReader::new(body.as_bytes());
+        assert_eq!(
+            Prysm::default().tags(&mut parser).unwrap(),
+            vec![
+                Tag::WDocument,
+                Tag::WParagraph,
+                Tag::WRun,
+                Tag::WText,
+                Tag::Content("Hi".to_string()),
+                Tag::Unknown {
+                    id: "w:proofErr".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn document_writes_legal_numbering() {
+        let mut parser = xml::EventReader::new(BufReader::new(LEGAL_NUMBERING.as_bytes()));
+        let numbering = numbering(&mut parser).unwrap();
+        let item = |level: usize, text: &str| {
+            format!(
+                r#"<w:p><w:pPr><w:numPr><w:ilvl w:val="{level}"/><w:numId w:val="1"/></w:numPr></w:pPr><w:r><w:t>{text}</w:t></w:r></w:p>"#
+            )
+        };
+        let body = [
+            item(0, "Scope"),
+            item(1, "Purpose"),
+            item(1, "Terms"),
+            item(0, "Rules"),
+            r#"<w:p><w:r><w:t>After</w:t></w:r></w:p>"#.to_string(),
+        ]
+        .concat();
+        let (actual, packages) =
+            convert_with_numbering(&body, &HashMap::new(), &numbering, &Options::default());
+        assert_eq!(
+            actual,
+            "\\begin{enumerate}\n\\item Scope\n\n\
+             \\begin{enumerate}[label=\\arabic{enumi}.\\arabic*.]\n\\item Purpose\n\n\
+             \\item Terms\n\n\\end{enumerate}\n\
+             \\item Rules\n\n\\end{enumerate}\n\n\
+             After\n\n"
+        );
+        assert!(packages.contains("enumitem"));
+    }
+
+    #[test]
+    fn document_writes_bullets_and_closes_lists() {
+        let mut parser = xml::EventReader::new(BufReader::new(LEGAL_NUMBERING.as_bytes()));
+        let numbering = numbering(&mut parser).unwrap();
+        let body = r#"<w:p><w:pPr><w:numPr><w:ilvl w:val="0"/><w:numId w:val="2"/></w:numPr></w:pPr><w:r><w:t>Point</w:t></w:r></w:p>"#;
+        let (actual, packages) =
+            convert_with_numbering(body, &HashMap::new(), &numbering, &Options::default());
+        assert_eq!(
+            actual,
+            "\\begin{itemize}\n\\item Point\n\n\\end{itemize}\n\n"
+        );
+        assert!(!packages.contains("enumitem"));
+    }
+
+    #[test]
+    fn document_nests_bullets_under_numbers() {
+        let raw = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:numbering xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">
+    <w:abstractNum w:abstractNumId="0">
+        <w:lvl w:ilvl="0"><w:numFmt w:val="decimal"/><w:lvlText w:val="%1."/></w:lvl>
+        <w:lvl w:ilvl="1"><w:numFmt w:val="bullet"/><w:lvlText w:val="o"/></w:lvl>
+        <w:lvl w:ilvl="2"><w:numFmt w:val="lowerRoman"/><w:lvlText w:val="%1.%3"/></w:lvl>
+    </w:abstractNum>
+    <w:num w:numId="1"><w:abstractNumId w:val="0"/></w:num>
+</w:numbering>
+"#;
+        let mut parser = xml::EventReader::new(BufReader::new(raw.as_bytes()));
+        let numbering = numbering(&mut parser).unwrap();
+        let item = |level: usize, text: &str| {
+            format!(
+                r#"<w:p><w:pPr><w:numPr><w:ilvl w:val="{level}"/><w:numId w:val="1"/></w:numPr></w:pPr><w:r><w:t>{text}</w:t></w:r></w:p>"#
+            )
+        };
+        let body = [
+            item(0, "Fruit"),
+            item(1, "Apple"),
+            item(2, "Core"),
+            item(1, "Pear"),
+            item(0, "Bread"),
+        ]
+        .concat();
+        let (actual, _) =
+            convert_with_numbering(&body, &HashMap::new(), &numbering, &Options::default());
+        assert_eq!(
+            actual,
+            "\\begin{enumerate}\n\\item Fruit\n\n\
+             \\begin{itemize}\n\\item Apple\n\n\
+             \\begin{enumerate}[label=\\arabic{enumi}.\\roman*]\n\\item Core\n\n\\end{enumerate}\n\
+             \\item Pear\n\n\\end{itemize}\n\
+             \\item Bread\n\n\\end{enumerate}\n\n"
+        );
+    }
+
+    #[test]
+    fn footnotes_reads_notes() {
+        let raw = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:footnotes xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">
+    <w:footnote w:type="separator" w:id="-1"><w:p><w:r><w:separator/></w:r></w:p></w:footnote>
+    <w:footnote w:id="1">
+        <w:p>
+            <w:r><w:rPr><w:rStyle w:val="FootnoteReference"/></w:rPr><w:footnoteRef/></w:r>
+            <w:r><w:t xml:space="preserve"> See ~5 pages.</w:t></w:r>
+        </w:p>
+    </w:footnote>
+</w:footnotes>
+"#;
+        let mut parser = xml::EventReader::new(BufReader::new(raw.as_bytes()));
+        let (notes, _, _) = footnotes(
+            &mut parser,
+            &HashMap::new(),
+            &HashMap::new(),
+            &HashMap::new(),
+            &Options::default(),
+        )
+        .unwrap();
+        assert_eq!(notes.get("1").unwrap(), "See \\textasciitilde{}5 pages.");
+    }
+
+    #[test]
+    fn footnotes_close_lists_and_return_packages() {
+        let raw = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:footnotes xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">
+    <w:footnote w:id="1">
+        <w:p><w:pPr><w:numPr><w:ilvl w:val="0"/><w:numId w:val="1"/></w:numPr></w:pPr><w:r><w:t>Item</w:t></w:r></w:p>
+    </w:footnote>
+    <w:footnote w:id="2">
+        <w:p><w:r><w:rPr><w:color w:val="FF0000"/></w:rPr><w:t>Red</w:t></w:r></w:p>
+    </w:footnote>
+</w:footnotes>
+"#;
+        let mut parser = xml::EventReader::new(BufReader::new(raw.as_bytes()));
+        let (notes, packages, _) = footnotes(
+            &mut parser,
+            &HashMap::new(),
+            &HashMap::new(),
+            &HashMap::new(),
+            &Options::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            notes.get("1").unwrap(),
+            "\\begin{enumerate}\n\\item Item\n\n\\end{enumerate}"
+        );
+        assert!(packages.contains("xcolor"));
+    }
+
+    #[test]
+    fn document_drops_typed_footnote_marks() {
+        let footnotes = HashMap::from([("1".to_string(), "A note.".to_string())]);
+        let body = r#"<w:p><w:r><w:t>Claim</w:t></w:r><w:r><w:rPr><w:rStyle w:val="FootnoteReference"/></w:rPr><w:footnoteReference w:id="1"/><w:t>1</w:t></w:r><w:r><w:t>.</w:t></w:r></w:p>"#;
+        assert_eq!(
+            convert_with_footnotes(body, &footnotes),
+            "Claim\\footnote{A note.}.\n\n"
+        );
+    }
+
+    #[test]
+    fn document_formats_run_by_inherited_style() {
+        let styles = HashMap::from([(
+            "Emphasis".to_string(),
+            Style {
+                based_on: Some("Strong".to_string()),
+                character: CharacterStyle {
+                    color: Some("FF0000".to_string()),
+                    vert_align: Some("superscript".to_string()),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        )]);
+        let body = r#"<w:p><w:r><w:rPr><w:rStyle w:val="Emphasis"/><w:vertAlign w:val="baseline"/></w:rPr><w:t>Red</w:t></w:r><w:r><w:rPr><w:rStyle w:val="Missing"/></w:rPr><w:t xml:space="preserve"> plain</w:t></w:r></w:p>"#;
+        let (actual, packages) = convert_with_styles(body, &styles);
+        assert_eq!(actual, "\\textcolor[HTML]{FF0000}{Red} plain\n\n");
+        assert!(packages.contains("xcolor"));
+    }
+
+    #[rstest]
+    #[case("0", "\\section{Introduction}\n\n")]
+    #[case("2", "\\subsubsection{Introduction}\n\n")]
+    #[case("9", "Introduction\n\n")]
+    fn document_sections_by_outline_level(#[case] level: &str, #[case] latex: &str) {
+        let body = format!(
+            r#"<w:p><w:pPr><w:outlineLvl w:val="{level}"/></w:pPr><w:r><w:t>Introduction</w:t></w:r></w:p>"#
+        );
+        assert_eq!(convert(&body, &HashMap::new()), latex);
+    }
+
+    #[test]
+    fn document_sections_by_style_outline_level() {
+        let styles = HashMap::from([(
+            "Heading2".to_string(),
+            Style {
+                paragraph: ParagraphStyle {
+                    outline_level: Some(1),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        )]);
+        let body = r#"<w:p><w:pPr><w:pStyle w:val="Heading2"/></w:pPr><w:r><w:t>Scope</w:t></w:r></w:p><w:p><w:pPr><w:pStyle w:val="Heading2"/><w:outlineLvl w:val="9"/></w:pPr><w:r><w:t>Plain</w:t></w:r></w:p>"#;
+        let (actual, _) = convert_with_styles(body, &styles);
+        assert_eq!(actual, "\\subsection{Scope}\n\nPlain\n\n");
+    }
+
+    #[test]
+    fn document_recognizes_title_page_without_converting_it() {
+        // headers and footers are not converted, so a different first page has nothing to apply to
+        let body = r#"<w:p><w:r><w:t>Text</w:t></w:r></w:p><w:sectPr><w:titlePg/></w:sectPr>"#;
+        let xml = format!(
+            r#"<w:body xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">{body}</w:body>"#
+        );
+        let mut parser = xml::EventReader::new(xml.as_bytes());
+        let tags = Prysm::default().tags(&mut parser).unwrap();
+        assert_eq!(tags.last(), Some(&Tag::WTitlePg));
+        assert_eq!(convert(body, &HashMap::new()), "Text\n\n");
+    }
+
+    #[rstest]
+    #[case(
+        r#"<w:p><w:r><w:t>Wide</w:t></w:r></w:p><w:sectPr><w:pgSz w:w="16838" w:h="11906" w:orient="landscape"/></w:sectPr>"#,
+        "\\begin{landscape}\nWide\n\n\\end{landscape}\n\n"
+    )]
+    #[case(
+        r#"<w:p><w:pPr><w:sectPr><w:pgSz w:w="16838" w:h="11906" w:orient="landscape"/></w:sectPr></w:pPr><w:r><w:t>Wide</w:t></w:r></w:p><w:p><w:r><w:t>Tall</w:t></w:r></w:p><w:sectPr><w:pgSz w:w="11906" w:h="16838"/></w:sectPr>"#,
+        "\\begin{landscape}\nWide\n\n\\end{landscape}\n\nTall\n\n"
+    )]
+    #[case(
+        r#"<w:p><w:pPr><w:sectPr><w:pgSz w:w="11906" w:h="16838"/></w:sectPr></w:pPr><w:r><w:t>Tall</w:t></w:r></w:p><w:p><w:r><w:t>Wide</w:t></w:r></w:p><w:sectPr><w:pgSz w:w="16838" w:h="11906"/></w:sectPr>"#,
+        "Tall\n\n\\begin{landscape}\nWide\n\n\\end{landscape}\n\n"
+    )]
+    fn document_turns_landscape_sections(#[case] body: &str, #[case] expected: &str) {
+        let (actual, packages) = convert_with_packages(body, &HashMap::new());
+        assert_eq!(actual, expected);
+        assert!(packages.contains("pdflscape"));
+    }
+
+    fn drain<W: Write>(buf_writer: &mut BufWriter<W>) -> std::io::Result<String> {
+        let mut s = String::new();
+        buf_writer.buffer().read_to_string(&mut s)?;
+        buf_writer.flush()?;
+        Ok(s)
+    }
+
+    fn convert(body: &str, rels: &HashMap<String, Target>) -> String {
+        convert_with_packages(body, rels).0
+    }
+
+    fn convert_with_packages(
+        body: &str,
+        rels: &HashMap<String, Target>,
+    ) -> (String, BTreeSet<&'static str>) {
+        convert_with_options(body, rels, &Options::default())
+    }
+
+    fn convert_with_options(
+        body: &str,
+        rels: &HashMap<String, Target>,
+        options: &Options,
+    ) -> (String, BTreeSet<&'static str>) {
+        convert_with_numbering(body, rels, &HashMap::new(), options)
+    }
+
+    fn convert_with_footnotes(body: &str, footnotes: &HashMap<String, String>) -> String {
+        convert_package(
+            body,
+            &HashMap::new(),
+            &HashMap::new(),
+            footnotes,
+            &HashMap::new(),
+            &Options::default(),
+        )
+        .0
+    }
+
+    fn convert_with_styles(
+        body: &str,
+        styles: &HashMap<StyleId, Style>,
+    ) -> (String, BTreeSet<&'static str>) {
+        convert_package(
+            body,
+            &HashMap::new(),
+            &HashMap::new(),
+            &HashMap::new(),
+            styles,
+            &Options::default(),
+        )
+    }
+
+    fn convert_with_numbering(
+        body: &str,
+        rels: &HashMap<String, Target>,
+        numbering: &HashMap<String, Vec<Level>>,
+        options: &Options,
+    ) -> (String, BTreeSet<&'static str>) {
+        convert_package(
+            body,
+            rels,
+            numbering,
+            &HashMap::new(),
+            &HashMap::new(),
+            options,
+        )
+    }
+
+    fn convert_package(
+        body: &str,
+        rels: &HashMap<String, Target>,
+        numbering: &HashMap<String, Vec<Level>>,
+        footnotes: &HashMap<String, String>,
+        styles: &HashMap<StyleId, Style>,
+        options: &Options,
+    ) -> (String, BTreeSet<&'static str>) {
+        let (latex, packages, _) =
+            convert_reporting(body, rels, numbering, footnotes, styles, options);
+        (latex, packages)
+    }
+
+    fn convert_reporting(
+        body: &str,
+        rels: &HashMap<String, Target>,
+        numbering: &HashMap<String, Vec<Level>>,
+        footnotes: &HashMap<String, String>,
+        styles: &HashMap<StyleId, Style>,
+        options: &Options,
+    ) -> (String, BTreeSet<&'static str>, ConversionReport) {
+        let raw = format!(
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:document
+    xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main"
+    xmlns:m="http://schemas.openxmlformats.org/officeDocument/2006/math"
+    xmlns:pic="http://schemas.openxmlformats.org/drawingml/2006/picture"
+    xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships"
+    xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"
+    xmlns:w15="http://schemas.microsoft.com/office/word/2012/wordml"
+    xmlns:wp="http://schemas.openxmlformats.org/drawingml/2006/wordprocessingDrawing">
+<w:body>{body}</w:body>
+</w:document>
+"#
+        );
+        let mut parser = xml::EventReader::new(BufReader::new(raw.as_bytes()));
+        let mut buf_writer = BufWriter::new(Vec::new());
+        let (packages, report) = document(
+            &mut parser,
+            &mut buf_writer,
+            rels,
+            numbering,
+            footnotes,
+            styles,
+            options,
+        )
+        .unwrap();
+        (drain(&mut buf_writer).unwrap(), packages, report)
+    }
+
+    struct Fixture {
+        pub buf_writer: BufWriter<Vec<u8>>,
+        pub rels: HashMap<String, Target>,
+        pub stack: Boo<Tag>,
+        pub context: Context,
+    }
+
+    impl Default for Fixture {
+        fn default() -> Self {
+            Self {
+                buf_writer: BufWriter::new(Vec::new()),
+                rels: Default::default(),
+                stack: Default::default(),
+                context: Context::default(),
+            }
+        }
+    }
+
+    #[rstest]
+    #[case(Tag::MRad, "\\sqrt")]
+    #[case(Tag::MDeg, "")]
+    #[case(Tag::MSub, "_{")]
+    #[case(Tag::MSup, "^{")]
+    #[case(Tag::MPre, "{}")]
+    #[case(Tag::MNum, "{")]
+    #[case(Tag::MDen, "{")]
+    fn start_element_works_with_simple_tags(#[case] input: Tag, #[case] output: &'static str) {
+        let Fixture {
+            mut buf_writer,
+            rels: _,
+            stack,
+            mut context,
+        } = Fixture::default();
+
+        let (name, attributes) = input.to_owned().unwrap();
+        let state = start_element(&mut buf_writer, &stack, &name, &attributes, &mut context);
+        assert!(state.is_ok());
+        let state = state.unwrap();
+        assert!(matches!(state, State::OpenedTag(_)));
+        if let State::OpenedTag(tag) = state {
+            assert_eq!(tag, input);
+        }
+
+        assert_eq!(drain(&mut buf_writer).unwrap(), output);
+    }
+
+    #[test]
+    fn start_element_recognizes_missing_attributes() {
+        let Fixture {
+            mut buf_writer,
+            rels: _,
+            stack,
+            mut context,
+        } = Fixture::default();
+
+        let name = owned_name("a", "blip");
+        let state = start_element(&mut buf_writer, &stack, &name, &vec![], &mut context);
+        assert!(state.is_ok());
+        let state = state.unwrap();
+        assert_eq!(
+            state,
+            State::OpenedTag(Tag::Unknown {
+                id: "a:blip".to_string()
+            })
+        );
+
+        assert_eq!(drain(&mut buf_writer).unwrap(), "");
+    }
+
+    #[rstest]
+    #[case(Tag::MoMath, owned_name("m", "box"), Some("m:box"))]
+    #[case(Tag::MoMath, owned_name("m", "oMathParaPr"), None)]
+    #[case(Tag::MNaryPr, owned_name("m", "limLoc"), None)]
+    #[case(Tag::Unknown { id: "m:rPr".to_string() }, owned_name("m", "sty"), None)]
+    #[case(Tag::WRun, owned_name("w", "sym"), None)]
+    fn start_element_audits_unsupported_math(
+        #[case] parent: Tag,
+        #[case] name: xml::name::OwnedName,
+        #[case] audited: Option<&str>,
+    ) {
+        let Fixture {
+            mut buf_writer,
+            rels: _,
+            stack: _,
+            mut context,
+        } = Fixture::default();
+        let stack = Boo::from(vec![parent]);
+
+        let state = start_element(&mut buf_writer, &stack, &name, &vec![], &mut context);
+        assert!(matches!(state, Ok(State::OpenedTag(Tag::Unknown { .. }))));
+        assert_eq!(
+            context.unsupported_math.iter().next().map(String::as_str),
+            audited
+        );
+        assert_eq!(drain(&mut buf_writer).unwrap(), "");
+    }
+
+    #[rstest]
+    #[case(Tag::WRun, owned_name("w", "sym"), false, false)]
+    #[case(Tag::WRun, owned_name("w", "sym"), true, true)]
+    #[case(Tag::MoMath, owned_name("m", "box"), true, true)]
+    #[case(Tag::WParagraph, owned_name("w", "customPr"), true, false)]
+    #[case(Tag::WRunPr, owned_name("w", "lang"), true, false)]
+    #[case(Tag::WParagraph, owned_name("w", "proofErr"), true, false)]
+    fn start_element_fails_on_unknown_tag_when_strict(
+        #[case] parent: Tag,
+        #[case] name: xml::name::OwnedName,
+        #[case] strict: bool,
+        #[case] fails: bool,
+    ) {
+        let Fixture {
+            mut buf_writer,
+            rels: _,
+            stack: _,
+            mut context,
+        } = Fixture::default();
+        context.options.strict = strict;
+        let stack = Boo::from(vec![parent]);
+
+        let state = start_element(&mut buf_writer, &stack, &name, &vec![], &mut context);
+        match state {
+            Err(error) => {
+                assert!(fails);
+                assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+                assert!(error.to_string().contains(&normalize(&name)));
+            }
+            Ok(state) => {
+                assert!(!fails);
+                assert!(matches!(state, State::OpenedTag(Tag::Unknown { .. })));
+            }
+        }
+    }
+
+    #[test]
+    fn document_accepts_example_when_strict() {
+        let options = Options {
+            strict: true,
+            ..Options::default()
+        };
+        // example.docx has no notes, so these are written the way Word writes them
+        let raw = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:footnotes xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">
+    <w:footnote w:type="separator" w:id="-1"><w:p><w:pPr><w:spacing w:after="0" w:line="240" w:lineRule="auto"/></w:pPr><w:r><w:separator/></w:r></w:p></w:footnote>
+    <w:footnote w:type="continuationSeparator" w:id="0"><w:p><w:pPr><w:spacing w:after="0" w:line="240" w:lineRule="auto"/></w:pPr><w:r><w:continuationSeparator/></w:r></w:p></w:footnote>
+    <w:footnote w:id="1"><w:p><w:pPr><w:pStyle w:val="FootnoteText"/></w:pPr><w:r><w:rPr><w:rStyle w:val="FootnoteReference"/></w:rPr><w:footnoteRef/></w:r><w:r><w:t xml:space="preserve"> A note.</w:t></w:r></w:p></w:footnote>
+</w:footnotes>
+"#;
+        let mut parser = xml::EventReader::new(BufReader::new(raw.as_bytes()));
+        let notes = footnotes(
+            &mut parser,
+            &HashMap::new(),
+            &HashMap::new(),
+            &HashMap::new(),
+            &options,
+        );
+        let (notes, _, _) = notes.unwrap();
+        assert_eq!(notes.get("1").unwrap(), "A note.");
+
+        let file = std::fs::File::open("example/word/document.xml").unwrap();
+        let mut parser = xml::EventReader::new(BufReader::new(file));
+        let prysm = super::Prysm::new(HashMap::new())
+            .options(options)
+            .footnotes(notes);
+        let result = prysm.document(&mut parser, &mut BufWriter::new(Vec::new()));
+        assert!(result.is_ok(), "{result:?}");
+    }
+
+    #[test]
+    fn start_element_recognizes_momathpara() {
+        let Fixture {
+            mut buf_writer,
+            rels: _,
+            stack,
+            mut context,
+        } = Fixture::default();
+
+        let name = owned_name("m", "oMathPara");
+
+        let state = start_element(&mut buf_writer, &stack, &name, &vec![], &mut context);
+        assert!(state.is_ok());
+        let state = state.unwrap();
+        assert!(matches!(state, State::OpenedTag(_)));
+        if let State::OpenedTag(tag) = state {
+            assert_eq!(tag, Tag::MoMathPara);
+        }
+
+        assert_eq!(drain(&mut buf_writer).unwrap(), "$$");
+        assert_eq!(context.math_depth, 1);
+
+        let state = start_element(&mut buf_writer, &stack, &name, &vec![], &mut context);
+        assert!(state.is_ok());
+        let state = state.unwrap();
+        assert!(matches!(state, State::OpenedTag(_)));
+        if let State::OpenedTag(tag) = state {
+            assert_eq!(tag, Tag::MoMathPara);
+        }
+
+        assert_eq!(drain(&mut buf_writer).unwrap(), "");
+        assert_eq!(context.math_depth, 2);
+    }
+
+    #[test]
+    fn start_element_collects_nary_chr() {
+        let Fixture {
+            mut buf_writer,
+            rels: _,
+            mut stack,
+            mut context,
+        } = Fixture::default();
+        stack.extend([Tag::MNary, Tag::MNaryPr]);
+        context.naries.push(Nary::default());
+
+        let name = owned_name("m", "chr");
+        let attr = vec![owned_attr("m", "val", "∑")];
+        let state = start_element(&mut buf_writer, &stack, &name, &attr, &mut context);
+        assert!(state.is_ok());
+        assert_eq!(
+            state.unwrap(),
+            State::OpenedTag(Tag::MChr {
+                value: "∑".to_string()
+            })
+        );
+
+        assert_eq!(context.naries.last().unwrap().chr.as_deref(), Some("∑"));
+        assert_eq!(drain(&mut buf_writer).unwrap(), "");
+    }
+
+    #[test]
+    fn start_element_ignores_mctrlpr() {
+        let Fixture {
+            mut buf_writer,
+            rels: _,
+            stack,
+            mut context,
+        } = Fixture::default();
+
+        let name = owned_name("m", "ctrlPr");
+        let state = start_element(&mut buf_writer, &stack, &name, &vec![], &mut context);
+        assert!(state.is_ok());
+        let state = state.unwrap();
+        assert_eq!(state, State::OpenedTag(Tag::MCtrlPr));
+
+        assert_eq!(context, Context::default());
+        assert_eq!(drain(&mut buf_writer).unwrap(), "");
+    }
+
+    #[rstest]
+    #[case(0, 0, "")]
+    #[case(1, 0, "$$\n")]
+    #[case(2, 1, "")]
+    fn end_element_recognizes_momathpara(
+        #[case] depth: usize,
+        #[case] remaining: usize,
+        #[case] latex: &str,
+    ) {
+        let Fixture {
+            mut buf_writer,
+            rels,
+            stack: _,
+            mut context,
+        } = Fixture::default();
+
+        let stack = Unimock::new((
+            MockPeek::reset.each_call(matching!()).returns(()),
+            MockPeek::peek.each_call(matching!()).returns(None::<Tag>),
+            MockPeek::last
+                .each_call(matching!())
+                .returns(Some(Tag::MoMathPara))
+                .once(),
+        ));
+
+        context.math_depth = depth;
+        let state = end_element(&mut buf_writer, &stack, &rels, &mut context);
+        assert!(matches!(state, Ok(State::ClosedTag)));
+        assert_eq!(context.math_depth, remaining);
+        assert_eq!(drain(&mut buf_writer).unwrap(), latex);
+    }
+
+    #[rstest]
+    #[case(Tag::WParagraph, "\n\n")]
+    #[case(Tag::WBookmarkStart { anchor: "Anchor".to_string() }, "\\label{Anchor}\\hypertarget{Anchor}{")]
+    #[case(Tag::MDelim, ")")]
+    #[case(Tag::MDeg, "]{")]
+    #[case(Tag::MSub, "}")]
+    #[case(Tag::MSup, "}")]
+    #[case(Tag::MNum, "}")]
+    #[case(Tag::MDen, "}")]
+    #[case(Tag::MRad, "}")]
+    #[case(Tag::WBookmarkEnd, "}")]
+    fn end_element_recognizes_lonely_tags(#[case] tag: Tag, #[case] latex: &'static str) {
+        let Fixture {
+            mut buf_writer,
+            rels,
+            stack: _,
+            mut context,
+        } = Fixture::default();
+
+        let stack = Unimock::new((
+            MockPeek::reset.each_call(matching!()).returns(()),
+            MockPeek::peek.each_call(matching!()).returns(None::<Tag>),
+            MockPeek::last
+                .each_call(matching!())
+                .returns(Some(tag))
+                .once(),
+        ));
+
+        let state = end_element(&mut buf_writer, &stack, &rels, &mut context);
+        assert!(matches!(state, Ok(State::ClosedTag)));
+        assert_eq!(drain(&mut buf_writer).unwrap(), latex);
+    }
+
+    #[test]
+    fn end_element_joins_runs_with_line_break() {
+        let Fixture {
+            mut buf_writer,
+            rels,
+            mut stack,
+            mut context,
+        } = Fixture::default();
+
+        stack.push(Tag::WParagraph);
+
+        stack.extend([Tag::WRun, Tag::WText, Tag::Content("First".to_string())]);
+        end_element(&mut buf_writer, &stack, &rels, &mut context).unwrap();
+        stack.truncate(1);
+
+        stack.extend([Tag::WRun, Tag::WBreak]);
+        end_element(&mut buf_writer, &stack, &rels, &mut context).unwrap();
+        assert!(context.line_break);
+        stack.truncate(1);
+
+        stack.extend([Tag::WRun, Tag::WText, Tag::Content("Second".to_string())]);
+        end_element(&mut buf_writer, &stack, &rels, &mut context).unwrap();
+        assert!(!context.line_break);
+        stack.truncate(1);
+
+        end_element(&mut buf_writer, &stack, &rels, &mut context).unwrap();
+        assert_eq!(drain(&mut buf_writer).unwrap(), "First\\\\\nSecond\n\n");
+    }
+
+    #[test]
+    fn end_element_suppresses_trailing_line_break() {
+        let Fixture {
+            mut buf_writer,
+            rels,
+            mut stack,
+            mut context,
+        } = Fixture::default();
+
+        stack.push(Tag::WParagraph);
+
+        stack.extend([Tag::WRun, Tag::WText, Tag::Content("Last".to_string())]);
+        end_element(&mut buf_writer, &stack, &rels, &mut context).unwrap();
+        stack.truncate(1);
+
+        stack.extend([Tag::WRun, Tag::WBreak]);
+        end_element(&mut buf_writer, &stack, &rels, &mut context).unwrap();
+        stack.truncate(1);
+
+        end_element(&mut buf_writer, &stack, &rels, &mut context).unwrap();
+        assert!(!context.line_break);
+        assert_eq!(drain(&mut buf_writer).unwrap(), "Last\n\n");
+    }
+
+    #[test]
+    fn document_resolves_internal_links_in_both_directions() {
+        let body = r#"
+<w:p><w:hyperlink w:anchor="_Ref 1"><w:r><w:t>Forward</w:t></w:r></w:hyperlink></w:p>
+<w:p><w:bookmarkStart w:id="0" w:name="_Ref 1"/><w:r><w:t>Target</w:t></w:r><w:bookmarkEnd w:id="0"/></w:p>
+<w:p><w:hyperlink w:anchor="_Ref 1"><w:r><w:t>Backward</w:t></w:r></w:hyperlink></w:p>
+"#;
+        let actual = convert(body, &HashMap::new());
+        assert_eq!(
+            actual,
+            "\\hyperlink{_Ref-1}{Forward}\n\n\\label{_Ref-1}\\hypertarget{_Ref-1}{Target}\n\n\\hyperlink{_Ref-1}{Backward}\n\n"
+        );
+    }
+
+    #[test]
+    fn document_escapes_hyperlink_text_but_not_url() {
+        let body = r#"<w:p><w:hyperlink r:id="rId3"><w:r><w:t>snake_case &amp; more</w:t></w:r></w:hyperlink><w:hyperlink w:anchor="_Toc_1"><w:r><w:t>50% off</w:t></w:r></w:hyperlink></w:p>"#;
+        let rels = HashMap::from([(
+            "rId3".to_string(),
+            Target::External("https://example.com/snake_case".to_string()),
+        )]);
+        let actual = convert(body, &rels);
+        assert_eq!(
+            actual,
+            "\\href{https://example.com/snake_case}{snake\\_case \\& more}\\hyperlink{_Toc_1}{50\\% off}\n\n"
+        );
+    }
+
+    #[rstest]
+    #[case(
+        true,
+        "\\pdftooltip{\\href{https://example.com}{Site}}{Opens \\textasciitilde{}home}\n\n"
+    )]
+    #[case(false, "\\href{https://example.com}{Site}\n\n")]
+    fn document_shows_hyperlink_tooltips(#[case] tooltips: bool, #[case] latex: &str) {
+        let body = r#"<w:p><w:hyperlink r:id="rId3" w:tooltip="Opens ~home" w:history="1"><w:r><w:t>Site</w:t></w:r></w:hyperlink></w:p>"#;
+        let rels = HashMap::from([(
+            "rId3".to_string(),
+            Target::External("https://example.com".to_string()),
+        )]);
+        let options = Options {
+            tooltips,
+            ..Default::default()
+        };
+        let (actual, packages) = convert_with_options(body, &rels, &options);
+        assert_eq!(actual, latex);
+        assert_eq!(packages.contains("pdfcomment"), tooltips);
+    }
+
+    #[test]
+    fn document_converts_simple_citation_field() {
+        let body = r#"
+<w:p>
+<w:r><w:t xml:space="preserve">As shown </w:t></w:r>
+<w:fldSimple w:instr=" CITATION Smi20 \l 1033 "><w:r><w:t>(Smith, 2020)</w:t></w:r></w:fldSimple>
+</w:p>
+"#;
+        let actual = convert(body, &HashMap::new());
+        assert_eq!(
+            actual,
+            "As shown \\cite{Smi20}\n\n\\begin{thebibliography}{1}\n\\bibitem{Smi20} Smi20\n\\end{thebibliography}\n"
+        );
+    }
+
+    #[test]
+    fn document_converts_complex_citation_field() {
+        let body = r#"
+<w:p>
+<w:r><w:fldChar w:fldCharType="begin"/></w:r>
+<w:r><w:instrText xml:space="preserve"> CITATION Smi20 \l 1033 </w:instrText></w:r>
+<w:r><w:fldChar w:fldCharType="separate"/></w:r>
+<w:r><w:t>(Smith, 2020)</w:t></w:r>
+<w:r><w:fldChar w:fldCharType="end"/></w:r>
+<w:r><w:t xml:space="preserve"> and </w:t></w:r>
+<w:fldSimple w:instr=" CITATION Smi20 \l 1033 "><w:r><w:t>(Smith, 2020)</w:t></w:r></w:fldSimple>
+</w:p>
+"#;
+        let actual = convert(body, &HashMap::new());
+        assert_eq!(
+            actual,
+            "\\cite{Smi20} and \\cite{Smi20}\n\n\\begin{thebibliography}{1}\n\\bibitem{Smi20} Smi20\n\\end{thebibliography}\n"
+        );
+    }
+
+    #[test]
+    fn document_converts_reference_fields() {
+        let body = r#"
+<w:p><w:bookmarkStart w:id="0" w:name="_Ref12345"/><w:r><w:t>Table 1</w:t></w:r><w:bookmarkEnd w:id="0"/></w:p>
+<w:p>
+<w:r><w:t xml:space="preserve">See </w:t></w:r>
+<w:fldSimple w:instr=" REF _Ref12345 \h "><w:r><w:t>Table 1</w:t></w:r></w:fldSimple>
+<w:r><w:t xml:space="preserve"> and </w:t></w:r>
+<w:r><w:fldChar w:fldCharType="begin"/></w:r>
+<w:r><w:instrText xml:space="preserve"> REF _Ref12345 \r \h </w:instrText></w:r>
+<w:r><w:fldChar w:fldCharType="separate"/></w:r>
+<w:r><w:t>1</w:t></w:r>
+<w:r><w:fldChar w:fldCharType="end"/></w:r>
+</w:p>
+"#;
+        let actual = convert(body, &HashMap::new());
+        assert_eq!(
+            actual,
+            "\\label{_Ref12345}\\hypertarget{_Ref12345}{Table 1}\n\nSee \\ref{_Ref12345} and \\ref{_Ref12345}\n\n"
+        );
+    }
+
+    #[test]
+    fn document_replaces_table_of_contents() {
+        let body = r#"
+<w:p><w:pPr><w:pStyle w:val="TOC1"/></w:pPr>
+<w:r><w:fldChar w:fldCharType="begin"/></w:r><w:r><w:instrText xml:space="preserve"> TOC \o "1-3" \h \z \u </w:instrText></w:r><w:r><w:fldChar w:fldCharType="separate"/></w:r>
+<w:hyperlink w:anchor="_Toc1"><w:r><w:t>Introduction</w:t></w:r><w:r><w:tab/></w:r><w:r><w:fldChar w:fldCharType="begin"/></w:r><w:r><w:instrText xml:space="preserve"> PAGEREF _Toc1 \h </w:instrText></w:r><w:r><w:fldChar w:fldCharType="separate"/></w:r><w:r><w:t>1</w:t></w:r><w:r><w:fldChar w:fldCharType="end"/></w:r></w:hyperlink>
+</w:p>
+<w:p><w:pPr><w:pStyle w:val="TOC2"/></w:pPr><w:hyperlink w:anchor="_Toc2"><w:r><w:t>Scope</w:t></w:r><w:r><w:tab/></w:r><w:r><w:t>2</w:t></w:r></w:hyperlink></w:p>
+<w:p><w:r><w:fldChar w:fldCharType="end"/></w:r></w:p>
+<w:p><w:r><w:t>After</w:t></w:r></w:p>
+<w:p><w:fldSimple w:instr=" TOC \o &quot;1-3&quot; "><w:r><w:t>No table of contents entries found.</w:t></w:r></w:fldSimple></w:p>
+"#;
+        let actual = convert(body, &HashMap::new());
+        assert_eq!(
+            actual,
+            "\\tableofcontents\n\nAfter\n\n\\tableofcontents\n\n"
+        );
+    }
+
+    #[test]
+    fn document_converts_matrix() {
+        let body = r#"
+<w:p><m:oMathPara><m:oMath><m:m>
+<m:mr><m:e><m:r><m:t>a</m:t></m:r></m:e><m:e><m:r><m:t>b</m:t></m:r></m:e></m:mr>
+<m:mr><m:e><m:r><m:t>c</m:t></m:r></m:e><m:e><m:r><m:t>d</m:t></m:r></m:e></m:mr>
+</m:m></m:oMath></m:oMathPara></w:p>
+"#;
+        let actual = convert(body, &HashMap::new());
+        assert_eq!(
+            actual,
+            "$$\\begin{matrix}a & b \\\\ c & d\\end{matrix}$$\n\n\n"
+        );
+    }
+
+    #[rstest]
+    #[case("\u{0302}", "\\hat{x}")]
+    #[case("\u{0304}", "\\bar{x}")]
+    #[case("\u{20D7}", "\\vec{x}")]
+    fn document_converts_accent(#[case] chr: &str, #[case] latex: &str) {
+        let body = format!(
+            r#"<w:p><m:oMathPara><m:oMath><m:acc><m:accPr><m:chr m:val="{chr}"/><m:ctrlPr/></m:accPr><m:e><m:r><m:t>x</m:t></m:r></m:e></m:acc></m:oMath></m:oMathPara></w:p>"#
+        );
+        let actual = convert(&body, &HashMap::new());
+        assert_eq!(actual, format!("$${latex}$$\n\n\n"));
+    }
+
+    #[test]
+    fn document_converts_accent_without_chr_to_hat() {
+        let body = r#"<w:p><m:oMathPara><m:oMath><m:acc><m:e><m:r><m:t>x</m:t></m:r></m:e></m:acc></m:oMath></m:oMathPara></w:p>"#;
+        let actual = convert(body, &HashMap::new());
+        assert_eq!(actual, "$$\\hat{x}$$\n\n\n");
+    }
+
+    #[rstest]
+    #[case(r#"<m:barPr><m:pos m:val="top"/></m:barPr>"#, "\\overline{x}")]
+    #[case(r#"<m:barPr><m:pos m:val="bot"/></m:barPr>"#, "\\underline{x}")]
+    #[case("", "\\overline{x}")]
+    fn document_converts_bar(#[case] properties: &str, #[case] latex: &str) {
+        let body = format!(
+            r#"<w:p><m:oMathPara><m:oMath><m:bar>{properties}<m:e><m:r><m:t>x</m:t></m:r></m:e></m:bar></m:oMath></m:oMathPara></w:p>"#
+        );
+        let actual = convert(&body, &HashMap::new());
+        assert_eq!(actual, format!("$${latex}$$\n\n\n"));
+    }
+
+    #[rstest]
+    #[case(
+        "sSub",
+        r#"<m:sub><m:r><m:t>n</m:t></m:r></m:sub>"#,
+        "\\overline{z}_{n}"
+    )]
+    #[case(
+        "sSup",
+        r#"<m:sup><m:r><m:t>2</m:t></m:r></m:sup>"#,
+        "\\overline{z}^{2}"
+    )]
+    #[case(
+        "sSubSup",
+        r#"<m:sub><m:r><m:t>n</m:t></m:r></m:sub><m:sup><m:r><m:t>2</m:t></m:r></m:sup>"#,
+        "\\overline{z}_{n}^{2}"
+    )]
+    fn document_converts_bar_with_script(
+        #[case] script: &str,
+        #[case] limits: &str,
+        #[case] latex: &str,
+    ) {
+        let body = format!(
+            r#"<w:p><m:oMathPara><m:oMath><m:{script}><m:e><m:bar><m:barPr><m:pos m:val="top"/></m:barPr><m:e><m:r><m:t>z</m:t></m:r></m:e></m:bar></m:e>{limits}</m:{script}></m:oMath></m:oMathPara></w:p>"#
+        );
+        let actual = convert(&body, &HashMap::new());
+        assert_eq!(actual, format!("$${latex}$$\n\n\n"));
+    }
+
+    #[test]
+    fn document_converts_summation_with_limits() {
+        let body = r#"<w:p><m:oMathPara><m:oMath><m:nary><m:naryPr><m:chr m:val="∑"/><m:ctrlPr/></m:naryPr><m:sub><m:r><m:t>i=0</m:t></m:r></m:sub><m:sup><m:r><m:t>n</m:t></m:r></m:sup><m:e><m:r><m:t>i</m:t></m:r></m:e></m:nary></m:oMath></m:oMathPara></w:p>"#;
+        let actual = convert(body, &HashMap::new());
+        assert_eq!(actual, "$$\\sum_{i=0}^{n}{i}$$\n\n\n");
+    }
+
+    #[test]
+    fn document_converts_nary_without_chr_to_integral() {
+        let body = r#"<w:p><m:oMathPara><m:oMath><m:nary><m:naryPr><m:ctrlPr/></m:naryPr><m:sub><m:r><m:t>0</m:t></m:r></m:sub><m:sup><m:r><m:t>1</m:t></m:r></m:sup><m:e><m:r><m:t>x</m:t></m:r></m:e></m:nary></m:oMath></m:oMathPara></w:p>"#;
+        let actual = convert(body, &HashMap::new());
+        assert_eq!(actual, "$$\\int_{0}^{1}{x}$$\n\n\n");
+    }
+
+    #[rstest]
+    #[case("sin", "\\sin{x}")]
+    #[case("sgn", "\\operatorname{sgn}{x}")]
+    fn document_converts_function(#[case] name: &str, #[case] latex: &str) {
+        let body = format!(
+            r#"<w:p><m:oMathPara><m:oMath><m:func><m:funcPr><m:ctrlPr/></m:funcPr><m:fName><m:r><m:t>{name}</m:t></m:r></m:fName><m:e><m:r><m:t>x</m:t></m:r></m:e></m:func></m:oMath></m:oMathPara></w:p>"#
+        );
+        let actual = convert(&body, &HashMap::new());
+        assert_eq!(actual, format!("$${latex}$$\n\n\n"));
+    }
+
+    #[test]
+    fn document_converts_function_with_lower_limit() {
+        let body = r#"<w:p><m:oMathPara><m:oMath><m:func><m:funcPr><m:ctrlPr/></m:funcPr><m:fName><m:limLow><m:limLowPr><m:ctrlPr/></m:limLowPr><m:e><m:r><m:t>lim</m:t></m:r></m:e><m:lim><m:r><m:t>x→0</m:t></m:r></m:lim></m:limLow></m:fName><m:e><m:r><m:t>x</m:t></m:r></m:e></m:func></m:oMath></m:oMathPara></w:p>"#;
+        let actual = convert(body, &HashMap::new());
+        assert_eq!(actual, "$$\\lim_{x\\rightarrow 0}{x}$$\n\n\n");
+    }
+
+    #[rstest]
+    #[case("limLow", "x", "n", "\\underset{n}{x}")]
+    #[case("limUpp", "x", "n", "\\overset{n}{x}")]
+    #[case("limLow", "lim", "x→0", "\\lim_{x\\rightarrow 0}")]
+    #[case("limUpp", "max", "n", "\\max^{n}")]
+    fn document_converts_limit(
+        #[case] limit: &str,
+        #[case] base: &str,
+        #[case] lim: &str,
+        #[case] latex: &str,
+    ) {
+        let body = format!(
+            r#"<w:p><m:oMathPara><m:oMath><m:{limit}><m:e><m:r><m:t>{base}</m:t></m:r></m:e><m:lim><m:r><m:t>{lim}</m:t></m:r></m:lim></m:{limit}></m:oMath></m:oMathPara></w:p>"#
+        );
+        let actual = convert(&body, &HashMap::new());
+        assert_eq!(actual, format!("$${latex}$$\n\n\n"));
+    }
+
+    #[rstest]
+    #[case("", "(x)")]
+    #[case(
+        r#"<m:dPr><m:begChr m:val="["/><m:endChr m:val="]"/></m:dPr>"#,
+        "\\left[x\\right]"
+    )]
+    #[case(
+        r#"<m:dPr><m:begChr m:val="{"/><m:endChr m:val=""/></m:dPr>"#,
+        "\\left\\{x\\right."
+    )]
+    #[case(
+        r#"<m:dPr><m:begChr m:val="‖"/><m:endChr m:val="‖"/></m:dPr>"#,
+        "\\left\\|x\\right\\|"
+    )]
+    fn document_converts_delimiter_characters(#[case] properties: &str, #[case] latex: &str) {
+        let body = format!(
+            r#"<w:p><m:oMathPara><m:oMath><m:d>{properties}<m:e><m:r><m:t>x</m:t></m:r></m:e></m:d></m:oMath></m:oMathPara></w:p>"#
+        );
+        let actual = convert(&body, &HashMap::new());
+        assert_eq!(actual, format!("$${latex}$$\n\n\n"));
+    }
+
+    #[rstest]
+    #[case("", "(a|b)")]
+    #[case(r#"<m:dPr><m:sepChr m:val=","/></m:dPr>"#, "(a,b)")]
+    fn document_separates_delimiter_elements(#[case] properties: &str, #[case] latex: &str) {
+        let body = format!(
+            r#"<w:p><m:oMathPara><m:oMath><m:d>{properties}<m:e><m:r><m:t>a</m:t></m:r></m:e><m:e><m:r><m:t>b</m:t></m:r></m:e></m:d></m:oMath></m:oMathPara></w:p>"#
+        );
+        let actual = convert(&body, &HashMap::new());
+        assert_eq!(actual, format!("$${latex}$$\n\n\n"));
+    }
+
+    #[rstest]
+    #[case("|", "\\left\\langle a\\middle|b\\right\\rangle ")]
+    #[case(",", "\\left\\langle a,b\\right\\rangle ")]
+    fn document_scales_delimiter_separators(#[case] separator: &str, #[case] latex: &str) {
+        let body = format!(
+            r#"<w:p><m:oMathPara><m:oMath><m:d><m:dPr><m:begChr m:val="⟨"/><m:sepChr m:val="{separator}"/><m:endChr m:val="⟩"/></m:dPr><m:e><m:r><m:t>a</m:t></m:r></m:e><m:e><m:r><m:t>b</m:t></m:r></m:e></m:d></m:oMath></m:oMathPara></w:p>"#
+        );
+        let actual = convert(&body, &HashMap::new());
+        assert_eq!(actual, format!("$${latex}$$\n\n\n"));
+    }
+
+    #[rstest]
+    #[case(
+        r#"<m:dPr><m:begChr m:val="["/><m:endChr m:val="]"/></m:dPr>"#,
+        r#"<m:dPr><m:begChr m:val="("/><m:endChr m:val=")"/></m:dPr>"#,
+        "\\left[\\left(x\\right)\\right]"
+    )]
+    #[case(
+        r#"<m:dPr><m:begChr m:val="{"/><m:endChr m:val=""/></m:dPr>"#,
+        r#"<m:dPr><m:begChr m:val="["/><m:endChr m:val="]"/></m:dPr>"#,
+        "\\left\\{\\left[x\\right]\\right."
+    )]
+    #[case(
+        r#"<m:dPr><m:begChr m:val="["/><m:endChr m:val="]"/></m:dPr>"#,
+        "",
+        "\\left[(x)\\right]"
+    )]
+    fn document_nests_delimiters_of_different_kinds(
+        #[case] outer: &str,
+        #[case] inner: &str,
+        #[case] latex: &str,
+    ) {
+        let body = format!(
+            r#"<w:p><m:oMathPara><m:oMath><m:d>{outer}<m:e><m:d>{inner}<m:e><m:r><m:t>x</m:t></m:r></m:e></m:d></m:e></m:d></m:oMath></m:oMathPara></w:p>"#
+        );
+        let actual = convert(&body, &HashMap::new());
+        assert_eq!(actual, format!("$${latex}$$\n\n\n"));
+    }
+
+    #[test]
+    fn document_does_not_double_separate_delimiter_elements() {
+        let body = r#"<w:p><m:oMathPara><m:oMath><m:d><m:dPr><m:sepChr m:val=","/></m:dPr><m:e><m:r><m:t>a</m:t></m:r></m:e><m:e><m:r><m:t>|</m:t></m:r></m:e><m:e><m:r><m:t>b</m:t></m:r></m:e><m:e><m:f><m:num><m:r><m:t>1</m:t></m:r></m:num><m:den><m:r><m:t>2</m:t></m:r></m:den></m:f></m:e></m:d></m:oMath></m:oMathPara></w:p>"#;
+        let actual = convert(body, &HashMap::new());
+        assert_eq!(actual, "$$(a|b,\\frac{1}{2})$$\n\n\n");
+    }
+
+    #[rstest]
+    #[case("<m:deg/>", "\\sqrt{x}")]
+    #[case("<m:deg></m:deg>", "\\sqrt{x}")]
+    #[case("<m:deg><m:r><m:t>3</m:t></m:r></m:deg>", "\\sqrt[3]{x}")]
+    #[case("<m:deg><m:sSup><m:e><m:r><m:t>n</m:t></m:r></m:e><m:sup><m:r><m:t>2</m:t></m:r></m:sup></m:sSup></m:deg>", "\\sqrt[n^{2}]{x}")]
+    fn document_converts_radical_degree(#[case] degree: &str, #[case] latex: &str) {
+        let body = format!(
+            r#"<w:p><m:oMathPara><m:oMath><m:rad><m:radPr><m:degHide m:val="1"/></m:radPr>{degree}<m:e><m:r><m:t>x</m:t></m:r></m:e></m:rad></m:oMath></m:oMathPara></w:p>"#
+        );
+        let actual = convert(&body, &HashMap::new());
+        assert_eq!(actual, format!("$${latex}$$\n\n\n"));
+    }
+
+    #[rstest]
+    #[case(
+        "<m:f><m:num><m:r><m:t>1</m:t></m:r></m:num><m:den><m:r><m:t>2</m:t></m:r></m:den></m:f>",
+        "x^{\\frac{1}{2}}"
+    )]
+    #[case(
+        "<m:rad><m:radPr><m:degHide m:val=\"1\"/></m:radPr><m:deg/><m:e><m:r><m:t>y</m:t></m:r></m:e></m:rad>",
+        "x^{\\sqrt{y}}"
+    )]
+    #[case(
+        "<m:r><m:t>n</m:t></m:r><m:f><m:num><m:r><m:t>1</m:t></m:r></m:num><m:den><m:r><m:t>2</m:t></m:r></m:den></m:f>",
+        "x^{n\\frac{1}{2}}"
+    )]
+    fn document_braces_whole_script(#[case] script: &str, #[case] latex: &str) {
+        let body = format!(
+            r#"<w:p><m:oMathPara><m:oMath><m:sSup><m:e><m:r><m:t>x</m:t></m:r></m:e><m:sup>{script}</m:sup></m:sSup></m:oMath></m:oMathPara></w:p>"#
+        );
+        let actual = convert(&body, &HashMap::new());
+        assert_eq!(actual, format!("$${latex}$$\n\n\n"));
+    }
+
+    #[test]
+    fn document_converts_sub_superscript() {
+        let body = r#"<w:p><m:oMathPara><m:oMath><m:sSubSup><m:sSubSupPr><m:ctrlPr/></m:sSubSupPr><m:e><m:r><m:t>x</m:t></m:r></m:e><m:sub><m:r><m:t>i</m:t></m:r></m:sub><m:sup><m:r><m:t>2</m:t></m:r></m:sup></m:sSubSup></m:oMath></m:oMathPara></w:p>"#;
+        let actual = convert(body, &HashMap::new());
+        assert_eq!(actual, "$$x_{i}^{2}$$\n\n\n");
+    }
+
+    #[test]
+    fn document_converts_pre_script() {
+        let body = r#"<w:p><m:oMathPara><m:oMath><m:sPre><m:sPrePr><m:ctrlPr/></m:sPrePr><m:sub><m:r><m:t>6</m:t></m:r></m:sub><m:sup><m:r><m:t>14</m:t></m:r></m:sup><m:e><m:r><m:t>C</m:t></m:r></m:e></m:sPre></m:oMath></m:oMathPara></w:p>"#;
+        let actual = convert(body, &HashMap::new());
+        assert_eq!(actual, "$${}_{6}^{14}C$$\n\n\n");
+    }
+
+    #[rstest]
+    #[case("", "\\frac{a}{b}")]
+    #[case(r#"<m:fPr><m:type m:val="bar"/></m:fPr>"#, "\\frac{a}{b}")]
+    #[case(r#"<m:fPr><m:type m:val="lin"/></m:fPr>"#, "a/b")]
+    #[case(
+        r#"<m:fPr><m:type m:val="noBar"/></m:fPr>"#,
+        "\\genfrac{}{}{0pt}{}{a}{b}"
+    )]
+    #[case(r#"<m:fPr><m:type m:val="skw"/></m:fPr>"#, "\\sfrac{a}{b}")]
+    fn document_converts_fraction_type(#[case] properties: &str, #[case] latex: &str) {
+        let body = format!(
+            r#"<w:p><m:oMathPara><m:oMath><m:f>{properties}<m:num><m:r><m:t>a</m:t></m:r></m:num><m:den><m:r><m:t>b</m:t></m:r></m:den></m:f></m:oMath></m:oMathPara></w:p>"#
+        );
+        let actual = convert(&body, &HashMap::new());
+        assert_eq!(actual, format!("$${latex}$$\n\n\n"));
+    }
+
+    #[test]
+    fn document_attaches_power_to_function_name() {
+        let body = r#"<w:p><m:oMathPara><m:oMath><m:func><m:funcPr><m:ctrlPr/></m:funcPr><m:fName><m:sSup><m:sSupPr><m:ctrlPr/></m:sSupPr><m:e><m:r><m:t>sin</m:t></m:r></m:e><m:sup><m:r><m:t>2</m:t></m:r></m:sup></m:sSup></m:fName><m:e><m:d><m:e><m:r><m:t>x</m:t></m:r></m:e></m:d></m:e></m:func></m:oMath></m:oMathPara></w:p>"#;
+        let actual = convert(body, &HashMap::new());
+        assert_eq!(actual, "$$\\sin^{2}{(x)}$$\n\n\n");
+    }
+
+    #[test]
+    fn document_converts_word_run_in_equation_to_text() {
+        let body = r#"<w:p><m:oMathPara><m:oMath><m:r><m:t>x</m:t></m:r><w:r><w:t xml:space="preserve"> if x&lt;0</w:t></w:r></m:oMath></m:oMathPara></w:p>"#;
+        let actual = convert(body, &HashMap::new());
+        assert_eq!(actual, "$$x\\text{ if x\\textless 0}$$\n\n\n");
+    }
+
+    #[rstest]
+    #[case(
+        r#"<m:groupChrPr><m:chr m:val="⏟"/><m:pos m:val="bot"/></m:groupChrPr>"#,
+        "\\underbrace{x+y}"
+    )]
+    #[case(
+        r#"<m:groupChrPr><m:chr m:val="⏞"/><m:pos m:val="top"/></m:groupChrPr>"#,
+        "\\overbrace{x+y}"
+    )]
+    #[case("", "\\underbrace{x+y}")]
+    fn document_converts_group_character(#[case] properties: &str, #[case] latex: &str) {
+        let body = format!(
+            r#"<w:p><m:oMathPara><m:oMath><m:groupChr>{properties}<m:e><m:r><m:t>x+y</m:t></m:r></m:e></m:groupChr></m:oMath></m:oMathPara></w:p>"#
+        );
+        let actual = convert(&body, &HashMap::new());
+        assert_eq!(actual, format!("$${latex}$$\n\n\n"));
+    }
+
+    #[test]
+    fn document_aligns_run_to_baseline_over_style() {
+        let styles = HashMap::from([
+            (
+                "Raised".to_string(),
+                Style {
+                    character: CharacterStyle {
+                        vert_align: Some("superscript".to_string()),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+            ),
+            (
+                "Level".to_string(),
+                Style {
+                    based_on: Some("Raised".to_string()),
+                    character: CharacterStyle {
+                        vert_align: Some("baseline".to_string()),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+            ),
+        ]);
+        let body = r#"<w:p><w:r><w:rPr><w:rStyle w:val="Raised"/></w:rPr><w:t>up</w:t></w:r><w:r><w:rPr><w:rStyle w:val="Level"/></w:rPr><w:t xml:space="preserve"> level</w:t></w:r><w:r><w:rPr><w:rStyle w:val="Raised"/><w:vertAlign w:val="baseline"/></w:rPr><w:t xml:space="preserve"> down</w:t></w:r></w:p>"#;
+        let (actual, _) = convert_with_styles(body, &styles);
+        assert_eq!(actual, "\\textsuperscript{up} level down\n\n");
+    }
+
+    #[rstest]
+    #[case(
+        r#"<m:limLow><m:e><m:groupChr><m:groupChrPr><m:chr m:val="⏟"/><m:pos m:val="bot"/></m:groupChrPr><m:e><m:r><m:t>a+b</m:t></m:r></m:e></m:groupChr></m:e><m:lim><m:r><m:t>n</m:t></m:r></m:lim></m:limLow>"#,
+        "\\underbrace{a+b}_{n}"
+    )]
+    #[case(
+        r#"<m:limUpp><m:e><m:groupChr><m:groupChrPr><m:chr m:val="⏞"/><m:pos m:val="top"/></m:groupChrPr><m:e><m:r><m:t>a+b</m:t></m:r></m:e></m:groupChr></m:e><m:lim><m:r><m:t>n</m:t></m:r></m:lim></m:limUpp>"#,
+        "\\overbrace{a+b}^{n}"
+    )]
+    #[case(
+        r#"<m:sSub><m:e><m:groupChr><m:groupChrPr><m:chr m:val="⏟"/><m:pos m:val="bot"/></m:groupChrPr><m:e><m:r><m:t>a+b</m:t></m:r></m:e></m:groupChr></m:e><m:sub><m:r><m:t>n</m:t></m:r></m:sub></m:sSub>"#,
+        "\\underbrace{a+b}_{n}"
+    )]
+    #[case(
+        r#"<m:limLow><m:e><m:r><m:t>c</m:t></m:r><m:groupChr><m:e><m:r><m:t>a+b</m:t></m:r></m:e></m:groupChr></m:e><m:lim><m:r><m:t>n</m:t></m:r></m:lim></m:limLow>"#,
+        "\\underset{n}{c\\underbrace{a+b}}"
+    )]
+    fn document_labels_group_character(#[case] math: &str, #[case] latex: &str) {
+        let body = format!("<w:p><m:oMathPara><m:oMath>{math}</m:oMath></m:oMathPara></w:p>");
+        assert_eq!(
+            convert(&body, &HashMap::new()),
+            format!("$${latex}$$\n\n\n")
+        );
+    }
+
+    #[rstest]
+    #[case(MathDelimiters::Dollars, "$$a$$\n\n\nb $c$\n\n")]
+    #[case(MathDelimiters::DisplayMath, "\\[a\\]\n\n\nb \\(c\\)\n\n")]
+    fn document_writes_math_delimiters(
+        #[case] math_delimiters: MathDelimiters,
+        #[case] latex: &str,
+    ) {
+        let body = r#"<w:p><m:oMathPara><m:oMath><m:r><m:t>a</m:t></m:r></m:oMath></m:oMathPara></w:p><w:p><w:r><w:t xml:space="preserve">b </w:t></w:r><m:oMath><m:r><m:t>c</m:t></m:r></m:oMath></w:p>"#;
+        let options = Options {
+            math_delimiters,
+            ..Options::default()
+        };
+        let (actual, _) = convert_with_options(body, &HashMap::new(), &options);
+        assert_eq!(actual, latex);
+    }
+
+    #[rstest]
+    #[case(false, "$$\\pi \\approx 3,14$$\n\n\nPi is 3,14\n\n")]
+    #[case(true, "$$\\pi \\approx 3{,}14$$\n\n\nPi is 3,14\n\n")]
+    fn document_braces_decimal_comma_in_math(#[case] decimal_comma: bool, #[case] latex: &str) {
+        let body = r#"<w:p><m:oMathPara><m:oMath><m:r><m:t>π≈3,14</m:t></m:r></m:oMath></m:oMathPara></w:p><w:p><w:r><w:t>Pi is 3,14</w:t></w:r></w:p>"#;
+        let options = Options {
+            decimal_comma,
+            ..Options::default()
+        };
+        let (actual, _) = convert_with_options(body, &HashMap::new(), &options);
+        assert_eq!(actual, latex);
+    }
+
+    #[test]
+    fn options_default_to_dollars() {
+        assert_eq!(Options::default().math_delimiters, MathDelimiters::Dollars);
+    }
+
+    #[test]
+    fn document_keeps_nested_omath_in_one_region() {
+        let body = r#"<w:p><m:oMathPara><m:oMath><m:r><m:t>a</m:t></m:r><m:d><m:e><m:oMath><m:r><m:t>b</m:t></m:r></m:oMath></m:e></m:d><m:r><m:t>c</m:t></m:r></m:oMath></m:oMathPara></w:p>"#;
+        let actual = convert(body, &HashMap::new());
+        assert_eq!(actual, "$$a(b)c$$\n\n\n");
+    }
+
+    #[test]
+    fn document_prefers_svg_blip() {
+        let body = r#"
+<w:p><w:r><w:drawing><wp:inline><a:graphic><a:graphicData><pic:pic><pic:blipFill>
+<a:blip r:embed="rId5"><a:extLst><a:ext uri="{96DAC541-7B7A-43D3-8B79-37D633B846F1}">
+<asvg:svgBlip xmlns:asvg="http://schemas.microsoft.com/office/drawing/2016/SVG/main" r:embed="rId6"/>
+</a:ext></a:extLst></a:blip>
+</pic:blipFill></pic:pic></a:graphicData></a:graphic></wp:inline></w:drawing></w:r></w:p>
+"#;
+        let rels = HashMap::from([
+            (
+                "rId5".to_string(),
+                Target::Internal("media/image1.png".to_string()),
+            ),
+            (
+                "rId6".to_string(),
+                Target::Internal("media/image2.svg".to_string()),
+            ),
+        ]);
+        let (actual, packages) = convert_with_packages(body, &rels);
+        assert_eq!(
+            actual,
+            "\\includesvg[width=\\textwidth]{media/image2.svg}\n\n"
+        );
+        assert!(packages.contains("svg"));
+    }
+
+    #[test]
+    fn document_sizes_image_by_extent() {
+        let body = r#"
+<w:p><w:r><w:drawing><wp:inline><wp:extent cx="914400" cy="685800"/><a:graphic><a:graphicData><pic:pic><pic:blipFill>
+<a:blip r:embed="rId5"/>
+</pic:blipFill></pic:pic></a:graphicData></a:graphic></wp:inline></w:drawing></w:r></w:p>
+"#;
+        let rels = HashMap::from([(
+            "rId5".to_string(),
+            Target::Internal("media/image1.png".to_string()),
+        )]);
+        let actual = convert(body, &rels);
+        assert_eq!(actual, "\\includegraphics[width=1in]{media/image1.png}\n\n");
+    }
+
+    #[rstest]
+    // a4 paper with margins of an inch leaves 9026 twips, or 5731510 EMU, of text
+    #[case("2865755", "0.5\\textwidth")]
+    #[case("7315200", "\\textwidth")]
+    fn document_sizes_image_by_share_of_text_width(#[case] cx: &str, #[case] width: &str) {
+        let body = format!(
+            r#"
+<w:p><w:r><w:drawing><wp:inline><wp:extent cx="{cx}" cy="685800"/><a:graphic><a:graphicData><pic:pic><pic:blipFill>
+<a:blip r:embed="rId5"/>
+</pic:blipFill></pic:pic></a:graphicData></a:graphic></wp:inline></w:drawing></w:r></w:p>
+<w:sectPr><w:pgSz w:w="11906" w:h="16838"/><w:pgMar w:top="1440" w:right="1440" w:bottom="1440" w:left="1440"/></w:sectPr>
+"#
+        );
+        let rels = HashMap::from([(
+            "rId5".to_string(),
+            Target::Internal("media/image1.png".to_string()),
+        )]);
+        let actual = convert(&body, &rels);
+        assert_eq!(
+            actual,
+            format!("\\includegraphics[width={width}]{{media/image1.png}}\n\n")
+        );
+    }
+
+    #[rstest]
+    #[case::cell_widths(
+        r#"<w:tbl><w:tr><w:tc><w:tcPr><w:tcW w:w="2500" w:type="pct"/></w:tcPr><w:p><w:r><w:t>Chart</w:t></w:r></w:p></w:tc><w:tc><w:tcPr><w:tcW w:w="2500" w:type="pct"/></w:tcPr><w:p>{DRAWING}</w:p></w:tc></w:tr></w:tbl>"#
+    )]
+    #[case::caption_above(
+        r#"<w:p><w:pPr><w:pStyle w:val="Caption"/></w:pPr><w:r><w:t>Sales </w:t></w:r>{DRAWING}</w:p><w:tbl><w:tblGrid><w:gridCol w:w="2700"/></w:tblGrid><w:tr><w:tc><w:p>{DRAWING}</w:p></w:tc></w:tr></w:tbl>"#
+    )]
+    #[case::caption_below(
+        r#"<w:tbl><w:tr><w:tc><w:tcPr><w:tcW w:w="5000" w:type="pct"/></w:tcPr><w:p>{DRAWING}</w:p></w:tc></w:tr></w:tbl><w:p><w:pPr><w:pStyle w:val="Caption"/></w:pPr><w:r><w:t>Sales </w:t></w:r>{DRAWING}</w:p>"#
+    )]
+    fn document_sizes_moved_image_by_share_of_text_width(#[case] body: &str) {
+        let drawing = r#"<w:r><w:drawing><wp:inline><wp:extent cx="2865755" cy="685800"/><a:graphic><a:graphicData><pic:pic><pic:blipFill><a:blip r:embed="rId5"/></pic:blipFill></pic:pic></a:graphicData></a:graphic></wp:inline></w:drawing></w:r>"#;
+        let section = r#"<w:sectPr><w:pgSz w:w="11906" w:h="16838"/><w:pgMar w:top="1440" w:right="1440" w:bottom="1440" w:left="1440"/></w:sectPr>"#;
+        let body = body.replace("{DRAWING}", drawing) + section;
+        let rels = HashMap::from([(
+            "rId5".to_string(),
+            Target::Internal("media/image1.png".to_string()),
+        )]);
+        let actual = convert(&body, &rels);
+        let image = "\\includegraphics[width=0.5\\textwidth]{media/image1.png}";
+        assert_eq!(
+            actual.matches(image).count(),
+            body.matches("<w:drawing>").count()
+        );
+        assert!(!actual.contains("in]"), "{actual}");
+    }
+
+    #[rstest]
+    #[case(false, "Before\n\n")]
+    #[case(
+        true,
+        "Before\\includegraphics[width=\\textwidth]{media/image1.png}\n\n"
+    )]
+    fn document_skips_hidden_drawing(#[case] include_hidden: bool, #[case] expected: &str) {
+        let body = r#"
+<w:p><w:r><w:t>Before</w:t><w:drawing><wp:inline><wp:docPr id="1" name="Picture 1" hidden="1"/><a:graphic><a:graphicData><pic:pic><pic:blipFill>
+<a:blip r:embed="rId5"/>
+</pic:blipFill></pic:pic></a:graphicData></a:graphic></wp:inline></w:drawing></w:r></w:p>
+"#;
+        let rels = HashMap::from([(
+            "rId5".to_string(),
+            Target::Internal("media/image1.png".to_string()),
+        )]);
+        let options = Options {
+            include_hidden,
+            ..Options::default()
+        };
+        let (actual, _) = convert_with_options(body, &rels, &options);
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest]
+    #[case(true, "", "\\begin{center}\n\\includegraphics[width=\\textwidth]{media/image1.png}\n\\end{center}\n\n")]
+    #[case(
+        false,
+        "",
+        "\\includegraphics[width=\\textwidth]{media/image1.png}\n\n"
+    )]
+    #[case(
+        true,
+        "<w:r><w:t>Sales</w:t></w:r>",
+        "\\includegraphics[width=\\textwidth]{media/image1.png}Sales\n\n"
+    )]
+    #[case(
+        true,
+        r#"<w:r><w:fldChar w:fldCharType="begin"/></w:r><w:r><w:instrText xml:space="preserve"> REF _Ref1 \h </w:instrText></w:r><w:r><w:fldChar w:fldCharType="separate"/></w:r><w:r><w:t>Table 1</w:t></w:r><w:r><w:fldChar w:fldCharType="end"/></w:r>"#,
+        "\\includegraphics[width=\\textwidth]{media/image1.png}\\ref{_Ref1}\n\n"
+    )]
+    #[case(
+        true,
+        r#"<w:bookmarkStart w:id="0" w:name="Chart"/><w:bookmarkEnd w:id="0"/>"#,
+        "\\includegraphics[width=\\textwidth]{media/image1.png}\\label{Chart}\\hypertarget{Chart}{}\n\n"
+    )]
+    fn document_centers_image_only_paragraph(
+        #[case] figures: bool,
+        #[case] after: &str,
+        #[case] expected: &str,
+    ) {
+        let body = format!(
+            r#"
+<w:p><w:pPr><w:jc w:val="center"/></w:pPr><w:r><w:drawing><wp:inline><a:graphic><a:graphicData><pic:pic><pic:blipFill>
+<a:blip r:embed="rId5"/>
+</pic:blipFill></pic:pic></a:graphicData></a:graphic></wp:inline></w:drawing></w:r>{after}</w:p>
+"#
+        );
+        let rels = HashMap::from([(
+            "rId5".to_string(),
+            Target::Internal("media/image1.png".to_string()),
+        )]);
+        let options = Options {
+            figures,
+            ..Options::default()
+        };
+        let (actual, _) = convert_with_options(&body, &rels, &options);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn document_reports_dangling_relationships() {
+        let body = r#"
+<w:p><w:r><w:drawing><wp:inline><a:graphic><a:graphicData><pic:pic><pic:blipFill>
+<a:blip r:embed="rId9"/>
+</pic:blipFill></pic:pic></a:graphicData></a:graphic></wp:inline></w:drawing></w:r>
+<w:hyperlink r:id="rId7"><w:r><w:t>Site</w:t></w:r></w:hyperlink>
+<w:r><w:footnoteReference w:id="4"/></w:r>
+<m:oMath><m:box><m:e><m:r><m:t>x</m:t></m:r></m:e></m:box></m:oMath></w:p>
+"#;
+        let rels = HashMap::from([(
+            "rId5".to_string(),
+            Target::Internal("media/image1.png".to_string()),
+        )]);
+        let (_, _, report) = convert_reporting(
+            body,
+            &rels,
+            &HashMap::new(),
+            &HashMap::new(),
+            &HashMap::new(),
+            &Options::default(),
+        );
+        let diagnostics: Vec<_> = report
+            .diagnostics
+            .iter()
+            .map(|diagnostic| {
+                (
+                    diagnostic.severity,
+                    &diagnostic.kind,
+                    diagnostic.tag.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            diagnostics,
+            vec![
+                (
+                    Severity::Error,
+                    &DiagnosticKind::MissingRelationship("rId9".to_string()),
+                    "a:blip"
+                ),
+                (
+                    Severity::Error,
+                    &DiagnosticKind::MissingRelationship("rId7".to_string()),
+                    "w:hyperlink"
+                ),
+                (
+                    Severity::Error,
+                    &DiagnosticKind::MissingFootnote("4".to_string()),
+                    "w:footnoteReference"
+                ),
+                (Severity::Warning, &DiagnosticKind::UnsupportedMath, "m:box"),
+            ]
+        );
+        assert_eq!(report.count(Severity::Error), 3);
+        assert!(!report.is_clean());
+
+        let (_, _, report) = convert_reporting(
+            "<w:p><w:r><w:t>Clean</w:t></w:r></w:p>",
+            &rels,
+            &HashMap::new(),
+            &HashMap::new(),
+            &HashMap::new(),
+            &Options::default(),
+        );
+        assert!(report.is_clean());
+    }
+
+    #[rstest]
+    #[case::truncated("<w:p><w:r><w:t>Cut off", "w:t")]
+    #[case::reference(
+        r#"<w:p><w:r><w:fldChar w:fldCharType="begin"/></w:r><w:r><w:instrText> REF </w:instrText></w:r><w:r><w:fldChar w:fldCharType="end"/></w:r></w:p>"#,
+        "w:instrText"
+    )]
+    #[case::field_end(
+        r#"<w:p><w:r><w:fldChar w:fldCharType="end"/></w:r></w:p>"#,
+        "w:fldChar"
+    )]
+    fn document_reports_broken_markup(#[case] body: &str, #[case] tag: &str) {
+        let (_, _, report) = convert_reporting(
+            body,
+            &HashMap::new(),
+            &HashMap::new(),
+            &HashMap::new(),
+            &HashMap::new(),
+            &Options::default(),
+        );
+        assert_eq!(report.count(Severity::Error), 1);
+        assert_eq!(report.diagnostics[0].tag, tag);
+    }
+
+    #[test]
+    fn document_describes_missing_image() {
+        let body = r#"
+<w:p><w:r><w:drawing><wp:inline><wp:docPr id="1" name="Picture 1" descr="Sales ~2020"/><a:graphic><a:graphicData><pic:pic><pic:blipFill>
+<a:blip r:embed="rId9"/>
+</pic:blipFill></pic:pic></a:graphicData></a:graphic></wp:inline></w:drawing></w:r></w:p>
+"#;
+        let actual = convert(body, &HashMap::new());
+        assert_eq!(actual, "\\fbox{[Image: Sales \\textasciitilde{}2020]}\n\n");
+    }
+
+    #[test]
+    fn document_converts_tab_to_space() {
+        let body = r#"
+<w:p><w:r><w:t>One</w:t><w:tab/><w:t>Two</w:t></w:r></w:p>
+"#;
+        let actual = convert(body, &HashMap::new());
+        assert_eq!(actual, "One\\hspace{2em}Two\n\n");
+    }
+
+    #[test]
+    fn document_converts_manual_toc_line() {
+        let body = r#"
+<w:p><w:pPr><w:tabs><w:tab w:val="right" w:leader="dot" w:pos="9350"/></w:tabs></w:pPr>
+<w:r><w:t>Introduction</w:t></w:r><w:r><w:tab/></w:r>
+<w:r><w:fldChar w:fldCharType="begin"/></w:r><w:r><w:instrText xml:space="preserve"> PAGEREF _Toc1 \h </w:instrText></w:r><w:r><w:fldChar w:fldCharType="separate"/></w:r>
+<w:r><w:t>3</w:t></w:r>
+<w:r><w:fldChar w:fldCharType="end"/></w:r>
+</w:p>
+"#;
+        let actual = convert(body, &HashMap::new());
+        assert_eq!(actual, "Introduction \\dotfill \\pageref{_Toc1}\n\n");
+    }
+
+    #[test]
+    fn document_ignores_tab_stops() {
+        let body = r#"
+<w:p><w:pPr><w:tabs><w:tab w:val="left" w:pos="720"/></w:tabs></w:pPr><w:r><w:t>One</w:t></w:r></w:p>
+"#;
+        let actual = convert(body, &HashMap::new());
+        assert_eq!(actual, "One\n\n");
+    }
+
+    #[test]
+    fn document_converts_table() {
+        let body = r#"
+<w:tbl>
+<w:tblGrid><w:gridCol w:w="2000"/><w:gridCol w:w="2000"/></w:tblGrid>
+<w:tr><w:tc><w:p><w:r><w:t>A</w:t></w:r></w:p></w:tc><w:tc><w:p><w:r><w:t>B</w:t></w:r></w:p><w:p><w:r><w:t>C</w:t></w:r></w:p></w:tc></w:tr>
+<w:tr><w:tc><w:p><w:r><w:t>D</w:t></w:r></w:p></w:tc><w:tc><w:p/></w:tc></w:tr>
+</w:tbl>
+"#;
+        let (actual, packages) = convert_with_packages(body, &HashMap::new());
+        assert_eq!(
+            actual,
+            "\\begin{tabular}{|l|l|}\n\\hline\nA & B C \\\\\n\\hline\nD &  \\\\\n\\hline\n\\end{tabular}\n\n"
+        );
+        assert!(packages.is_empty());
+    }
+
+    #[test]
+    fn document_proportions_percentage_columns() {
+        let body = r#"
+<w:tbl>
+<w:tblPr><w:tblW w:w="5000" w:type="pct"/></w:tblPr>
+<w:tblGrid><w:gridCol w:w="4500"/><w:gridCol w:w="4500"/></w:tblGrid>
+<w:tr><w:tc><w:tcPr><w:tcW w:w="1500" w:type="pct"/></w:tcPr><w:p><w:r><w:t>A</w:t></w:r></w:p></w:tc><w:tc><w:tcPr><w:tcW w:w="3500" w:type="pct"/></w:tcPr><w:p><w:r><w:t>B</w:t></w:r></w:p></w:tc></w:tr>
+</w:tbl>
+"#;
+        let actual = convert(body, &HashMap::new());
+        assert_eq!(
+            actual,
+            "\\begin{tabular}{|p{0.30\\textwidth}|p{0.70\\textwidth}|}\n\\hline\nA & B \\\\\n\\hline\n\\end{tabular}\n\n"
+        );
+    }
+
+    #[rstest]
+    #[case(
+        r#"<w:tblLayout w:type="fixed"/>"#,
+        "\\begin{tabular}{|p{135.00pt}|p{315.00pt}|}\n\\hline\nA & B \\\\\n\\hline\n\\end{tabular}\n\n",
+        false
+    )]
+    #[case(
+        r#"<w:tblLayout w:type="autofit"/>"#,
+        "\\begin{tabularx}{0.50\\textwidth}{|X|X|}\n\\hline\nA & B \\\\\n\\hline\n\\end{tabularx}\n\n",
+        true
+    )]
+    #[case(
+        "<w:tblLayout/>",
+        "\\begin{tabularx}{0.50\\textwidth}{|X|X|}\n\\hline\nA & B \\\\\n\\hline\n\\end{tabularx}\n\n",
+        true
+    )]
+    fn document_sizes_columns_by_layout(
+        #[case] layout: &str,
+        #[case] latex: &str,
+        #[case] tabularx: bool,
+    ) {
+        let body = format!(
+            r#"
+<w:tbl>
+<w:tblPr><w:tblW w:w="2500" w:type="pct"/>{layout}</w:tblPr>
+<w:tblGrid><w:gridCol w:w="2700"/><w:gridCol w:w="6300"/></w:tblGrid>
+<w:tr><w:tc><w:p><w:r><w:t>A</w:t></w:r></w:p></w:tc><w:tc><w:p><w:r><w:t>B</w:t></w:r></w:p></w:tc></w:tr>
+</w:tbl>
+"#
+        );
+        let (actual, packages) = convert_with_packages(&body, &HashMap::new());
+        assert_eq!(actual, latex);
+        assert_eq!(packages.contains("tabularx"), tabularx);
+    }
+
+    #[test]
+    fn document_begins_table_without_grid_at_first_row() {
+        let body = r#"
+<w:tbl>
+<w:tblPr><w:tblStyle w:val="TableGrid"/></w:tblPr>
+<w:tr><w:tc><w:p><w:r><w:t>A</w:t></w:r></w:p></w:tc><w:tc><w:p><w:r><w:t>B</w:t></w:r></w:p></w:tc></w:tr>
+</w:tbl>
+"#;
+        let (actual, _) = convert_with_packages(body, &HashMap::new());
+        assert_eq!(
+            actual,
+            "\\begin{tabular}{|*{63}{l|}}\n\\hline\nA & B \\\\\n\\hline\n\\end{tabular}\n\n"
+        );
+    }
+
+    #[test]
+    fn document_proportions_columns_of_table_without_grid() {
+        let body = r#"
+<w:tbl>
+<w:tblPr><w:tblW w:w="2500" w:type="pct"/></w:tblPr>
+<w:tr><w:tc><w:tcPr><w:tcW w:w="2000" w:type="pct"/></w:tcPr><w:p><w:r><w:t>A</w:t></w:r></w:p></w:tc><w:tc><w:tcPr><w:tcW w:w="3000" w:type="pct"/></w:tcPr><w:p><w:r><w:t>B</w:t></w:r></w:p></w:tc></w:tr>
+<w:tr><w:tc><w:tcPr><w:tcW w:w="5000" w:type="pct"/></w:tcPr><w:p><w:r><w:t>C</w:t></w:r></w:p></w:tc></w:tr>
+</w:tbl>
+"#;
+        let (actual, _) = convert_with_packages(body, &HashMap::new());
+        assert_eq!(
+            actual,
+            "\\begin{tabular}{|p{0.20\\textwidth}|p{0.30\\textwidth}|}\n\\hline\nA & B \\\\\n\\hline\nC \\\\\n\\hline\n\\end{tabular}\n\n"
+        );
+    }
+
+    #[rstest]
+    #[case::without_grid(
+        r#"<w:tbl><w:tr><w:tc><w:p><w:r><w:t>A</w:t></w:r></w:p></w:tc></w:tr></w:tbl>"#,
+        "\\begin{adjustbox}{max width=\\textwidth}\n\\begin{tabular}{|*{63}{l|}}\n\\hline\nA \\\\\n\\hline\n\\end{tabular}\n\\end{adjustbox}\n\n"
+    )]
+    #[case::empty("<w:tbl><w:tblPr/></w:tbl>", "\n\n")]
+    fn document_fits_table_without_grid(#[case] body: &str, #[case] expected: &str) {
+        let options = Options {
+            fit_tables: true,
+            ..Options::default()
+        };
+        let (actual, _) = convert_with_options(body, &HashMap::new(), &options);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn document_fits_table_to_text_width() {
+        let body = r#"
+<w:tbl>
+<w:tblGrid><w:gridCol w:w="2700"/></w:tblGrid>
+<w:tr><w:tc><w:p><w:r><w:t>A</w:t></w:r></w:p></w:tc></w:tr>
+</w:tbl>
+"#;
+        let options = Options {
+            fit_tables: true,
+            ..Options::default()
+        };
+        let (actual, packages) = convert_with_options(body, &HashMap::new(), &options);
+        assert_eq!(
+            actual,
+            "\\begin{adjustbox}{max width=\\textwidth}\n\\begin{tabular}{|l|}\n\\hline\nA \\\\\n\\hline\n\\end{tabular}\n\\end{adjustbox}\n\n"
+        );
+        assert!(packages.contains("adjustbox"));
+    }
+
+    #[rstest]
+    #[case::above(
+        r#"<w:p><w:pPr><w:pStyle w:val="Caption"/></w:pPr><w:r><w:t xml:space="preserve">Table </w:t></w:r><w:fldSimple w:instr=" SEQ Table \* ARABIC "><w:r><w:t>1</w:t></w:r></w:fldSimple><w:r><w:t>: Results</w:t></w:r></w:p>{TABLE}<w:p><w:r><w:t>After</w:t></w:r></w:p>"#,
+        "\\begin{table}[h]\n\\centering\n\\caption{Results}\n\\begin{tabular}{|l|}\n\\hline\nA \\\\\n\\hline\n\\end{tabular}\n\\end{table}\n\nAfter\n\n"
+    )]
+    #[case::below(
+        r#"<w:p><w:r><w:t>Before</w:t></w:r></w:p>{TABLE}<w:p><w:pPr><w:pStyle w:val="Caption"/></w:pPr><w:r><w:t>Results</w:t></w:r></w:p>"#,
+        "Before\n\n\\begin{table}[h]\n\\centering\n\\begin{tabular}{|l|}\n\\hline\nA \\\\\n\\hline\n\\end{tabular}\n\\caption{Results}\n\\end{table}\n\n"
+    )]
+    #[case::unnumbered(
+        r#"{TABLE}<w:p><w:pPr><w:pStyle w:val="Caption"/></w:pPr><w:r><w:t>Revenue 2024 by region</w:t></w:r></w:p>"#,
+        "\\begin{table}[h]\n\\centering\n\\begin{tabular}{|l|}\n\\hline\nA \\\\\n\\hline\n\\end{tabular}\n\\caption{Revenue 2024 by region}\n\\end{table}\n\n"
+    )]
+    #[case::field_chars(
+        r#"{TABLE}<w:p><w:pPr><w:pStyle w:val="Caption"/></w:pPr><w:r><w:t xml:space="preserve">Table </w:t></w:r><w:r><w:fldChar w:fldCharType="begin"/></w:r><w:r><w:instrText xml:space="preserve"> SEQ Table \* ARABIC </w:instrText></w:r><w:r><w:fldChar w:fldCharType="separate"/></w:r><w:r><w:t>2</w:t></w:r><w:r><w:fldChar w:fldCharType="end"/></w:r><w:r><w:t xml:space="preserve"> - Revenue 2024 by region</w:t></w:r></w:p>"#,
+        "\\begin{table}[h]\n\\centering\n\\begin{tabular}{|l|}\n\\hline\nA \\\\\n\\hline\n\\end{tabular}\n\\caption{Revenue 2024 by region}\n\\end{table}\n\n"
+    )]
+    #[case::apart(
+        r#"<w:p><w:pPr><w:pStyle w:val="Caption"/></w:pPr><w:r><w:t>Results</w:t></w:r></w:p><w:p><w:r><w:t>Between</w:t></w:r></w:p>{TABLE}"#,
+        "Results\n\nBetween\n\n\\begin{tabular}{|l|}\n\\hline\nA \\\\\n\\hline\n\\end{tabular}\n\n"
+    )]
+    fn document_floats_table_with_caption(#[case] body: &str, #[case] expected: &str) {
+        let table = r#"<w:tbl><w:tblGrid><w:gridCol w:w="2700"/></w:tblGrid><w:tr><w:tc><w:p><w:r><w:t>A</w:t></w:r></w:p></w:tc></w:tr></w:tbl>"#;
+        let body = body.replace("{TABLE}", table);
+        assert_eq!(convert(&body, &HashMap::new()), expected);
     }
 
-    struct Fixture {
-        pub buf_writer: BufWriter<Vec<u8>>,
-        pub rels: HashMap<String, String>,
-        pub stack: Boo<Tag>,
-        pub math_mode: bool,
-        pub nary_has_chr: Option<bool>,
+    #[test]
+    fn document_converts_table_in_repeating_section() {
+        let body = r#"
+<w:sdt>
+<w:sdtPr><w:alias w:val="Items"/><w:tag w:val="Items"/><w:id w:val="1"/><w15:repeatingSection/></w:sdtPr>
+<w:sdtContent>
+<w:tbl>
+<w:tblGrid><w:gridCol w:w="2000"/><w:gridCol w:w="2000"/></w:tblGrid>
+<w:sdt>
+<w:sdtPr><w:id w:val="2"/><w15:repeatingSectionItem/></w:sdtPr>
+<w:sdtContent>
+<w:tr><w:tc><w:p><w:r><w:t>A</w:t></w:r></w:p></w:tc><w:tc><w:p><w:r><w:t>B</w:t></w:r></w:p></w:tc></w:tr>
+</w:sdtContent>
+</w:sdt>
+<w:sdt>
+<w:sdtPr><w:id w:val="3"/><w15:repeatingSectionItem/></w:sdtPr>
+<w:sdtContent>
+<w:tr><w:tc><w:p><w:r><w:t>C</w:t></w:r></w:p></w:tc><w:tc><w:p><w:r><w:t>D</w:t></w:r></w:p></w:tc></w:tr>
+</w:sdtContent>
+</w:sdt>
+</w:tbl>
+</w:sdtContent>
+</w:sdt>
+"#;
+        let actual = convert(body, &HashMap::new());
+        assert_eq!(
+            actual,
+            "\\begin{tabular}{|l|l|}\n\\hline\nA & B \\\\\n\\hline\nC & D \\\\\n\\hline\n\\end{tabular}\n\n"
+        );
     }
 
-    impl Default for Fixture {
-        fn default() -> Self {
-            Self {
-                buf_writer: BufWriter::new(Vec::new()),
-                rels: Default::default(),
-                stack: Default::default(),
-                math_mode: false,
-                nary_has_chr: None,
-            }
-        }
+    #[test]
+    fn document_rotates_bottom_to_top_cell() {
+        let body = r#"
+<w:tbl>
+<w:tblGrid><w:gridCol w:w="2000"/><w:gridCol w:w="2000"/></w:tblGrid>
+<w:tr>
+<w:tc><w:tcPr><w:textDirection w:val="btLr"/></w:tcPr><w:p><w:r><w:t>Header</w:t></w:r></w:p></w:tc>
+<w:tc><w:p><w:r><w:t>Value</w:t></w:r></w:p></w:tc>
+</w:tr>
+</w:tbl>
+"#;
+        let (actual, packages) = convert_with_packages(body, &HashMap::new());
+        assert_eq!(
+            actual,
+            "\\begin{tabular}{|l|l|}\n\\hline\n\\rotatebox{90}{Header} & Value \\\\\n\\hline\n\\end{tabular}\n\n"
+        );
+        assert!(packages.contains("graphicx"));
     }
 
     #[rstest]
-    #[case(Tag::MDelim, "(")]
-    #[case(Tag::MRad, "\\sqrt")]
-    #[case(Tag::MDeg, "[")]
-    #[case(Tag::MSub, "_{")]
-    #[case(Tag::MSup, "^{")]
-    #[case(Tag::MFraction, "\\frac")]
-    #[case(Tag::MNum, "{")]
-    #[case(Tag::MDen, "{")]
-    fn start_element_works_with_simple_tags(#[case] input: Tag, #[case] output: &'static str) {
-        let Fixture {
-            mut buf_writer,
-            rels: _,
-            stack: _,
-            mut math_mode,
-            mut nary_has_chr,
-        } = Fixture::default();
+    #[case(
+        r#"<w:r><w:t>Hello</w:t></w:r><w:r><w:t xml:space="preserve"> world</w:t></w:r>"#,
+        "Hello world"
+    )]
+    #[case(
+        r#"<w:r><w:t>Hello</w:t></w:r><w:r><w:t xml:space="preserve"> </w:t></w:r><w:r><w:t>world</w:t></w:r>"#,
+        "Hello world"
+    )]
+    #[case(r#"<w:r><w:t> Hello </w:t></w:r><w:r><w:t> </w:t></w:r>"#, "Hello")]
+    fn document_preserves_space_of_text(#[case] runs: &str, #[case] expected: &str) {
+        let body = format!("<w:p>{runs}</w:p>");
+        assert_eq!(convert(&body, &HashMap::new()), format!("{expected}\n\n"));
+    }
 
-        let (name, attributes) = input.to_owned().unwrap();
-        let state = start_element(
-            &mut buf_writer,
-            &name,
-            &attributes,
-            &mut math_mode,
-            &mut nary_has_chr,
-        );
-        assert!(state.is_ok());
-        let state = state.unwrap();
-        assert!(matches!(state, State::OpenedTag(_)));
-        if let State::OpenedTag(tag) = state {
-            assert_eq!(tag, input);
-        }
+    #[rstest]
+    #[case(r#"<w:r><w:t>Lorem ipsum  </w:t></w:r>"#, "Lorem ipsum")]
+    #[case(r#"<w:r><w:t>Lorem </w:t></w:r><w:r><w:t> </w:t></w:r>"#, "Lorem")]
+    #[case(
+        r#"<w:r><w:t xml:space="preserve">Lorem ipsum  </w:t></w:r>"#,
+        "Lorem ipsum  "
+    )]
+    #[case(
+        r#"<w:r><w:t xml:space="preserve">Lorem </w:t></w:r><w:r><w:t xml:space="preserve"> </w:t></w:r>"#,
+        "Lorem  "
+    )]
+    #[case(
+        r#"<w:r><w:t xml:space="preserve">Lorem </w:t></w:r><w:r><w:br/></w:r><w:r><w:t>ipsum</w:t></w:r>"#,
+        "Lorem \\\\\nipsum"
+    )]
+    fn document_trims_trailing_space_of_paragraph(#[case] runs: &str, #[case] expected: &str) {
+        let body = format!("<w:p>{runs}</w:p>");
+        assert_eq!(convert(&body, &HashMap::new()), format!("{expected}\n\n"));
+    }
 
-        assert_eq!(drain(&mut buf_writer).unwrap(), output);
+    #[rstest]
+    #[case::color(
+        r#"<w:p><w:r><w:rPr><w:color/></w:rPr><w:t>kept</w:t></w:r></w:p>"#,
+        "kept\n\n"
+    )]
+    #[case::style(
+        r#"<w:p><w:pPr><w:pStyle/></w:pPr><w:r><w:t>styled</w:t></w:r></w:p><w:p><w:r><w:t>next</w:t></w:r></w:p>"#,
+        "styled\n\nnext\n\n"
+    )]
+    fn document_keeps_text_after_tag_missing_attributes(
+        #[case] body: &str,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(convert(body, &HashMap::new()), expected);
     }
 
     #[test]
-    fn start_element_recognizes_missing_attributes() {
-        let Fixture {
-            mut buf_writer,
-            rels: _,
-            stack: _,
-            mut math_mode,
-            mut nary_has_chr,
-        } = Fixture::default();
-
-        let name = owned_name("a", "blip");
-        let state = start_element(
-            &mut buf_writer,
-            &name,
-            &vec![],
-            &mut math_mode,
-            &mut nary_has_chr,
+    fn document_preserves_multiple_spaces() {
+        let body =
+            r#"<w:p><w:r><w:t xml:space="preserve">Name:   Value_1  and 2 </w:t></w:r></w:p>"#;
+        assert_eq!(
+            convert(body, &HashMap::new()),
+            "Name:   Value\\_1  and 2 \n\n"
         );
-        assert!(state.is_ok());
-        let state = state.unwrap();
-        assert!(matches!(state, State::AttributesMissing));
-
-        assert_eq!(drain(&mut buf_writer).unwrap(), "");
+        let options = Options {
+            preserve_spaces: true,
+            ..Options::default()
+        };
+        let (actual, _) = convert_with_options(body, &HashMap::new(), &options);
+        assert_eq!(actual, "Name:\\ \\ \\ Value\\_1\\ \\ and 2 \n\n");
     }
 
     #[test]
-    fn start_element_recognizes_momathpara() {
-        let Fixture {
-            mut buf_writer,
-            rels: _,
-            stack: _,
-            mut math_mode,
-            mut nary_has_chr,
-        } = Fixture::default();
+    fn document_colors_run_text() {
+        let body = r#"<w:p><w:r><w:rPr><w:color w:val="FF0000"/></w:rPr><w:t>Red</w:t></w:r><w:r><w:t xml:space="preserve"> plain</w:t></w:r></w:p>"#;
+        let (actual, packages) = convert_with_packages(body, &HashMap::new());
+        assert_eq!(actual, "\\textcolor[HTML]{FF0000}{Red} plain\n\n");
+        assert!(packages.contains("xcolor"));
+    }
 
-        let name = owned_name("m", "oMathPara");
+    #[rstest]
+    #[case(
+        r#"<w:rFonts w:ascii="Consolas" w:hAnsi="Consolas"/>"#,
+        "\\texttt{x = 1}"
+    )]
+    #[case(r#"<w:rFonts w:ascii="Arial" w:hAnsi="Arial"/>"#, "\\textsf{x = 1}")]
+    #[case(r#"<w:rFonts w:ascii="Calibri" w:hAnsi="Calibri"/>"#, "x = 1")]
+    #[case(
+        r#"<w:rFonts w:asciiTheme="minorHAnsi" w:hAnsiTheme="minorHAnsi"/>"#,
+        "x = 1"
+    )]
+    fn document_sets_font_family_of_run(#[case] fonts: &str, #[case] expected: &str) {
+        let body = format!(r#"<w:p><w:r><w:rPr>{fonts}</w:rPr><w:t>x = 1</w:t></w:r></w:p>"#);
+        assert_eq!(convert(&body, &HashMap::new()), format!("{expected}\n\n"));
+    }
 
-        let state = start_element(
-            &mut buf_writer,
-            &name,
-            &vec![],
-            &mut math_mode,
-            &mut nary_has_chr,
-        );
-        assert!(state.is_ok());
-        let state = state.unwrap();
-        assert!(matches!(state, State::OpenedTag(_)));
-        if let State::OpenedTag(tag) = state {
-            assert_eq!(tag, Tag::MoMathPara);
-        }
+    #[test]
+    fn document_ignores_automatic_color() {
+        let body = r#"<w:p><w:r><w:rPr><w:color w:val="auto"/></w:rPr><w:t>Text</w:t></w:r></w:p>"#;
+        let (actual, packages) = convert_with_packages(body, &HashMap::new());
+        assert_eq!(actual, "Text\n\n");
+        assert!(packages.is_empty());
+    }
 
-        assert_eq!(drain(&mut buf_writer).unwrap(), "$$");
+    #[test]
+    fn document_highlights_run_text() {
+        let body = r#"<w:p><w:r><w:rPr><w:highlight w:val="yellow"/></w:rPr><w:t>Marked</w:t></w:r></w:p>"#;
+        let (actual, packages) = convert_with_packages(body, &HashMap::new());
+        assert_eq!(actual, "\\sethlcolor{yellow}\\hl{Marked}\n\n");
+        assert!(packages.contains("soul"));
+        assert!(packages.contains("xcolor"));
+    }
 
-        math_mode = true;
-        let state = start_element(
-            &mut buf_writer,
-            &name,
-            &vec![],
-            &mut math_mode,
-            &mut nary_has_chr,
+    #[test]
+    fn document_formats_hyperlink_text() {
+        let body = r#"<w:p><w:hyperlink w:anchor="x"><w:r><w:rPr><w:color w:val="FF0000"/><w:highlight w:val="yellow"/></w:rPr><w:t>link</w:t></w:r></w:hyperlink></w:p>"#;
+        let (actual, packages) = convert_with_packages(body, &HashMap::new());
+        assert_eq!(
+            actual,
+            "\\hyperlink{x}{\\sethlcolor{yellow}\\hl{\\textcolor[HTML]{FF0000}{link}}}\n\n"
         );
-        assert!(state.is_ok());
-        let state = state.unwrap();
-        assert!(matches!(state, State::OpenedTag(_)));
-        if let State::OpenedTag(tag) = state {
-            assert_eq!(tag, Tag::MoMathPara);
-        }
+        assert!(packages.contains("soul"));
+        assert!(packages.contains("xcolor"));
+    }
 
-        assert_eq!(drain(&mut buf_writer).unwrap(), "");
+    #[test]
+    fn document_loads_no_color_package_without_colored_text() {
+        let body = r#"<w:p><w:r><w:rPr><w:color w:val="FF0000"/><w:highlight w:val="yellow"/></w:rPr><w:tab/></w:r></w:p>"#;
+        let (_, packages) = convert_with_packages(body, &HashMap::new());
+        assert!(!packages.contains("soul"));
+        assert!(!packages.contains("xcolor"));
     }
 
     #[test]
-    fn start_element_recognizes_mnarypr() {
-        let Fixture {
-            mut buf_writer,
-            rels: _,
-            stack: _,
-            mut math_mode,
-            mut nary_has_chr,
-        } = Fixture::default();
+    fn document_ignores_no_highlight() {
+        let body =
+            r#"<w:p><w:r><w:rPr><w:highlight w:val="none"/></w:rPr><w:t>Text</w:t></w:r></w:p>"#;
+        let (actual, packages) = convert_with_packages(body, &HashMap::new());
+        assert_eq!(actual, "Text\n\n");
+        assert!(packages.is_empty());
+    }
 
-        let name = owned_name("m", "naryPr");
+    #[test]
+    fn document_raises_superscript_run() {
+        let body = r#"<w:p><w:r><w:t>1</w:t></w:r><w:r><w:rPr><w:vertAlign w:val="superscript"/></w:rPr><w:t>st</w:t></w:r></w:p>"#;
+        let actual = convert(body, &HashMap::new());
+        assert_eq!(actual, "1\\textsuperscript{st}\n\n");
+    }
 
-        let state = start_element(
-            &mut buf_writer,
-            &name,
-            &vec![],
-            &mut math_mode,
-            &mut nary_has_chr,
+    #[test]
+    fn document_lowers_subscript_run() {
+        let body = r#"<w:p><w:r><w:t>H</w:t></w:r><w:r><w:rPr><w:color w:val="FF0000"/><w:vertAlign w:val="subscript"/></w:rPr><w:t>2</w:t></w:r><w:r><w:t>O</w:t></w:r></w:p>"#;
+        let actual = convert(body, &HashMap::new());
+        assert_eq!(
+            actual,
+            "H\\textcolor[HTML]{FF0000}{\\textsubscript{2}}O\n\n"
         );
-        assert!(state.is_ok());
-        let state = state.unwrap();
-        assert!(matches!(state, State::OpenedTag(_)));
-        if let State::OpenedTag(tag) = state {
-            assert_eq!(tag, Tag::MNaryPr);
-        }
+    }
 
-        assert_eq!(nary_has_chr, Some(false));
-        assert_eq!(drain(&mut buf_writer).unwrap(), "");
+    #[test]
+    fn document_stretches_auto_line_spacing() {
+        let body = r#"<w:p><w:pPr><w:spacing w:after="160" w:line="360" w:lineRule="auto"/></w:pPr><w:r><w:t>Text</w:t></w:r></w:p>"#;
+        let (actual, packages) = convert_with_packages(body, &HashMap::new());
+        assert_eq!(actual, "{\\setstretch{1.5}Text\\par}\n\n");
+        assert!(packages.contains("setspace"));
+    }
 
-        let state = start_element(
-            &mut buf_writer,
-            &name,
-            &vec![],
-            &mut math_mode,
-            &mut nary_has_chr,
+    #[test]
+    fn document_sets_exact_line_spacing() {
+        let body = r#"<w:p><w:pPr><w:spacing w:line="280" w:lineRule="exact"/></w:pPr><w:r><w:t>Text</w:t></w:r></w:p><w:p><w:r><w:t>Next</w:t></w:r></w:p>"#;
+        let (actual, packages) = convert_with_packages(body, &HashMap::new());
+        assert_eq!(
+            actual,
+            "{\\setlength{\\baselineskip}{14pt}Text\\par}\n\nNext\n\n"
         );
-        assert!(state.is_ok());
-        let state = state.unwrap();
-        assert!(matches!(state, State::OpenedTag(_)));
-        if let State::OpenedTag(tag) = state {
-            assert_eq!(tag, Tag::MNaryPr);
-        }
-
-        assert_eq!(drain(&mut buf_writer).unwrap(), "");
+        assert!(packages.is_empty());
     }
 
-    #[rstest]
-    #[case("⋀", "\\bigwedge")]
-    #[case("⋁", "\\bigvee")]
-    #[case("⋂", "\\bigcap")]
-    #[case("⋃", "\\bigcup")]
-    #[case("∐", "\\coprod")]
-    #[case("∏", "\\prod")]
-    #[case("∑", "\\sum")]
-    #[case("∮", "\\oint")]
-    fn start_element_recognizes_mchr(#[case] input: &str, #[case] output: &str) {
-        let Fixture {
-            mut buf_writer,
-            rels: _,
-            stack: _,
-            mut math_mode,
-            nary_has_chr: _,
-        } = Fixture::default();
-        let mut nary_has_chr = Some(false);
+    #[test]
+    fn document_centers_title_and_subtitle() {
+        let body = r#"<w:p><w:pPr><w:pStyle w:val="Title"/></w:pPr><w:r><w:t>Report</w:t></w:r></w:p><w:p><w:pPr><w:pStyle w:val="Subtitle"/><w:spacing w:line="360" w:lineRule="auto"/></w:pPr><w:r><w:t>Draft</w:t></w:r></w:p>"#;
+        let actual = convert(body, &HashMap::new());
+        assert_eq!(
+            actual,
+            "{\\centering\\LARGE Report\\par}\n\n{\\centering\\Large {\\setstretch{1.5}Draft\\par}}\n\n"
+        );
+    }
 
-        let name = owned_name("m", "chr");
-        let attr = vec![owned_attr("m", "val", input)];
-        let mchr = Tag::MChr {
-            value: input.to_string(),
+    #[test]
+    fn document_makes_title_page() {
+        let body = r#"<w:p><w:pPr><w:pStyle w:val="Title"/></w:pPr><w:r><w:t>Report</w:t></w:r></w:p><w:p><w:r><w:t>Text</w:t></w:r></w:p>"#;
+        let options = Options {
+            title_page: true,
+            ..Options::default()
         };
+        let (actual, _) = convert_with_options(body, &HashMap::new(), &options);
+        assert_eq!(actual, "\\title{Report}\\maketitle\n\nText\n\n");
+    }
 
-        let state = start_element(
-            &mut buf_writer,
-            &name,
-            &attr,
-            &mut math_mode,
-            &mut nary_has_chr,
+    #[test]
+    fn document_wraps_right_to_left_paragraph() {
+        let body = r#"<w:p><w:pPr><w:bidi/><w:spacing w:line="360" w:lineRule="auto"/></w:pPr><w:r><w:t>שלום</w:t></w:r></w:p><w:p><w:pPr><w:bidi w:val="0"/></w:pPr><w:r><w:t>Hello</w:t></w:r></w:p>"#;
+        let (actual, packages) = convert_with_packages(body, &HashMap::new());
+        assert_eq!(
+            actual,
+            "\\begin{RTL}{\\setstretch{1.5}שלום\\par}\\end{RTL}\n\nHello\n\n"
         );
+        assert!(packages.contains("bidi"));
+    }
 
-        assert!(state.is_ok());
-        let state = state.unwrap();
-        assert!(matches!(state, State::OpenedTag(Tag::MChr { value: _ })));
-        if let State::OpenedTag(tag) = state {
-            assert_eq!(tag, mchr);
-        }
-        assert_eq!(drain(&mut buf_writer).unwrap(), output);
-        assert_eq!(nary_has_chr, Some(true));
-
-        let state = start_element(
-            &mut buf_writer,
-            &name,
-            &attr,
-            &mut math_mode,
-            &mut nary_has_chr,
+    #[test]
+    fn document_suppresses_line_numbers_of_paragraph() {
+        let body = r#"<w:p><w:pPr><w:suppressLineNumbers/></w:pPr><w:r><w:t>Quote</w:t></w:r></w:p><w:p><w:r><w:t>Counted</w:t></w:r></w:p><w:sectPr><w:lnNumType w:countBy="1" w:restart="continuous"/></w:sectPr>"#;
+        let (actual, packages) = convert_with_packages(body, &HashMap::new());
+        assert_eq!(
+            actual,
+            "\\runninglinenumbers\n{\\nolinenumbers Quote\\par}\n\nCounted\n\n\\nolinenumbers\n\n"
         );
-
-        assert!(state.is_ok());
-        let state = state.unwrap();
-        assert!(matches!(state, State::OpenedTag(Tag::MChr { value: _ })));
-        if let State::OpenedTag(tag) = state {
-            assert_eq!(tag, mchr);
-        }
-        assert_eq!(nary_has_chr, Some(true));
-        assert_eq!(drain(&mut buf_writer).unwrap(), output);
+        assert!(packages.contains("lineno"));
     }
 
-    #[rstest]
-    #[case(true)]
-    #[case(false)]
-    fn end_element_recognizes_momathpara(#[case] mut mode: bool) {
-        let Fixture {
-            mut buf_writer,
-            rels,
-            stack: _,
-            math_mode: _,
-            mut nary_has_chr,
-        } = Fixture::default();
-
-        let stack = Unimock::new((
-            MockPeek::reset.each_call(matching!()).returns(()),
-            MockPeek::peek.each_call(matching!()).returns(None::<Tag>),
-            MockPeek::last
-                .each_call(matching!())
-                .returns(Some(Tag::MoMathPara))
-                .once(),
-        ));
+    #[test]
+    fn document_numbers_lines_of_section() {
+        let body = r#"<w:p><w:r><w:t>First</w:t></w:r></w:p><w:p><w:pPr><w:sectPr><w:lnNumType w:countBy="5"/></w:sectPr></w:pPr></w:p><w:p><w:r><w:t>Second</w:t></w:r></w:p><w:sectPr/>"#;
+        let (actual, packages) = convert_with_packages(body, &HashMap::new());
+        assert_eq!(
+            actual,
+            "\\modulolinenumbers[5]\\pagewiselinenumbers\nFirst\n\n\n\n\\nolinenumbers\n\nSecond\n\n"
+        );
+        assert!(packages.contains("lineno"));
+    }
 
-        let state = end_element(&mut buf_writer, &stack, &rels, &mut mode, &mut nary_has_chr);
-        assert!(matches!(state, Ok(State::ClosedTag)));
-        assert!(!mode);
-        assert_eq!(drain(&mut buf_writer).unwrap(), "$$\n");
+    #[test]
+    fn document_keeps_number_in_right_to_left_sentence() {
+        let body = r#"<w:p><w:pPr><w:bidi/></w:pPr><w:r><w:rPr><w:rtl/></w:rPr><w:t xml:space="preserve">נולד בשנת </w:t></w:r><w:r><w:t>1984</w:t></w:r><w:r><w:rPr><w:rtl/></w:rPr><w:t xml:space="preserve"> בעיר </w:t></w:r><w:r><w:rPr><w:rtl w:val="0"/></w:rPr><w:t>Paris</w:t></w:r></w:p>"#;
+        let (actual, packages) = convert_with_packages(body, &HashMap::new());
+        assert_eq!(
+            actual,
+            "\\begin{RTL}נולד בשנת \\LR{1984} בעיר \\LR{Paris}\\par\\end{RTL}\n\n"
+        );
+        assert!(packages.contains("bidi"));
     }
 
-    #[rstest]
-    #[case(Some(true), "")]
-    #[case(Some(false), "\\int")]
-    #[case(None, "")]
-    fn end_element_recognizes_nary(#[case] mut nary: Option<bool>, #[case] latex: &'static str) {
-        let Fixture {
-            mut buf_writer,
-            rels,
-            stack: _,
-            mut math_mode,
-            nary_has_chr: _,
-        } = Fixture::default();
+    #[test]
+    fn document_embeds_right_to_left_run() {
+        let body = r#"<w:p><w:r><w:t xml:space="preserve">Shalom is </w:t></w:r><w:r><w:rPr><w:rtl/></w:rPr><w:t>שלום</w:t></w:r><w:r><w:t>.</w:t></w:r></w:p>"#;
+        let (actual, packages) = convert_with_packages(body, &HashMap::new());
+        assert_eq!(actual, "Shalom is \\RL{שלום}.\n\n");
+        assert!(packages.contains("bidi"));
+    }
 
-        let stack = Unimock::new((
-            MockPeek::reset.each_call(matching!()).returns(()),
-            MockPeek::peek.each_call(matching!()).returns(None::<Tag>),
-            MockPeek::last
-                .each_call(matching!())
-                .returns(Some(Tag::MNaryPr))
-                .once(),
-        ));
+    #[test]
+    fn document_escapes_dollar_in_equation() {
+        let body = r#"<w:p><w:r><w:t xml:space="preserve">$5 and </w:t></w:r><m:oMath><m:r><m:t>$x+1</m:t></m:r></m:oMath></w:p>"#;
+        let actual = convert(body, &HashMap::new());
+        assert_eq!(actual, "\\$5 and $\\$x+1$\n\n");
+    }
 
-        let state = end_element(&mut buf_writer, &stack, &rels, &mut math_mode, &mut nary);
-        assert!(matches!(state, Ok(State::ClosedTag)));
-        assert!(nary.is_none());
-        assert_eq!(drain(&mut buf_writer).unwrap(), latex);
+    #[test]
+    fn document_escapes_caret_outside_of_math() {
+        let body =
+            r#"<w:p><w:r><w:t>2^10</w:t></w:r><m:oMath><m:r><m:t>2^10</m:t></m:r></m:oMath></w:p>"#;
+        let actual = convert(body, &HashMap::new());
+        assert_eq!(actual, "2\\textasciicircum{}10$2^10$\n\n");
     }
 
     #[rstest]
-    #[case(Tag::WParagraph, "\n\n")]
-    #[case(Tag::WBookmarkStart { anchor: "Anchor".to_string() }, "\\hypertarget{Anchor}{")]
-    #[case(Tag::MDelim, ")")]
-    #[case(Tag::MDeg, "]{")]
-    #[case(Tag::MSub, "}")]
-    #[case(Tag::MSup, "}")]
-    #[case(Tag::MNum, "}")]
-    #[case(Tag::MDen, "}")]
-    #[case(Tag::MRad, "}")]
-    #[case(Tag::WBookmarkEnd, "}")]
-    fn end_element_recognizes_lonely_tags(#[case] tag: Tag, #[case] latex: &'static str) {
-        let Fixture {
-            mut buf_writer,
-            rels,
-            stack: _,
-            mut math_mode,
-            mut nary_has_chr,
-        } = Fixture::default();
-
-        let stack = Unimock::new((
-            MockPeek::reset.each_call(matching!()).returns(()),
-            MockPeek::peek.each_call(matching!()).returns(None::<Tag>),
-            MockPeek::last
-                .each_call(matching!())
-                .returns(Some(tag))
-                .once(),
-        ));
+    #[case(
+        r#"<w:p><w:r><w:t xml:space="preserve">Let </w:t></w:r><m:oMath><m:r><m:t>x</m:t></m:r></m:oMath><w:r><w:t xml:space="preserve"> be real.</w:t></w:r></w:p>"#,
+        "Let $x$ be real.\n\n"
+    )]
+    #[case(
+        r#"<w:p><m:oMathPara><m:oMath><m:r><m:t>x</m:t></m:r></m:oMath></m:oMathPara></w:p>"#,
+        "$$x$$\n\n\n"
+    )]
+    fn document_tells_inline_from_display_math(#[case] body: &str, #[case] latex: &str) {
+        assert_eq!(convert(body, &HashMap::new()), latex);
+    }
 
-        let state = end_element(
-            &mut buf_writer,
-            &stack,
-            &rels,
-            &mut math_mode,
-            &mut nary_has_chr,
-        );
-        assert!(matches!(state, Ok(State::ClosedTag)));
-        assert_eq!(drain(&mut buf_writer).unwrap(), latex);
+    #[test]
+    fn document_keeps_result_of_unsupported_field() {
+        let body = r#"
+<w:p><w:fldSimple w:instr=" AUTHOR "><w:r><w:t>Smith</w:t></w:r></w:fldSimple></w:p>
+"#;
+        let actual = convert(body, &HashMap::new());
+        assert_eq!(actual, "Smith\n\n");
     }
 
     #[rstest]
@@ -806,18 +4887,10 @@ Relationships>
             mut buf_writer,
             rels,
             stack,
-            mut math_mode,
-            mut nary_has_chr,
+            mut context,
         } = Fixture::default();
 
-        let result = xml_event(
-            &mut buf_writer,
-            &stack,
-            &rels,
-            &event,
-            &mut math_mode,
-            &mut nary_has_chr,
-        );
+        let result = xml_event(&mut buf_writer, &stack, &rels, &event, &mut context);
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), state);
     }
@@ -1,5 +1,9 @@
 use clap::Parser;
-use std::{io::Write, path::PathBuf};
+use std::{
+    collections::{BTreeSet, HashMap},
+    io::{Read, Write},
+    path::{Path, PathBuf},
+};
 
 use xml::reader::EventReader;
 
@@ -7,13 +11,365 @@ use xml::reader::EventReader;
 #[derive(Parser, Debug)]
 #[command(version, about, long_about = None)]
 struct Args {
-    /// Input directory containing Office Open XML package obtained by unzipping target `.docx` file.
-    /// User is tasked with unzipping the file manually to provide finer control over the filesystem.
+    /// Input `.docx` file, or a directory containing the Office Open XML package obtained by unzipping it.
+    /// Unzipping the file manually gives finer control over the filesystem.
+    /// With `-`, a `document.xml` is read from stdin, without relationships and media.
     #[arg(short, long)]
     input: PathBuf,
     /// Output directory, where the resulting latex and media files will be placed.
+    /// With `-`, the latex is written to stdout instead.
     #[arg(short, long)]
     output: PathBuf,
+    /// Skip conversion when the output is newer than the input `document.xml` and its relationships.
+    #[arg(long)]
+    incremental: bool,
+    /// Typeset paragraphs in the Title style with `\maketitle` instead of as a centered line.
+    #[arg(long)]
+    title_page: bool,
+    /// Delimiters around equations: `$$` and `$`, or `\[` `\]` and `\(` `\)` as amsmath recommends.
+    #[arg(long, default_value = "dollars", value_parser = ["dollars", "displaymath"])]
+    math_delimiters: String,
+    /// Show the tooltips of hyperlinks when hovering over them, through the pdfcomment package.
+    #[arg(long)]
+    tooltips: bool,
+    /// Keep runs of two or more spaces in text, which latex would collapse into one.
+    #[arg(long)]
+    preserve_spaces: bool,
+    /// Write drawings that are hidden in Word, which are skipped otherwise.
+    #[arg(long)]
+    include_hidden: bool,
+    /// Scale tables down to the width of the text, through the adjustbox package.
+    #[arg(long)]
+    fit_tables: bool,
+    /// Fail on elements that have no conversion, other than properties that only tune the look and markup such as proofing marks.
+    #[arg(long)]
+    strict: bool,
+    /// Center paragraphs that hold nothing but an image, as figures.
+    #[arg(long)]
+    figures: bool,
+    /// Treat a comma between digits in equations as a decimal separator, as in 3,14.
+    #[arg(long)]
+    decimal_comma: bool,
+    /// Class of the document in the built-in preamble.
+    #[arg(long, default_value = "article")]
+    documentclass: String,
+    /// Size of the body font in the built-in preamble.
+    #[arg(long, default_value = "16pt")]
+    fontsize: String,
+    /// Left, right and bottom margins of the page in the built-in preamble.
+    #[arg(long, default_value = "2cm")]
+    margin: String,
+    /// Preamble and trailer to use instead of the built-in ones, with the body going in place of `%%BODY%%`.
+    #[arg(long)]
+    template: Option<PathBuf>,
+    /// End the lines of the latex with `\r\n`, as is customary on Windows, instead of `\n`.
+    #[arg(long)]
+    crlf: bool,
+    /// Write a `.latexmkrc` next to the document, naming the packages it loads and the engine to compile it with.
+    #[arg(long)]
+    emit_build_hints: bool,
+}
+
+/// Path that stands for stdin as the input and for stdout as the output
+const STREAM: &str = "-";
+
+/// Marks the place of the body in a template
+const BODY_MARKER: &str = "%%BODY%%";
+
+/// Parts of the package that `document.xml` refers to
+#[derive(Default)]
+struct Package {
+    rels: HashMap<String, docx2latex::Target>,
+    numbering: HashMap<String, Vec<docx2latex::Level>>,
+    footnotes: HashMap<String, String>,
+    /// Latex packages that the text of the footnotes depends on
+    footnote_packages: BTreeSet<&'static str>,
+    /// What could not be converted faithfully in the footnotes
+    footnote_report: docx2latex::ConversionReport,
+    styles: HashMap<docx2latex::StyleId, docx2latex::Style>,
+    metadata: docx2latex::Metadata,
+    /// The package has a `word/media` folder
+    media_present: bool,
+}
+
+/// Page and font settings of the built-in preamble
+struct Layout {
+    documentclass: String,
+    fontsize: String,
+    margin: String,
+}
+
+impl From<&Args> for Layout {
+    fn from(args: &Args) -> Self {
+        Self {
+            documentclass: args.documentclass.clone(),
+            fontsize: args.fontsize.clone(),
+            margin: args.margin.clone(),
+        }
+    }
+}
+
+/// Writer that ends every line with a carriage return and a line feed
+struct Crlf<W: Write>(W);
+
+impl<W: Write> Write for Crlf<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        for line in buf.split_inclusive(|&byte| byte == b'\n') {
+            match line.strip_suffix(b"\n") {
+                Some(line) => {
+                    self.0.write_all(line)?;
+                    self.0.write_all(b"\r\n")?;
+                }
+                None => self.0.write_all(line)?,
+            }
+        }
+        // the whole buffer is written, so a line feed is never parted from its carriage return
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.0.flush()
+    }
+}
+
+/// Packages that every document loads, with their options
+const PACKAGES: [(&str, &str); 9] = [
+    ("fontenc", "T2A"),
+    ("inputenc", "utf8"),
+    // the options of fontsize and geometry come from the layout
+    ("fontsize", ""),
+    ("geometry", ""),
+    ("babel", "english,ukrainian"),
+    ("amsmath", ""),
+    ("amssymb", ""),
+    ("dsfont", ""),
+    ("hyperref", ""),
+];
+
+/// Most bytes that the entries of a package may take up once extracted, which stops zip bombs
+const MAX_EXTRACTED: u64 = 1 << 30;
+
+/// Unzips the `.docx` package `archive` into `destination`, skipping entries that would land outside of it
+fn extract(archive: &Path, destination: &Path) -> std::io::Result<()> {
+    let mut archive = zip::ZipArchive::new(std::fs::File::open(archive)?)?;
+    let mut remaining = MAX_EXTRACTED;
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i)?;
+        let Some(name) = entry.enclosed_name() else {
+            log::warn!("Skipping entry {:?} outside of the package", entry.name());
+            continue;
+        };
+        let path = destination.join(name);
+        if entry.is_dir() {
+            std::fs::create_dir_all(&path)?;
+            continue;
+        }
+        if !entry.is_file() {
+            log::warn!("Skipping entry {:?}, which is not a file", entry.name());
+            continue;
+        }
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut file = std::fs::File::create_new(&path)?;
+        // the sizes in the archive can not be trusted, so the limit is on what is actually inflated
+        let written = std::io::copy(&mut (&mut entry).take(remaining + 1), &mut file)?;
+        remaining = remaining.checked_sub(written).ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("Package is larger than {MAX_EXTRACTED} bytes once extracted"),
+            )
+        })?;
+    }
+    Ok(())
+}
+
+/// Checks that `output` exists and was modified after every one of `inputs`
+fn up_to_date(inputs: &[PathBuf], output: &Path) -> std::io::Result<bool> {
+    if !output.exists() {
+        return Ok(false);
+    }
+    let converted = std::fs::metadata(output)?.modified()?;
+    for input in inputs {
+        if std::fs::metadata(input)?.modified()? > converted {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+/// Checks whether a part of the package is a font, which latex can not use
+fn is_font(path: &Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| {
+            ["odttf", "ttf", "otf", "fntdata"].contains(&extension.to_lowercase().as_str())
+        })
+}
+
+/// Copies every file of `media` into `output`, except for fonts
+fn copy_media(media: &Path, output: &Path) -> std::io::Result<()> {
+    for entry in std::fs::read_dir(media)? {
+        if let Ok(file) = &entry {
+            if is_font(&file.path()) {
+                log::warn!("Skipping embedded font {:?}", file.file_name());
+                continue;
+            }
+            std::fs::copy(file.path(), output.join(file.file_name()))?;
+            log::info!("Copied media file {:?}", file.file_name());
+        } else {
+            log::error!("DirEntry Error: {:?}", &entry);
+        }
+    }
+    Ok(())
+}
+
+/// Writes the preamble, returning the names of the loaded packages in order
+fn preamble<W: Write>(
+    buf_writer: &mut W,
+    packages: &BTreeSet<&'static str>,
+    media_present: bool,
+    layout: &Layout,
+) -> std::io::Result<Vec<&'static str>> {
+    let mut loaded = Vec::new();
+
+    writeln!(buf_writer, "\\documentclass{{{}}}", layout.documentclass)?;
+    for (package, options) in PACKAGES {
+        let options = match package {
+            "fontsize" => format!("fontsize={}", layout.fontsize),
+            "geometry" => format!("left={0},right={0},bottom={0}", layout.margin),
+            _ => options.to_string(),
+        };
+        if options.is_empty() {
+            writeln!(buf_writer, "\\usepackage{{{package}}}")?;
+        } else {
+            writeln!(buf_writer, "\\usepackage[{options}]{{{package}}}")?;
+        }
+        loaded.push(package);
+    }
+
+    for package in packages {
+        if *package != "bidi" && (*package != "graphicx" || !media_present) {
+            writeln!(buf_writer, "\\usepackage{{{package}}}")?;
+            loaded.push(package);
+        }
+    }
+
+    if media_present {
+        writeln!(buf_writer, "\\usepackage{{graphicx}}")?;
+        writeln!(buf_writer, "\\graphicspath{{ {{./media/}} }}")?;
+        loaded.push("graphicx");
+    }
+
+    // bidi has to be loaded after every other package
+    if packages.contains("bidi") {
+        writeln!(buf_writer, "\\usepackage{{bidi}}")?;
+        loaded.push("bidi");
+    }
+
+    Ok(loaded)
+}
+
+/// Contents of a `.latexmkrc` that compiles a document loading `packages`
+fn build_hints(packages: &[&str]) -> String {
+    let mut hints = format!("# Packages: {}\n", packages.join(", "));
+    // bidi only works with xelatex
+    if packages.contains(&"bidi") {
+        hints.push_str("$pdf_mode = 5;\n");
+    } else {
+        hints.push_str("$pdf_mode = 1;\n");
+    }
+    // svg calls inkscape to convert images
+    if packages.contains(&"svg") {
+        hints.push_str("set_tex_cmds('-shell-escape %O %S');\n");
+    }
+    hints
+}
+
+/// Converts `document.xml` read from `reader` into a complete latex document,
+/// returning the names of the packages it loads, or needs when the preamble comes from a `template`
+fn convert<R: Read, W: Write>(
+    reader: R,
+    writer: &mut W,
+    package: &Package,
+    options: &docx2latex::Options,
+    template: Option<&str>,
+    layout: &Layout,
+) -> std::io::Result<Vec<&'static str>> {
+    let mut parser = EventReader::new(std::io::BufReader::new(reader));
+
+    // the body is converted first, because it decides which packages the preamble needs
+    let mut body = std::io::BufWriter::new(Vec::new());
+    let prysm = docx2latex::Prysm::new(package.rels.clone())
+        .options(options.clone())
+        .numbering(package.numbering.clone())
+        .footnotes(package.footnotes.clone())
+        .styles(package.styles.clone());
+    let (mut packages, mut report) = prysm.document(&mut parser, &mut body)?;
+    let body = body.into_inner().map_err(|e| e.into_error())?;
+    packages.extend(&package.footnote_packages);
+    let notes = package.footnote_report.diagnostics.iter().cloned();
+    report.diagnostics.extend(notes);
+    if !report.is_clean() {
+        log::warn!(
+            "Converted with {} errors and {} warnings",
+            report.count(docx2latex::Severity::Error),
+            report.count(docx2latex::Severity::Warning)
+        );
+    }
+
+    let mut buf_writer = std::io::BufWriter::new(writer);
+
+    if let Some(template) = template {
+        let (preamble, trailer) = template.split_once(BODY_MARKER).ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("Template has no {BODY_MARKER} marker"),
+            )
+        })?;
+        for package in &packages {
+            log::info!("The document needs package {package:?}, which the template has to load");
+        }
+        buf_writer.write_all(preamble.as_bytes())?;
+        buf_writer.write_all(&body)?;
+        buf_writer.write_all(trailer.as_bytes())?;
+        buf_writer.flush()?;
+        return Ok(packages.into_iter().collect());
+    }
+
+    let loaded = preamble(&mut buf_writer, &packages, package.media_present, layout)?;
+
+    // with --title-page, a Title paragraph makes the title block where it is, replacing the title
+    // of the metadata but keeping its author and date; text is escaped, so only it writes \maketitle
+    let metadata = &package.metadata;
+    let maketitle = b"\\maketitle";
+    let titled = options.title_page && body.windows(maketitle.len()).any(|w| w == maketitle);
+    if metadata.title.is_some() || titled {
+        writeln!(&mut buf_writer)?;
+        if let Some(title) = &metadata.title {
+            writeln!(&mut buf_writer, "\\title{{{title}}}")?;
+        }
+        let author = metadata.author.as_deref().unwrap_or_default();
+        writeln!(&mut buf_writer, "\\author{{{author}}}")?;
+        let date = metadata.date.as_deref().unwrap_or_default();
+        writeln!(&mut buf_writer, "\\date{{{date}}}")?;
+    }
+
+    writeln!(&mut buf_writer)?;
+    writeln!(&mut buf_writer, "\\begin{{document}}")?;
+    writeln!(&mut buf_writer)?;
+
+    if metadata.title.is_some() && !titled {
+        writeln!(&mut buf_writer, "\\maketitle")?;
+        writeln!(&mut buf_writer)?;
+    }
+
+    buf_writer.write_all(&body)?;
+
+    writeln!(&mut buf_writer, "\\end{{document}}")?;
+    buf_writer.flush()?;
+
+    Ok(loaded)
 }
 
 fn main() -> std::io::Result<()> {
@@ -22,92 +378,527 @@ fn main() -> std::io::Result<()> {
     log::info!("Entered 'main'");
 
     let args = Args::parse();
-    log::debug!("Input directory is {:?}", args.input);
-    log::debug!("Output directory is {:?}", args.output);
+    log::debug!("Input is {:?}", args.input);
+    log::debug!("Output is {:?}", args.output);
+
+    let options = docx2latex::Options {
+        title_page: args.title_page,
+        math_delimiters: match args.math_delimiters.as_str() {
+            "displaymath" => docx2latex::MathDelimiters::DisplayMath,
+            _ => docx2latex::MathDelimiters::Dollars,
+        },
+        tooltips: args.tooltips,
+        preserve_spaces: args.preserve_spaces,
+        include_hidden: args.include_hidden,
+        fit_tables: args.fit_tables,
+        strict: args.strict,
+        figures: args.figures,
+        decimal_comma: args.decimal_comma,
+        ..Default::default()
+    };
 
+    let layout = Layout::from(&args);
+
+    let stdout = args.output == Path::new(STREAM);
     let mut output = args.output;
-    if !output.exists() {
+    if !stdout && !output.exists() {
         log::info!("Creating directory {:?}", output);
         std::fs::create_dir(&output)?;
     }
 
     let mut input = args.input;
 
-    input.push("word");
-    input.push("media");
-    let media_present;
-    if input.exists() {
-        log::info!("Found a media folder at {:?}", &input);
-        media_present = true;
-        output.push("media");
-        if !output.exists() {
-            log::info!("Creating directory {:?}", output);
-            std::fs::create_dir(&output)?;
-        }
-        for entry in std::fs::read_dir(&input)? {
-            if let Ok(file) = &entry {
-                output.push(file.file_name());
-                std::fs::copy(file.path(), &output)?;
-                log::info!("Copied media file {:?}", file.file_name());
-                output.pop();
+    // a document read from stdin comes without the rest of its package
+    let (reader, package): (Box<dyn Read>, _) = if input == Path::new(STREAM) {
+        log::info!("Reading document.xml from stdin, relationships and media are skipped");
+        (Box::new(std::io::stdin().lock()), Package::default())
+    } else {
+        if args.incremental && !stdout {
+            let inputs = if input.is_file() {
+                vec![input.clone()]
             } else {
-                log::error!("DirEntry Error: {:?}", &entry);
+                vec![
+                    input.join("word").join("document.xml"),
+                    input.join("word").join("_rels").join("document.xml.rels"),
+                ]
+            };
+            if up_to_date(&inputs, &output.join("document.latex"))? {
+                log::info!("Output is up to date with {:?}, skipping", &input);
+                return Ok(());
+            }
+        }
+
+        // a `.docx` file is unzipped into a temporary directory, removed once the conversion is over
+        let _extracted = if input.is_file() {
+            let directory = tempfile::Builder::new().prefix("docx2latex-").tempdir()?;
+            log::info!("Extracting {:?} into {:?}", &input, directory.path());
+            extract(&input, directory.path())?;
+            input = directory.path().to_path_buf();
+            Some(directory)
+        } else {
+            None
+        };
+
+        input.push("word");
+        if input.join("fonts").exists() {
+            log::warn!("Fonts embedded in {:?} will not be reproduced", &input);
+        }
+        input.push("media");
+        let media_present;
+        if input.exists() && stdout {
+            log::warn!("Media in {:?} is not copied when writing to stdout", &input);
+            media_present = true;
+        } else if input.exists() {
+            log::info!("Found a media folder at {:?}", &input);
+            media_present = true;
+            output.push("media");
+            if !output.exists() {
+                log::info!("Creating directory {:?}", output);
+                std::fs::create_dir(&output)?;
             }
+            copy_media(&input, &output)?;
+            output.pop();
+        } else {
+            log::info!("Did not find media folder at {:?}", &input);
+            media_present = false;
         }
-        output.pop();
+        input.pop();
+
+        input.push("_rels");
+        input.push("document.xml.rels");
+
+        log::debug!("Reading {:?}", &input);
+        let mut parser = EventReader::new(std::io::BufReader::new(std::fs::File::open(&input)?));
+        let rels = docx2latex::relationships(&mut parser)
+            .map_err(|_| std::io::Error::from(std::io::ErrorKind::InvalidData))?;
+
+        input.pop();
+        input.pop();
+        input.push("numbering.xml");
+
+        let numbering = if input.exists() {
+            log::debug!("Reading {:?}", &input);
+            let mut parser =
+                EventReader::new(std::io::BufReader::new(std::fs::File::open(&input)?));
+            docx2latex::numbering(&mut parser)
+                .map_err(|_| std::io::Error::from(std::io::ErrorKind::InvalidData))?
+        } else {
+            log::info!("Did not find numbering at {:?}", &input);
+            Default::default()
+        };
+
+        input.pop();
+        input.push("styles.xml");
+
+        let styles = if input.exists() {
+            log::debug!("Reading {:?}", &input);
+            let mut parser =
+                EventReader::new(std::io::BufReader::new(std::fs::File::open(&input)?));
+            docx2latex::styles(&mut parser)
+                .map_err(|_| std::io::Error::from(std::io::ErrorKind::InvalidData))?
+        } else {
+            log::info!("Did not find styles at {:?}", &input);
+            Default::default()
+        };
+
+        input.pop();
+        input.push("footnotes.xml");
+
+        let (footnotes, footnote_packages, footnote_report) = if input.exists() {
+            log::debug!("Reading {:?}", &input);
+            let mut parser =
+                EventReader::new(std::io::BufReader::new(std::fs::File::open(&input)?));
+            docx2latex::footnotes(&mut parser, &rels, &numbering, &styles, &options)?
+        } else {
+            log::info!("Did not find footnotes at {:?}", &input);
+            Default::default()
+        };
+
+        input.pop();
+        input.pop();
+        input.push("docProps");
+        input.push("core.xml");
+
+        let metadata = if input.exists() {
+            log::debug!("Reading {:?}", &input);
+            let mut parser =
+                EventReader::new(std::io::BufReader::new(std::fs::File::open(&input)?));
+            docx2latex::metadata(&mut parser)
+                .map_err(|_| std::io::Error::from(std::io::ErrorKind::InvalidData))?
+        } else {
+            log::info!("Did not find metadata at {:?}", &input);
+            Default::default()
+        };
+
+        input.pop();
+        input.pop();
+        input.push("word");
+        input.push("document.xml");
+
+        // the temporary directory may be removed once the document is read into memory
+        log::debug!("Reading {:?}", &input);
+        let document = std::fs::read(&input)?;
+        (
+            Box::new(std::io::Cursor::new(document)),
+            Package {
+                rels,
+                numbering,
+                footnotes,
+                footnote_packages,
+                footnote_report,
+                styles,
+                metadata,
+                media_present,
+            },
+        )
+    };
+
+    let template = match &args.template {
+        Some(path) => {
+            log::debug!("Reading {:?}", path);
+            Some(std::fs::read_to_string(path)?)
+        }
+        None => None,
+    };
+
+    let mut writer: Box<dyn Write> = if stdout {
+        Box::new(std::io::stdout().lock())
     } else {
-        log::info!("Did not find media folder at {:?}", &input);
-        media_present = false;
-    }
-    input.pop();
-
-    output.push("document.latex");
-    log::info!("Creating file {:?}", output);
-    let mut buf_writer = std::io::BufWriter::new(std::fs::File::create(&output)?);
-
-    writeln!(&mut buf_writer, "\\documentclass{{article}}")?;
-    writeln!(&mut buf_writer, "\\usepackage[T2A]{{fontenc}}")?;
-    writeln!(&mut buf_writer, "\\usepackage[utf8]{{inputenc}}")?;
-    writeln!(&mut buf_writer, "\\usepackage[fontsize=16pt]{{fontsize}}")?;
-    writeln!(
-        &mut buf_writer,
-        "\\usepackage[left=2cm,right=2cm,bottom=2cm]{{geometry}}"
+        output.push("document.latex");
+        log::info!("Creating file {:?}", output);
+        Box::new(std::fs::File::create(&output)?)
+    };
+    if args.crlf {
+        writer = Box::new(Crlf(writer));
+    }
+    let loaded = convert(
+        reader,
+        &mut writer,
+        &package,
+        &options,
+        template.as_deref(),
+        &layout,
     )?;
-    writeln!(&mut buf_writer, "\\usepackage[english,ukrainian]{{babel}}")?;
-    writeln!(&mut buf_writer, "\\usepackage{{amsmath}}")?;
-    writeln!(&mut buf_writer, "\\usepackage{{amssymb}}")?;
-    writeln!(&mut buf_writer, "\\usepackage{{dsfont}}")?;
-    writeln!(&mut buf_writer, "\\usepackage{{hyperref}}")?;
 
-    if media_present {
-        writeln!(&mut buf_writer, "\\usepackage{{graphicx}}")?;
-        writeln!(&mut buf_writer, "\\graphicspath{{ {{./media/}} }}")?;
+    if args.emit_build_hints && stdout {
+        log::warn!("Build hints are not written when writing to stdout");
+    } else if args.emit_build_hints {
+        output.set_file_name(".latexmkrc");
+        log::info!("Creating file {:?}", output);
+        std::fs::write(&output, build_hints(&loaded))?;
     }
 
-    writeln!(&mut buf_writer)?;
-    writeln!(&mut buf_writer, "\\begin{{document}}")?;
-    writeln!(&mut buf_writer)?;
+    log::info!("Exiting 'main'");
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
 
-    input.push("_rels");
-    input.push("document.xml.rels");
+    fn touch(path: &Path) {
+        std::fs::write(path, "").unwrap();
+    }
 
-    log::debug!("Reading {:?}", &input);
-    let mut parser = EventReader::new(std::io::BufReader::new(std::fs::File::open(&input)?));
-    let rels = docx2latex::relationships(&mut parser)
-        .map_err(|_| std::io::Error::from(std::io::ErrorKind::InvalidData))?;
+    /// Creates an empty file, last modified `seconds` after the epoch
+    fn touch_at(path: &Path, seconds: u64) {
+        let file = std::fs::File::create(path).unwrap();
+        let modified = std::time::UNIX_EPOCH + std::time::Duration::from_secs(seconds);
+        file.set_modified(modified).unwrap();
+    }
 
-    input.pop();
-    input.pop();
-    input.push("document.xml");
+    #[test]
+    fn up_to_date_skips_unchanged_input() {
+        let dir = tempfile::tempdir().unwrap();
+        let input = dir.path().join("document.xml");
+        let output = dir.path().join("document.latex");
+        touch_at(&input, 1_000);
+        touch_at(&output, 2_000);
 
-    log::debug!("Reading {:?}", &input);
-    let mut parser = EventReader::new(std::io::BufReader::new(std::fs::File::open(&input)?));
+        assert!(up_to_date(&[input], &output).unwrap());
+    }
 
-    docx2latex::document(&mut parser, &mut buf_writer, &rels)?;
+    #[test]
+    fn up_to_date_rejects_changed_input() {
+        let dir = tempfile::tempdir().unwrap();
+        let input = dir.path().join("document.xml");
+        let output = dir.path().join("document.latex");
+        touch_at(&output, 1_000);
+        touch_at(&input, 2_000);
 
-    writeln!(&mut buf_writer, "\\end{{document}}")?;
+        assert!(!up_to_date(&[input], &output).unwrap());
+    }
 
-    log::info!("Exiting 'main'");
+    #[test]
+    fn copy_media_skips_fonts() {
+        let dir = tempfile::tempdir().unwrap();
+        let media = dir.path().join("media");
+        let output = dir.path().join("output");
+        std::fs::create_dir_all(&media).unwrap();
+        std::fs::create_dir_all(&output).unwrap();
+        touch(&media.join("image1.png"));
+        touch(&media.join("font1.odttf"));
+        touch(&media.join("font2.TTF"));
 
-    Ok(())
+        copy_media(&media, &output).unwrap();
+        assert!(output.join("image1.png").exists());
+        assert!(!output.join("font1.odttf").exists());
+        assert!(!output.join("font2.TTF").exists());
+    }
+
+    #[test]
+    fn build_hints_list_graphicx_with_media() {
+        let mut preamble_text = Vec::new();
+        let loaded = preamble(
+            &mut preamble_text,
+            &BTreeSet::from(["xcolor"]),
+            true,
+            &layout(),
+        )
+        .unwrap();
+        let hints = build_hints(&loaded);
+        assert!(hints.contains("graphicx"));
+        assert!(hints.contains("xcolor"));
+        assert!(hints.contains("$pdf_mode = 1;"));
+    }
+
+    #[test]
+    fn build_hints_choose_xelatex_for_bidi() {
+        let mut preamble_text = Vec::new();
+        let loaded = preamble(
+            &mut preamble_text,
+            &BTreeSet::from(["bidi"]),
+            false,
+            &layout(),
+        )
+        .unwrap();
+        assert_eq!(loaded.last(), Some(&"bidi"));
+        assert!(build_hints(&loaded).contains("$pdf_mode = 5;"));
+    }
+
+    fn layout() -> Layout {
+        let args = Args::try_parse_from(["docx2latex", "-i", "in", "-o", "out"]).unwrap();
+        Layout::from(&args)
+    }
+
+    const DOCUMENT: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">
+<w:body><w:p><w:r><w:t>Piped</w:t></w:r></w:p></w:body>
+</w:document>
+"#;
+
+    #[test]
+    fn convert_streams_a_document() {
+        let mut stdout = Vec::new();
+        let loaded = convert(
+            DOCUMENT.as_bytes(),
+            &mut stdout,
+            &Package::default(),
+            &docx2latex::Options::default(),
+            None,
+            &layout(),
+        )
+        .unwrap();
+        let latex = String::from_utf8(stdout).unwrap();
+        assert!(latex.starts_with("\\documentclass{article}\n"));
+        assert!(latex.ends_with("\\begin{document}\n\nPiped\n\n\\end{document}\n"));
+        assert!(!loaded.contains(&"graphicx"));
+    }
+
+    #[test]
+    fn convert_writes_title_from_metadata() {
+        let package = Package {
+            metadata: docx2latex::Metadata {
+                title: Some("Report".to_string()),
+                author: None,
+                date: Some("2024-03-01".to_string()),
+            },
+            ..Default::default()
+        };
+        let mut output = Vec::new();
+        convert(
+            DOCUMENT.as_bytes(),
+            &mut output,
+            &package,
+            &docx2latex::Options::default(),
+            None,
+            &layout(),
+        )
+        .unwrap();
+        let latex = String::from_utf8(output).unwrap();
+        assert!(latex.contains(
+            "\n\\title{Report}\n\\author{}\n\\date{2024-03-01}\n\n\\begin{document}\n\n\\maketitle\n\nPiped\n\n"
+        ));
+
+        let mut output = Vec::new();
+        let options = docx2latex::Options {
+            title_page: true,
+            ..Default::default()
+        };
+        convert(
+            DOCUMENT.as_bytes(),
+            &mut output,
+            &package,
+            &options,
+            None,
+            &layout(),
+        )
+        .unwrap();
+        // without a Title paragraph, the title of the metadata is kept
+        assert!(String::from_utf8(output).unwrap().contains(
+            "\n\\title{Report}\n\\author{}\n\\date{2024-03-01}\n\n\\begin{document}\n\n\\maketitle\n\nPiped\n\n"
+        ));
+    }
+
+    #[test]
+    fn convert_keeps_author_and_date_of_metadata_for_title_page() {
+        let package = Package {
+            metadata: docx2latex::Metadata {
+                title: Some("Report".to_string()),
+                author: Some("A. Author".to_string()),
+                date: Some("2024-03-01".to_string()),
+            },
+            ..Default::default()
+        };
+        let document = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">
+<w:body><w:p><w:pPr><w:pStyle w:val="Title"/></w:pPr><w:r><w:t>Styled</w:t></w:r></w:p><w:p><w:r><w:t>Piped</w:t></w:r></w:p></w:body>
+</w:document>
+"#;
+        let options = docx2latex::Options {
+            title_page: true,
+            ..Default::default()
+        };
+        let mut output = Vec::new();
+        convert(
+            document.as_bytes(),
+            &mut output,
+            &package,
+            &options,
+            None,
+            &layout(),
+        )
+        .unwrap();
+        let latex = String::from_utf8(output).unwrap();
+        assert!(latex.contains(
+            "\n\\title{Report}\n\\author{A. Author}\n\\date{2024-03-01}\n\n\\begin{document}\n\n\\title{Styled}\\maketitle\n\nPiped\n\n"
+        ));
+        assert_eq!(latex.matches("\\maketitle").count(), 1);
+    }
+
+    #[test]
+    fn convert_fills_template() {
+        let template = "\\documentclass{report}\n\\begin{document}\n%%BODY%%\\end{document}\n";
+        let mut output = Vec::new();
+        convert(
+            DOCUMENT.as_bytes(),
+            &mut output,
+            &Package::default(),
+            &docx2latex::Options::default(),
+            Some(template),
+            &layout(),
+        )
+        .unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "\\documentclass{report}\n\\begin{document}\nPiped\n\n\\end{document}\n"
+        );
+    }
+
+    #[test]
+    fn convert_rejects_template_without_marker() {
+        let result = convert(
+            DOCUMENT.as_bytes(),
+            &mut Vec::new(),
+            &Package::default(),
+            &docx2latex::Options::default(),
+            Some("\\documentclass{report}\n"),
+            &layout(),
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn preamble_uses_layout_from_args() {
+        let mut preamble_text = Vec::new();
+        preamble(&mut preamble_text, &BTreeSet::new(), false, &layout()).unwrap();
+        let preamble_text = String::from_utf8(preamble_text).unwrap();
+        assert!(preamble_text.starts_with("\\documentclass{article}\n"));
+        assert!(preamble_text.contains("\\usepackage[fontsize=16pt]{fontsize}\n"));
+        assert!(preamble_text.contains("\\usepackage[left=2cm,right=2cm,bottom=2cm]{geometry}\n"));
+
+        let args = Args::try_parse_from([
+            "docx2latex",
+            "-i",
+            "in",
+            "-o",
+            "out",
+            "--documentclass=report",
+            "--fontsize=12pt",
+            "--margin=1in",
+        ])
+        .unwrap();
+        let mut preamble_text = Vec::new();
+        preamble(
+            &mut preamble_text,
+            &BTreeSet::new(),
+            false,
+            &Layout::from(&args),
+        )
+        .unwrap();
+        let preamble_text = String::from_utf8(preamble_text).unwrap();
+        assert!(preamble_text.starts_with("\\documentclass{report}\n"));
+        assert!(preamble_text.contains("\\usepackage[fontsize=12pt]{fontsize}\n"));
+        assert!(preamble_text.contains("\\usepackage[left=1in,right=1in,bottom=1in]{geometry}\n"));
+    }
+
+    #[test]
+    fn crlf_ends_lines_with_carriage_returns() {
+        let mut output = Vec::new();
+        convert(
+            DOCUMENT.as_bytes(),
+            &mut Crlf(&mut output),
+            &Package::default(),
+            &docx2latex::Options::default(),
+            None,
+            &layout(),
+        )
+        .unwrap();
+        let latex = String::from_utf8(output).unwrap();
+        assert!(latex.starts_with("\\documentclass{article}\r\n"));
+        assert!(latex.ends_with("Piped\r\n\r\n\\end{document}\r\n"));
+        assert_eq!(latex.matches('\n').count(), latex.matches("\r\n").count());
+    }
+
+    /// Leaves out the whitespace between tags, which differs between the example and its package
+    fn markup(xml: &str) -> String {
+        xml.split('>')
+            .map(str::trim_start)
+            .collect::<Vec<_>>()
+            .join(">")
+    }
+
+    #[test]
+    fn extract_unpacks_example() {
+        let dir = tempfile::tempdir().unwrap();
+        extract(Path::new("example.docx"), dir.path()).unwrap();
+
+        let document = std::fs::read_to_string(dir.path().join("word/document.xml")).unwrap();
+        let example = std::fs::read_to_string("example/word/document.xml").unwrap();
+        assert_eq!(markup(&document), markup(&example));
+        assert_eq!(
+            std::fs::read(dir.path().join("word/media/image1.jpeg")).unwrap(),
+            std::fs::read("example/word/media/image1.jpeg").unwrap()
+        );
+    }
+
+    #[test]
+    fn up_to_date_rejects_missing_output() {
+        let dir = tempfile::tempdir().unwrap();
+        let input = dir.path().join("document.xml");
+        touch(&input);
+
+        assert!(!up_to_date(&[input], &dir.path().join("document.latex")).unwrap());
+    }
 }
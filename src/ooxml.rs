@@ -1,4 +1,4 @@
-use super::{blink, Link, Tag};
+use super::{blink, Link, Spacing, Tag, Width};
 use crate::peekaboo::Peek;
 
 pub fn hyperlink<P: Peek<Item = Tag>>(boo: &P) -> Option<(&Link, &String)> {
@@ -39,6 +39,243 @@ pub fn math_text<P: Peek<Item = Tag>>(boo: &P) -> Option<&String> {
     Some(content)
 }
 
+pub fn function_name<P: Peek<Item = Tag>>(boo: &P) -> Option<&String> {
+    boo.reset();
+    let content = boo.peek()?.content()?;
+    blink(matches!(boo.peek()?, Tag::MText))?;
+    blink(matches!(boo.peek()?, Tag::MRun))?;
+    blink(matches!(boo.peek()?, Tag::MFName))?;
+    Some(content)
+}
+
+pub fn tab<P: Peek<Item = Tag>>(boo: &P) -> Option<()> {
+    boo.reset();
+    blink(matches!(boo.peek()?, Tag::WTab))?;
+    blink(matches!(boo.peek()?, Tag::WRun))?;
+    Some(())
+}
+
+pub fn run_color<P: Peek<Item = Tag>>(boo: &P) -> Option<&String> {
+    boo.reset();
+    let value = boo.peek()?.w_color()?;
+    blink(matches!(boo.peek()?, Tag::WRunPr))?;
+    blink(matches!(boo.peek()?, Tag::WRun))?;
+    Some(value)
+}
+
+pub fn run_highlight<P: Peek<Item = Tag>>(boo: &P) -> Option<&String> {
+    boo.reset();
+    let value = boo.peek()?.w_highlight()?;
+    blink(matches!(boo.peek()?, Tag::WRunPr))?;
+    blink(matches!(boo.peek()?, Tag::WRun))?;
+    Some(value)
+}
+
+pub fn run_vert_align<P: Peek<Item = Tag>>(boo: &P) -> Option<&String> {
+    boo.reset();
+    let value = boo.peek()?.w_vert_align()?;
+    blink(matches!(boo.peek()?, Tag::WRunPr))?;
+    blink(matches!(boo.peek()?, Tag::WRun))?;
+    Some(value)
+}
+
+pub fn run_fonts<P: Peek<Item = Tag>>(boo: &P) -> Option<&String> {
+    boo.reset();
+    let font = boo.peek()?.w_run_fonts()?;
+    blink(matches!(boo.peek()?, Tag::WRunPr))?;
+    blink(matches!(boo.peek()?, Tag::WRun))?;
+    Some(font)
+}
+
+pub fn run_rtl<P: Peek<Item = Tag>>(boo: &P) -> Option<bool> {
+    boo.reset();
+    let rtl = boo.peek()?.w_rtl()?;
+    blink(matches!(boo.peek()?, Tag::WRunPr))?;
+    blink(matches!(boo.peek()?, Tag::WRun))?;
+    Some(rtl)
+}
+
+pub fn run_style<P: Peek<Item = Tag>>(boo: &P) -> Option<&String> {
+    boo.reset();
+    let style = boo.peek()?.w_run_style()?;
+    blink(matches!(boo.peek()?, Tag::WRunPr))?;
+    blink(matches!(boo.peek()?, Tag::WRun))?;
+    Some(style)
+}
+
+pub fn paragraph_style<P: Peek<Item = Tag>>(boo: &P) -> Option<&String> {
+    boo.reset();
+    let style = boo.peek()?.w_paragraph_style()?;
+    blink(matches!(boo.peek()?, Tag::WParagraphPr))?;
+    blink(matches!(boo.peek()?, Tag::WParagraph))?;
+    Some(style)
+}
+
+pub fn paragraph_suppress_line_numbers<P: Peek<Item = Tag>>(boo: &P) -> Option<()> {
+    boo.reset();
+    blink(matches!(boo.peek()?, Tag::WSuppressLineNumbers))?;
+    blink(matches!(boo.peek()?, Tag::WParagraphPr))?;
+    blink(matches!(boo.peek()?, Tag::WParagraph))?;
+    Some(())
+}
+
+pub fn paragraph_bidi<P: Peek<Item = Tag>>(boo: &P) -> Option<bool> {
+    boo.reset();
+    let bidi = boo.peek()?.w_bidi()?;
+    blink(matches!(boo.peek()?, Tag::WParagraphPr))?;
+    blink(matches!(boo.peek()?, Tag::WParagraph))?;
+    Some(bidi)
+}
+
+pub fn paragraph_level<P: Peek<Item = Tag>>(boo: &P) -> Option<&String> {
+    boo.reset();
+    let level = boo.peek()?.w_level()?;
+    blink(matches!(boo.peek()?, Tag::WNumPr))?;
+    blink(matches!(boo.peek()?, Tag::WParagraphPr))?;
+    blink(matches!(boo.peek()?, Tag::WParagraph))?;
+    Some(level)
+}
+
+pub fn paragraph_numbering<P: Peek<Item = Tag>>(boo: &P) -> Option<&String> {
+    boo.reset();
+    let id = boo.peek()?.w_num_id()?;
+    blink(matches!(boo.peek()?, Tag::WNumPr))?;
+    blink(matches!(boo.peek()?, Tag::WParagraphPr))?;
+    blink(matches!(boo.peek()?, Tag::WParagraph))?;
+    Some(id)
+}
+
+pub fn paragraph_outline_level<P: Peek<Item = Tag>>(boo: &P) -> Option<&String> {
+    boo.reset();
+    let level = boo.peek()?.w_outline_level()?;
+    blink(matches!(boo.peek()?, Tag::WParagraphPr))?;
+    blink(matches!(boo.peek()?, Tag::WParagraph))?;
+    Some(level)
+}
+
+pub fn paragraph_spacing<P: Peek<Item = Tag>>(boo: &P) -> Option<&Spacing> {
+    boo.reset();
+    let spacing = boo.peek()?.w_spacing()?;
+    blink(matches!(boo.peek()?, Tag::WParagraphPr))?;
+    blink(matches!(boo.peek()?, Tag::WParagraph))?;
+    Some(spacing)
+}
+
+pub fn paragraph_tab_stop<P: Peek<Item = Tag>>(boo: &P) -> Option<&String> {
+    boo.reset();
+    let leader = boo.peek()?.w_tab_stop()?;
+    blink(matches!(boo.peek()?, Tag::WTabs))?;
+    blink(matches!(boo.peek()?, Tag::WParagraphPr))?;
+    blink(matches!(boo.peek()?, Tag::WParagraph))?;
+    Some(leader)
+}
+
+pub fn table_width<P: Peek<Item = Tag>>(boo: &P) -> Option<&Width> {
+    boo.reset();
+    let width = boo.peek()?.w_table_width()?;
+    blink(matches!(boo.peek()?, Tag::WTablePr))?;
+    blink(matches!(boo.peek()?, Tag::WTable))?;
+    Some(width)
+}
+
+pub fn table_layout<P: Peek<Item = Tag>>(boo: &P) -> Option<&String> {
+    boo.reset();
+    let layout = boo.peek()?.w_table_layout()?;
+    blink(matches!(boo.peek()?, Tag::WTablePr))?;
+    blink(matches!(boo.peek()?, Tag::WTable))?;
+    Some(layout)
+}
+
+pub fn cell_width<P: Peek<Item = Tag>>(boo: &P) -> Option<&Width> {
+    boo.reset();
+    let width = boo.peek()?.w_table_cell_width()?;
+    blink(matches!(boo.peek()?, Tag::WTableCellPr))?;
+    blink(matches!(boo.peek()?, Tag::WTableCell))?;
+    Some(width)
+}
+
+pub fn cell_text_direction<P: Peek<Item = Tag>>(boo: &P) -> Option<&String> {
+    boo.reset();
+    let value = boo.peek()?.w_text_direction()?;
+    blink(matches!(boo.peek()?, Tag::WTableCellPr))?;
+    blink(matches!(boo.peek()?, Tag::WTableCell))?;
+    Some(value)
+}
+
+pub fn accent_element<P: Peek<Item = Tag>>(boo: &P) -> Option<()> {
+    boo.reset();
+    blink(matches!(boo.peek()?, Tag::MElement))?;
+    blink(matches!(boo.peek()?, Tag::MAcc))?;
+    Some(())
+}
+
+pub fn function_element<P: Peek<Item = Tag>>(boo: &P) -> Option<()> {
+    boo.reset();
+    blink(matches!(boo.peek()?, Tag::MElement))?;
+    blink(matches!(boo.peek()?, Tag::MFunc))?;
+    Some(())
+}
+
+pub fn nary_element<P: Peek<Item = Tag>>(boo: &P) -> Option<()> {
+    boo.reset();
+    blink(matches!(boo.peek()?, Tag::MElement))?;
+    blink(matches!(boo.peek()?, Tag::MNary))?;
+    Some(())
+}
+
+pub fn group_character_element<P: Peek<Item = Tag>>(boo: &P) -> Option<()> {
+    boo.reset();
+    blink(matches!(boo.peek()?, Tag::MElement))?;
+    blink(matches!(boo.peek()?, Tag::MGroupChr))?;
+    Some(())
+}
+
+pub fn bar_element<P: Peek<Item = Tag>>(boo: &P) -> Option<()> {
+    boo.reset();
+    blink(matches!(boo.peek()?, Tag::MElement))?;
+    blink(matches!(boo.peek()?, Tag::MBar))?;
+    Some(())
+}
+
+pub fn limit_element<P: Peek<Item = Tag>>(boo: &P) -> Option<()> {
+    boo.reset();
+    blink(matches!(boo.peek()?, Tag::MElement))?;
+    blink(matches!(boo.peek()?, Tag::MLimLow | Tag::MLimUpp))?;
+    Some(())
+}
+
+pub fn base_function_name<P: Peek<Item = Tag>>(boo: &P) -> Option<&String> {
+    boo.reset();
+    let content = boo.peek()?.content()?;
+    blink(matches!(boo.peek()?, Tag::MText))?;
+    blink(matches!(boo.peek()?, Tag::MRun))?;
+    blink(matches!(boo.peek()?, Tag::MElement))?;
+    blink(matches!(
+        boo.peek()?,
+        Tag::MLimLow | Tag::MLimUpp | Tag::MSubscript | Tag::MSuperscript | Tag::MSubSup
+    ))?;
+    blink(matches!(boo.peek()?, Tag::MFName))?;
+    Some(content)
+}
+
+pub fn delimiter_text<P: Peek<Item = Tag>>(boo: &P) -> Option<&String> {
+    boo.reset();
+    let content = boo.peek()?.content()?;
+    blink(matches!(boo.peek()?, Tag::MText))?;
+    blink(matches!(boo.peek()?, Tag::MRun))?;
+    blink(matches!(boo.peek()?, Tag::MElement))?;
+    blink(matches!(boo.peek()?, Tag::MDelim))?;
+    Some(content)
+}
+
+pub fn instr_text<P: Peek<Item = Tag>>(boo: &P) -> Option<&String> {
+    boo.reset();
+    let content = boo.peek()?.content()?;
+    blink(matches!(boo.peek()?, Tag::WInstrText))?;
+    blink(matches!(boo.peek()?, Tag::WRun))?;
+    Some(content)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -236,4 +473,705 @@ mod test {
         let content = math_text(&boo).unwrap();
         assert_eq!(content, "Content");
     }
+
+    #[test]
+    fn function_name_works() {
+        let mut boo = Boo::default();
+        assert!(function_name(&boo).is_none());
+
+        boo.push(Tag::MFName);
+        assert!(function_name(&boo).is_none());
+
+        boo.push(Tag::MText);
+        assert!(function_name(&boo).is_none());
+
+        boo.push(Tag::Content("sin".to_string()));
+        assert!(function_name(&boo).is_none());
+
+        boo.truncate(1);
+        boo.push(Tag::MRun);
+        boo.push(Tag::MText);
+        boo.push(Tag::Content("sin".to_string()));
+        assert!(function_name(&boo).is_some());
+
+        boo.reset();
+        assert!(function_name(&boo).is_some());
+
+        let content = function_name(&boo).unwrap();
+        assert_eq!(content, "sin");
+    }
+
+    #[test]
+    fn tab_works() {
+        let mut boo = Boo::default();
+        assert!(tab(&boo).is_none());
+
+        boo.push(Tag::WTab);
+        assert!(tab(&boo).is_none());
+
+        boo.pop();
+        boo.push(Tag::WRun);
+        assert!(tab(&boo).is_none());
+
+        boo.push(Tag::WTab);
+        assert!(tab(&boo).is_some());
+
+        boo.reset();
+        assert!(tab(&boo).is_some());
+    }
+
+    #[test]
+    fn run_color_works() {
+        let mut boo = Boo::default();
+        assert!(run_color(&boo).is_none());
+
+        boo.push(Tag::WRun);
+        assert!(run_color(&boo).is_none());
+
+        boo.push(Tag::WColor {
+            value: "FF0000".to_string(),
+        });
+        assert!(run_color(&boo).is_none());
+
+        boo.pop();
+        boo.push(Tag::WRunPr);
+        assert!(run_color(&boo).is_none());
+
+        boo.push(Tag::WColor {
+            value: "FF0000".to_string(),
+        });
+        assert!(run_color(&boo).is_some());
+
+        boo.reset();
+        assert!(run_color(&boo).is_some());
+
+        let value = run_color(&boo).unwrap();
+        assert_eq!(value, "FF0000");
+    }
+
+    #[test]
+    fn run_highlight_works() {
+        let mut boo = Boo::default();
+        assert!(run_highlight(&boo).is_none());
+
+        boo.push(Tag::WRun);
+        assert!(run_highlight(&boo).is_none());
+
+        boo.push(Tag::WHighlight {
+            value: "yellow".to_string(),
+        });
+        assert!(run_highlight(&boo).is_none());
+
+        boo.pop();
+        boo.push(Tag::WRunPr);
+        assert!(run_highlight(&boo).is_none());
+
+        boo.push(Tag::WHighlight {
+            value: "yellow".to_string(),
+        });
+        assert!(run_highlight(&boo).is_some());
+
+        boo.reset();
+        assert!(run_highlight(&boo).is_some());
+
+        let value = run_highlight(&boo).unwrap();
+        assert_eq!(value, "yellow");
+    }
+
+    #[test]
+    fn run_vert_align_works() {
+        let mut boo = Boo::default();
+        assert!(run_vert_align(&boo).is_none());
+
+        boo.push(Tag::WRun);
+        assert!(run_vert_align(&boo).is_none());
+
+        boo.push(Tag::WVertAlign {
+            value: "subscript".to_string(),
+        });
+        assert!(run_vert_align(&boo).is_none());
+
+        boo.pop();
+        boo.push(Tag::WRunPr);
+        assert!(run_vert_align(&boo).is_none());
+
+        boo.push(Tag::WVertAlign {
+            value: "subscript".to_string(),
+        });
+        assert!(run_vert_align(&boo).is_some());
+
+        boo.reset();
+        assert!(run_vert_align(&boo).is_some());
+
+        let value = run_vert_align(&boo).unwrap();
+        assert_eq!(value, "subscript");
+    }
+
+    #[test]
+    fn run_fonts_works() {
+        let mut boo = Boo::default();
+        assert!(run_fonts(&boo).is_none());
+
+        boo.push(Tag::WRun);
+        boo.push(Tag::WRunPr);
+        boo.push(Tag::WRunFonts { ascii: None });
+        assert!(run_fonts(&boo).is_none());
+
+        boo.pop();
+        boo.push(Tag::WRunFonts {
+            ascii: Some("Consolas".to_string()),
+        });
+        assert!(run_fonts(&boo).is_some());
+
+        let font = run_fonts(&boo).unwrap();
+        assert_eq!(font, "Consolas");
+    }
+
+    #[test]
+    fn run_style_works() {
+        let mut boo = Boo::default();
+        assert!(run_style(&boo).is_none());
+
+        boo.push(Tag::WRun);
+        boo.push(Tag::WRunStyle {
+            value: "FootnoteReference".to_string(),
+        });
+        assert!(run_style(&boo).is_none());
+
+        boo.pop();
+        boo.push(Tag::WRunPr);
+        boo.push(Tag::WRunStyle {
+            value: "FootnoteReference".to_string(),
+        });
+        assert_eq!(run_style(&boo), Some(&"FootnoteReference".to_string()));
+
+        boo.reset();
+        assert_eq!(run_style(&boo), Some(&"FootnoteReference".to_string()));
+    }
+
+    #[test]
+    fn paragraph_style_works() {
+        let mut boo = Boo::default();
+        assert!(paragraph_style(&boo).is_none());
+
+        boo.push(Tag::WParagraph);
+        assert!(paragraph_style(&boo).is_none());
+
+        boo.push(Tag::WParagraphStyle {
+            value: "Title".to_string(),
+        });
+        assert!(paragraph_style(&boo).is_none());
+
+        boo.pop();
+        boo.push(Tag::WParagraphPr);
+        assert!(paragraph_style(&boo).is_none());
+
+        boo.push(Tag::WParagraphStyle {
+            value: "Title".to_string(),
+        });
+        assert!(paragraph_style(&boo).is_some());
+
+        boo.reset();
+        assert!(paragraph_style(&boo).is_some());
+
+        let style = paragraph_style(&boo).unwrap();
+        assert_eq!(style, "Title");
+    }
+
+    #[test]
+    fn run_rtl_works() {
+        let mut boo = Boo::default();
+        assert!(run_rtl(&boo).is_none());
+
+        boo.push(Tag::WRun);
+        boo.push(Tag::WRtl(true));
+        assert!(run_rtl(&boo).is_none());
+
+        boo.pop();
+        boo.push(Tag::WRunPr);
+        boo.push(Tag::WRtl(false));
+        assert_eq!(run_rtl(&boo), Some(false));
+
+        boo.reset();
+        assert_eq!(run_rtl(&boo), Some(false));
+    }
+
+    #[test]
+    fn paragraph_suppress_line_numbers_works() {
+        let mut boo = Boo::default();
+        assert!(paragraph_suppress_line_numbers(&boo).is_none());
+
+        boo.push(Tag::WParagraph);
+        boo.push(Tag::WSuppressLineNumbers);
+        assert!(paragraph_suppress_line_numbers(&boo).is_none());
+
+        boo.pop();
+        boo.push(Tag::WParagraphPr);
+        boo.push(Tag::WSuppressLineNumbers);
+        assert!(paragraph_suppress_line_numbers(&boo).is_some());
+
+        boo.reset();
+        assert!(paragraph_suppress_line_numbers(&boo).is_some());
+    }
+
+    #[test]
+    fn paragraph_bidi_works() {
+        let mut boo = Boo::default();
+        assert!(paragraph_bidi(&boo).is_none());
+
+        boo.push(Tag::WParagraph);
+        assert!(paragraph_bidi(&boo).is_none());
+
+        boo.push(Tag::WBidi(true));
+        assert!(paragraph_bidi(&boo).is_none());
+
+        boo.pop();
+        boo.push(Tag::WParagraphPr);
+        assert!(paragraph_bidi(&boo).is_none());
+
+        boo.push(Tag::WBidi(true));
+        assert_eq!(paragraph_bidi(&boo), Some(true));
+
+        boo.reset();
+        assert_eq!(paragraph_bidi(&boo), Some(true));
+    }
+
+    #[test]
+    fn paragraph_level_works() {
+        let mut boo = Boo::default();
+        assert!(paragraph_level(&boo).is_none());
+
+        boo.push(Tag::WParagraph);
+        boo.push(Tag::WParagraphPr);
+        boo.push(Tag::WLevel {
+            value: "1".to_string(),
+        });
+        assert!(paragraph_level(&boo).is_none());
+
+        boo.pop();
+        boo.push(Tag::WNumPr);
+        boo.push(Tag::WLevel {
+            value: "1".to_string(),
+        });
+        assert_eq!(paragraph_level(&boo), Some(&"1".to_string()));
+
+        boo.reset();
+        assert_eq!(paragraph_level(&boo), Some(&"1".to_string()));
+    }
+
+    #[test]
+    fn paragraph_numbering_works() {
+        let mut boo = Boo::default();
+        assert!(paragraph_numbering(&boo).is_none());
+
+        boo.push(Tag::WParagraph);
+        boo.push(Tag::WParagraphPr);
+        boo.push(Tag::WNumId {
+            value: "3".to_string(),
+        });
+        assert!(paragraph_numbering(&boo).is_none());
+
+        boo.pop();
+        boo.push(Tag::WNumPr);
+        boo.push(Tag::WNumId {
+            value: "3".to_string(),
+        });
+        assert_eq!(paragraph_numbering(&boo), Some(&"3".to_string()));
+
+        boo.reset();
+        assert_eq!(paragraph_numbering(&boo), Some(&"3".to_string()));
+    }
+
+    #[test]
+    fn paragraph_outline_level_works() {
+        let mut boo = Boo::default();
+        assert!(paragraph_outline_level(&boo).is_none());
+
+        boo.push(Tag::WParagraph);
+        boo.push(Tag::WOutlineLevel {
+            value: "0".to_string(),
+        });
+        assert!(paragraph_outline_level(&boo).is_none());
+
+        boo.pop();
+        boo.push(Tag::WParagraphPr);
+        boo.push(Tag::WOutlineLevel {
+            value: "0".to_string(),
+        });
+        assert_eq!(paragraph_outline_level(&boo), Some(&"0".to_string()));
+
+        boo.reset();
+        assert_eq!(paragraph_outline_level(&boo), Some(&"0".to_string()));
+    }
+
+    #[test]
+    fn paragraph_spacing_works() {
+        let spacing = || {
+            Tag::WSpacing(Spacing {
+                line: Some("360".to_string()),
+                rule: None,
+            })
+        };
+        let mut boo = Boo::default();
+        assert!(paragraph_spacing(&boo).is_none());
+
+        boo.push(Tag::WParagraph);
+        assert!(paragraph_spacing(&boo).is_none());
+
+        boo.push(spacing());
+        assert!(paragraph_spacing(&boo).is_none());
+
+        boo.pop();
+        boo.push(Tag::WParagraphPr);
+        assert!(paragraph_spacing(&boo).is_none());
+
+        boo.push(spacing());
+        assert!(paragraph_spacing(&boo).is_some());
+
+        boo.reset();
+        assert!(paragraph_spacing(&boo).is_some());
+
+        let value = paragraph_spacing(&boo).unwrap();
+        assert_eq!(value.line.as_deref(), Some("360"));
+    }
+
+    #[test]
+    fn paragraph_tab_stop_works() {
+        let tab_stop = || Tag::WTabStop {
+            leader: "dot".to_string(),
+        };
+        let mut boo = Boo::default();
+        assert!(paragraph_tab_stop(&boo).is_none());
+
+        boo.push(Tag::WParagraph);
+        assert!(paragraph_tab_stop(&boo).is_none());
+
+        boo.push(Tag::WParagraphPr);
+        assert!(paragraph_tab_stop(&boo).is_none());
+
+        boo.push(tab_stop());
+        assert!(paragraph_tab_stop(&boo).is_none());
+
+        boo.pop();
+        boo.push(Tag::WTabs);
+        assert!(paragraph_tab_stop(&boo).is_none());
+
+        boo.push(tab_stop());
+        assert!(paragraph_tab_stop(&boo).is_some());
+
+        boo.reset();
+        assert!(paragraph_tab_stop(&boo).is_some());
+
+        let leader = paragraph_tab_stop(&boo).unwrap();
+        assert_eq!(leader, "dot");
+    }
+
+    #[test]
+    fn table_width_works() {
+        let width = || {
+            Tag::WTableWidth(Width {
+                value: Some("5000".to_string()),
+                kind: Some("pct".to_string()),
+            })
+        };
+        let mut boo = Boo::default();
+        assert!(table_width(&boo).is_none());
+
+        boo.push(Tag::WTable);
+        assert!(table_width(&boo).is_none());
+
+        boo.push(width());
+        assert!(table_width(&boo).is_none());
+
+        boo.pop();
+        boo.push(Tag::WTablePr);
+        assert!(table_width(&boo).is_none());
+
+        boo.push(width());
+        assert!(table_width(&boo).is_some());
+
+        boo.reset();
+        let value = table_width(&boo).unwrap();
+        assert_eq!(value.value.as_deref(), Some("5000"));
+    }
+
+    #[test]
+    fn cell_width_works() {
+        let width = || {
+            Tag::WTableCellWidth(Width {
+                value: Some("1500".to_string()),
+                kind: Some("pct".to_string()),
+            })
+        };
+        let mut boo = Boo::default();
+        assert!(cell_width(&boo).is_none());
+
+        boo.push(Tag::WTableCell);
+        boo.push(width());
+        assert!(cell_width(&boo).is_none());
+
+        boo.pop();
+        boo.push(Tag::WTableCellPr);
+        boo.push(width());
+        assert!(cell_width(&boo).is_some());
+
+        boo.reset();
+        let value = cell_width(&boo).unwrap();
+        assert_eq!(value.value.as_deref(), Some("1500"));
+    }
+
+    #[test]
+    fn table_layout_works() {
+        let mut boo = Boo::default();
+        assert!(table_layout(&boo).is_none());
+
+        boo.push(Tag::WTable);
+        boo.push(Tag::WTableLayout {
+            value: "fixed".to_string(),
+        });
+        assert!(table_layout(&boo).is_none());
+
+        boo.pop();
+        boo.push(Tag::WTablePr);
+        boo.push(Tag::WTableLayout {
+            value: "fixed".to_string(),
+        });
+        assert_eq!(table_layout(&boo), Some(&"fixed".to_string()));
+
+        boo.reset();
+        assert_eq!(table_layout(&boo), Some(&"fixed".to_string()));
+    }
+
+    #[test]
+    fn cell_text_direction_works() {
+        let mut boo = Boo::default();
+        assert!(cell_text_direction(&boo).is_none());
+
+        boo.push(Tag::WTableCell);
+        assert!(cell_text_direction(&boo).is_none());
+
+        boo.push(Tag::WTextDirection {
+            value: "btLr".to_string(),
+        });
+        assert!(cell_text_direction(&boo).is_none());
+
+        boo.pop();
+        boo.push(Tag::WTableCellPr);
+        assert!(cell_text_direction(&boo).is_none());
+
+        boo.push(Tag::WTextDirection {
+            value: "btLr".to_string(),
+        });
+        assert!(cell_text_direction(&boo).is_some());
+
+        boo.reset();
+        assert!(cell_text_direction(&boo).is_some());
+
+        let value = cell_text_direction(&boo).unwrap();
+        assert_eq!(value, "btLr");
+    }
+
+    #[test]
+    fn accent_element_works() {
+        let mut boo = Boo::default();
+        assert!(accent_element(&boo).is_none());
+
+        boo.push(Tag::MElement);
+        assert!(accent_element(&boo).is_none());
+
+        boo.pop();
+        boo.push(Tag::MAcc);
+        assert!(accent_element(&boo).is_none());
+
+        boo.push(Tag::MElement);
+        assert!(accent_element(&boo).is_some());
+
+        boo.reset();
+        assert!(accent_element(&boo).is_some());
+    }
+
+    #[test]
+    fn function_element_works() {
+        let mut boo = Boo::default();
+        assert!(function_element(&boo).is_none());
+
+        boo.push(Tag::MElement);
+        assert!(function_element(&boo).is_none());
+
+        boo.pop();
+        boo.push(Tag::MFunc);
+        assert!(function_element(&boo).is_none());
+
+        boo.push(Tag::MElement);
+        assert!(function_element(&boo).is_some());
+
+        boo.reset();
+        assert!(function_element(&boo).is_some());
+    }
+
+    #[test]
+    fn nary_element_works() {
+        let mut boo = Boo::default();
+        assert!(nary_element(&boo).is_none());
+
+        boo.push(Tag::MElement);
+        assert!(nary_element(&boo).is_none());
+
+        boo.pop();
+        boo.push(Tag::MNary);
+        assert!(nary_element(&boo).is_none());
+
+        boo.push(Tag::MElement);
+        assert!(nary_element(&boo).is_some());
+
+        boo.reset();
+        assert!(nary_element(&boo).is_some());
+    }
+
+    #[test]
+    fn group_character_element_works() {
+        let mut boo = Boo::default();
+        assert!(group_character_element(&boo).is_none());
+
+        boo.push(Tag::MElement);
+        assert!(group_character_element(&boo).is_none());
+
+        boo.pop();
+        boo.push(Tag::MGroupChr);
+        assert!(group_character_element(&boo).is_none());
+
+        boo.push(Tag::MElement);
+        assert!(group_character_element(&boo).is_some());
+
+        boo.reset();
+        assert!(group_character_element(&boo).is_some());
+    }
+
+    #[test]
+    fn bar_element_works() {
+        let mut boo = Boo::default();
+        assert!(bar_element(&boo).is_none());
+
+        boo.push(Tag::MElement);
+        assert!(bar_element(&boo).is_none());
+
+        boo.pop();
+        boo.push(Tag::MBar);
+        assert!(bar_element(&boo).is_none());
+
+        boo.push(Tag::MElement);
+        assert!(bar_element(&boo).is_some());
+
+        boo.reset();
+        assert!(bar_element(&boo).is_some());
+    }
+
+    #[test]
+    fn limit_element_works() {
+        let mut boo = Boo::default();
+        assert!(limit_element(&boo).is_none());
+
+        boo.push(Tag::MElement);
+        assert!(limit_element(&boo).is_none());
+
+        boo.pop();
+        boo.push(Tag::MLimLow);
+        assert!(limit_element(&boo).is_none());
+
+        boo.push(Tag::MElement);
+        assert!(limit_element(&boo).is_some());
+
+        boo.pop();
+        boo.pop();
+        boo.push(Tag::MLimUpp);
+        boo.push(Tag::MElement);
+        assert!(limit_element(&boo).is_some());
+
+        boo.reset();
+        assert!(limit_element(&boo).is_some());
+    }
+
+    #[test]
+    fn base_function_name_works() {
+        let mut boo = Boo::default();
+        assert!(base_function_name(&boo).is_none());
+
+        boo.push(Tag::MLimLow);
+        boo.push(Tag::MElement);
+        boo.push(Tag::MRun);
+        boo.push(Tag::MText);
+        boo.push(Tag::Content("lim".to_string()));
+        assert!(base_function_name(&boo).is_none());
+
+        let mut boo = Boo::default();
+        boo.push(Tag::MFName);
+        boo.push(Tag::MLimLow);
+        boo.push(Tag::MElement);
+        boo.push(Tag::MRun);
+        boo.push(Tag::MText);
+        boo.push(Tag::Content("lim".to_string()));
+        assert!(base_function_name(&boo).is_some());
+
+        boo.reset();
+        let name = base_function_name(&boo).unwrap();
+        assert_eq!(name, "lim");
+
+        let mut boo = Boo::default();
+        boo.push(Tag::MFName);
+        boo.push(Tag::MSuperscript);
+        boo.push(Tag::MElement);
+        boo.push(Tag::MRun);
+        boo.push(Tag::MText);
+        boo.push(Tag::Content("sin".to_string()));
+        assert!(base_function_name(&boo).is_some());
+    }
+
+    #[test]
+    fn delimiter_text_works() {
+        let mut boo = Boo::default();
+        assert!(delimiter_text(&boo).is_none());
+
+        boo.push(Tag::MElement);
+        boo.push(Tag::MRun);
+        boo.push(Tag::MText);
+        boo.push(Tag::Content("|".to_string()));
+        assert!(delimiter_text(&boo).is_none());
+
+        let mut boo = Boo::default();
+        boo.push(Tag::MDelim);
+        boo.push(Tag::MElement);
+        boo.push(Tag::MRun);
+        boo.push(Tag::MText);
+        boo.push(Tag::Content("|".to_string()));
+        assert!(delimiter_text(&boo).is_some());
+
+        boo.reset();
+        let content = delimiter_text(&boo).unwrap();
+        assert_eq!(content, "|");
+    }
+
+    #[test]
+    fn instr_text_works() {
+        let mut boo = Boo::default();
+        assert!(instr_text(&boo).is_none());
+
+        boo.push(Tag::WRun);
+        assert!(instr_text(&boo).is_none());
+
+        boo.push(Tag::Content("CITATION".to_string()));
+        assert!(instr_text(&boo).is_none());
+
+        boo.pop();
+        boo.push(Tag::WInstrText);
+        assert!(instr_text(&boo).is_none());
+
+        boo.push(Tag::Content("CITATION".to_string()));
+        assert!(instr_text(&boo).is_some());
+
+        boo.reset();
+        assert!(instr_text(&boo).is_some());
+
+        let content = instr_text(&boo).unwrap();
+        assert_eq!(content, "CITATION");
+    }
 }
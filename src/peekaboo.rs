@@ -20,6 +20,10 @@ pub trait Peek {
     fn peeked(&self) -> usize;
     fn incr(&self);
     fn reset(&self);
+    /// Steps back by a single element, so that `peek` returns it again.
+    /// Unlike `reset`, the elements peeked before it stay behind.
+    #[allow(dead_code)]
+    fn unpeek(&self);
     fn len(&self) -> usize;
     fn get(&self, index: usize) -> Option<&Self::Item>;
     // provided
@@ -40,6 +44,16 @@ pub trait Peek {
             self.get(self.len() - peeked - 1)
         }
     }
+
+    /// Looks at the element `n` positions below the top, without moving on to it like `peek` does
+    #[allow(dead_code)]
+    fn peek_n(&self, n: usize) -> Option<&Self::Item> {
+        if n >= self.len() {
+            None
+        } else {
+            self.get(self.len() - n - 1)
+        }
+    }
 }
 
 impl<T> Peek for Boo<T> {
@@ -57,6 +71,10 @@ impl<T> Peek for Boo<T> {
         self.peeked.set(0);
     }
 
+    fn unpeek(&self) {
+        self.peeked.set(self.peeked().saturating_sub(1));
+    }
+
     fn get(&self, index: usize) -> Option<&T> {
         self.vec.get(index)
     }
@@ -66,6 +84,14 @@ impl<T> Peek for Boo<T> {
     }
 }
 
+impl<T> Boo<T> {
+    /// Iterates from the most recently pushed element to the oldest one, leaving the `peek` cursor alone
+    #[allow(dead_code)]
+    pub fn top_down(&self) -> std::iter::Rev<std::slice::Iter<'_, T>> {
+        self.vec.iter().rev()
+    }
+}
+
 impl<T> Default for Boo<T> {
     fn default() -> Boo<T> {
         Boo {
@@ -147,6 +173,17 @@ mod test {
         assert_eq!(format!("{vec:?}"), format!("{boo:?}"));
     }
 
+    #[test]
+    fn top_down_reverses_vec() {
+        let vec = vec![0, 1, 2, 3];
+        let boo = Boo::from(vec.clone());
+
+        assert_eq!(boo.peek(), Some(&3));
+        let top_down: Vec<_> = boo.top_down().copied().collect();
+        assert_eq!(top_down, vec.into_iter().rev().collect::<Vec<_>>());
+        assert_eq!(boo.peeked(), 1);
+    }
+
     #[test]
     fn peek_persists() {
         let boo = Boo::from(vec![0, 1, 2, 3, 4]);
@@ -184,6 +221,79 @@ mod test {
         assert_eq!(boo.peeked(), 6);
     }
 
+    #[test]
+    fn peek_n_persists() {
+        let boo = Boo::from(vec![0, 1, 2, 3, 4]);
+
+        assert_eq!(boo.peek_n(0), Some(&4));
+        assert_eq!(boo.peeked(), 0);
+
+        assert_eq!(boo.peek_n(2), Some(&2));
+        assert_eq!(boo.peeked(), 0);
+
+        assert_eq!(boo.peek_n(4), Some(&0));
+        assert_eq!(boo.peek_n(5), None);
+        assert_eq!(boo.peeked(), 0);
+    }
+
+    #[test]
+    fn peek_n_leaves_peek_alone() {
+        let boo = Boo::from(vec![0, 1, 2, 3, 4]);
+
+        assert_eq!(boo.peek(), Some(&4));
+        assert_eq!(boo.peek_n(3), Some(&1));
+        assert_eq!(boo.peeked(), 1);
+
+        assert_eq!(boo.peek(), Some(&3));
+        assert_eq!(boo.peeked(), 2);
+    }
+
+    #[test]
+    fn unpeek_steps_back() {
+        let boo = Boo::from(vec![0, 1, 2]);
+
+        assert_eq!(boo.peek(), Some(&2));
+        assert_eq!(boo.peek(), Some(&1));
+        boo.unpeek();
+        assert_eq!(boo.peeked(), 1);
+
+        assert_eq!(boo.peek(), Some(&1));
+        assert_eq!(boo.peeked(), 2);
+    }
+
+    #[test]
+    fn unpeek_stops_at_zero() {
+        let boo = Boo::from(vec![0, 1, 2]);
+
+        boo.unpeek();
+        assert_eq!(boo.peeked(), 0);
+
+        assert_eq!(boo.peek(), Some(&2));
+        boo.unpeek();
+        boo.unpeek();
+        assert_eq!(boo.peeked(), 0);
+        assert_eq!(boo.peek(), Some(&2));
+    }
+
+    #[test]
+    fn unpeek_after_exhaustion() {
+        let boo = Boo::from(vec![0]);
+
+        assert_eq!(boo.peek(), Some(&0));
+        assert_eq!(boo.peek(), None);
+        assert_eq!(boo.peek(), None);
+        assert_eq!(boo.peeked(), 3);
+
+        // every peek past the bottom has to be stepped back from
+        boo.unpeek();
+        assert_eq!(boo.peek(), None);
+
+        boo.unpeek();
+        boo.unpeek();
+        boo.unpeek();
+        assert_eq!(boo.peek(), Some(&0));
+    }
+
     #[test]
     fn reset_works() {
         let boo = Boo::from(vec![0]);
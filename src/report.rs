@@ -0,0 +1,65 @@
+/// How much a problem takes away from the output
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// Part of the document is written in a simpler form than in Word
+    Warning,
+    /// Part of the document is missing from the output
+    Error,
+}
+
+/// What went wrong at a tag
+#[derive(Clone, Debug, PartialEq)]
+pub enum DiagnosticKind {
+    /// The tag lacks attributes that its conversion needs
+    MissingAttributes(Vec<&'static str>),
+    /// The relationship that the tag refers to is missing or unusable
+    MissingRelationship(String),
+    /// `word/footnotes.xml` has no note with the id that the tag refers to
+    MissingFootnote(String),
+    /// A math element that equations are written without
+    UnsupportedMath,
+    /// The part stops being well-formed XML, so the rest of it is lost
+    MalformedXml(String),
+    /// A field whose code lacks what its conversion needs, such as the bookmark of a REF
+    IncompleteField(String),
+    /// The tag ends something that was never begun, such as a field or an equation
+    Unbalanced,
+}
+
+/// A problem met while converting a document
+#[derive(Clone, Debug, PartialEq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub kind: DiagnosticKind,
+    /// Id of the tag the problem was found at, such as "a:blip"
+    pub tag: String,
+}
+
+/// Everything that kept a conversion from being faithful, in the order it was found
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ConversionReport {
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl ConversionReport {
+    /// Nothing of the document was lost or simplified
+    pub fn is_clean(&self) -> bool {
+        self.diagnostics.is_empty()
+    }
+
+    /// Number of diagnostics of the given severity
+    pub fn count(&self, severity: Severity) -> usize {
+        self.diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.severity == severity)
+            .count()
+    }
+
+    pub(crate) fn push(&mut self, severity: Severity, kind: DiagnosticKind, tag: &str) {
+        self.diagnostics.push(Diagnostic {
+            severity,
+            kind,
+            tag: tag.to_string(),
+        });
+    }
+}
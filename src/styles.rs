@@ -0,0 +1,209 @@
+use std::{
+    collections::HashMap,
+    io::{BufReader, Read},
+};
+
+use xml::reader::{EventReader, XmlEvent};
+
+use crate::tag::normalize;
+
+/// Value of `w:styleId`, which paragraphs and runs refer to
+pub type StyleId = String;
+
+/// A style from `word/styles.xml`, holding the properties it inherits through `w:basedOn`
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Style {
+    /// Value of `w:name`, such as "heading 1"
+    pub name: Option<String>,
+    pub based_on: Option<StyleId>,
+    pub paragraph: ParagraphStyle,
+    pub character: CharacterStyle,
+}
+
+/// Properties from the `w:pPr` of a style
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ParagraphStyle {
+    /// Value of `w:outlineLvl`, where 0 is the level of a top heading
+    pub outline_level: Option<usize>,
+    pub bidi: Option<bool>,
+}
+
+/// Properties from the `w:rPr` of a style
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CharacterStyle {
+    /// Hex RGB value or "auto"
+    pub color: Option<String>,
+    pub highlight: Option<String>,
+    pub vert_align: Option<String>,
+}
+
+impl Style {
+    /// Fills every property that is not set with the one of `parent`
+    fn inherit(&mut self, parent: &Style) {
+        let paragraph = &mut self.paragraph;
+        paragraph.outline_level = paragraph.outline_level.or(parent.paragraph.outline_level);
+        paragraph.bidi = paragraph.bidi.or(parent.paragraph.bidi);
+        let character = &mut self.character;
+        for (property, inherited) in [
+            (&mut character.color, &parent.character.color),
+            (&mut character.highlight, &parent.character.highlight),
+            (&mut character.vert_align, &parent.character.vert_align),
+        ] {
+            if property.is_none() {
+                property.clone_from(inherited);
+            }
+        }
+    }
+}
+
+/// Reads `word/styles.xml`, resolving every style with the properties of the styles it is based on
+pub fn styles<R: Read>(
+    parser: &mut EventReader<BufReader<R>>,
+) -> Result<HashMap<StyleId, Style>, xml::reader::Error> {
+    let mut declared = HashMap::<StyleId, Style>::default();
+    let mut current: Option<(StyleId, Style)> = None;
+    loop {
+        match parser.next() {
+            Ok(XmlEvent::StartElement {
+                name, attributes, ..
+            }) => {
+                let find = |name: &str| {
+                    attributes
+                        .iter()
+                        .find(|&a| normalize(&a.name) == name)
+                        .map(|a| a.value.clone())
+                };
+                let name = normalize(&name);
+                if name == "w:style" {
+                    current = find("w:styleId").map(|id| (id, Style::default()));
+                    continue;
+                }
+                let Some((_, style)) = current.as_mut() else {
+                    continue;
+                };
+                match name.as_str() {
+                    "w:name" => style.name = find("w:val"),
+                    "w:basedOn" => style.based_on = find("w:val"),
+                    "w:outlineLvl" => {
+                        style.paragraph.outline_level =
+                            find("w:val").and_then(|level| level.parse().ok())
+                    }
+                    "w:bidi" => {
+                        style.paragraph.bidi = Some(!matches!(
+                            find("w:val").as_deref(),
+                            Some("0" | "false" | "off")
+                        ))
+                    }
+                    "w:color" => style.character.color = find("w:val"),
+                    "w:highlight" => style.character.highlight = find("w:val"),
+                    "w:vertAlign" => style.character.vert_align = find("w:val"),
+                    _ => {}
+                }
+            }
+            Ok(XmlEvent::EndElement { name }) if normalize(&name) == "w:style" => {
+                if let Some((id, style)) = current.take() {
+                    declared.insert(id, style);
+                }
+            }
+            Ok(XmlEvent::EndDocument) => break,
+            Ok(_) => continue,
+            Err(e) => return Err(e),
+        }
+    }
+
+    let mut resolved = HashMap::with_capacity(declared.len());
+    for (id, style) in &declared {
+        let mut parent = style.based_on.as_ref();
+        let mut style = style.clone();
+        let mut ancestors = vec![id];
+        while let Some(parent_id) = parent {
+            if ancestors.contains(&parent_id) {
+                log::error!("Style {id:?} is based on itself through {parent_id:?}");
+                break;
+            }
+            let Some(parent_style) = declared.get(parent_id) else {
+                log::warn!("Style {id:?} is based on a missing style {parent_id:?}");
+                break;
+            };
+            style.inherit(parent_style);
+            ancestors.push(parent_id);
+            parent = parent_style.based_on.as_ref();
+        }
+        resolved.insert(id.clone(), style);
+    }
+    Ok(resolved)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn parse(raw: &str) -> HashMap<StyleId, Style> {
+        let mut parser = EventReader::new(BufReader::new(raw.as_bytes()));
+        styles(&mut parser).unwrap()
+    }
+
+    #[test]
+    fn styles_resolve_based_on_chain() {
+        let styles = parse(
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:styles xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">
+    <w:style w:type="paragraph" w:styleId="Normal">
+        <w:name w:val="Normal"/>
+        <w:pPr><w:bidi/></w:pPr>
+        <w:rPr><w:color w:val="000000"/></w:rPr>
+    </w:style>
+    <w:style w:type="paragraph" w:styleId="Heading1">
+        <w:name w:val="heading 1"/>
+        <w:basedOn w:val="Normal"/>
+        <w:pPr><w:outlineLvl w:val="0"/></w:pPr>
+        <w:rPr><w:color w:val="2F5496"/></w:rPr>
+    </w:style>
+    <w:style w:type="paragraph" w:styleId="Heading1Red">
+        <w:name w:val="heading 1 red"/>
+        <w:basedOn w:val="Heading1"/>
+        <w:rPr><w:color w:val="FF0000"/><w:highlight w:val="yellow"/></w:rPr>
+    </w:style>
+</w:styles>
+"#,
+        );
+        assert_eq!(styles.len(), 3);
+
+        let heading = styles.get("Heading1").unwrap();
+        assert_eq!(heading.name.as_deref(), Some("heading 1"));
+        assert_eq!(heading.paragraph.outline_level, Some(0));
+        assert_eq!(heading.paragraph.bidi, Some(true));
+        assert_eq!(heading.character.color.as_deref(), Some("2F5496"));
+
+        let red = styles.get("Heading1Red").unwrap();
+        assert_eq!(red.based_on.as_deref(), Some("Heading1"));
+        assert_eq!(red.paragraph.outline_level, Some(0));
+        assert_eq!(red.paragraph.bidi, Some(true));
+        assert_eq!(red.character.color.as_deref(), Some("FF0000"));
+        assert_eq!(red.character.highlight.as_deref(), Some("yellow"));
+        assert_eq!(red.character.vert_align, None);
+    }
+
+    #[test]
+    fn styles_survive_cyclic_and_missing_parents() {
+        let styles = parse(
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:styles xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">
+    <w:style w:type="character" w:styleId="A"><w:basedOn w:val="B"/><w:rPr><w:vertAlign w:val="superscript"/></w:rPr></w:style>
+    <w:style w:type="character" w:styleId="B"><w:basedOn w:val="A"/><w:rPr><w:color w:val="00FF00"/></w:rPr></w:style>
+    <w:style w:type="character" w:styleId="C"><w:basedOn w:val="Missing"/></w:style>
+</w:styles>
+"#,
+        );
+        let a = styles.get("A").unwrap();
+        assert_eq!(a.character.vert_align.as_deref(), Some("superscript"));
+        assert_eq!(a.character.color.as_deref(), Some("00FF00"));
+        assert_eq!(
+            styles.get("C").unwrap(),
+            &Style {
+                based_on: Some("Missing".to_string()),
+                ..Default::default()
+            }
+        );
+    }
+}
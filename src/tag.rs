@@ -9,32 +9,174 @@ pub enum Tag {
     MoMathPara,
     MoMath,
     MDelim,
+    MDelimPr,
     MRad,
     MDeg,
     MRun,
     MText,
     MSub,
     MSup,
+    MSubscript,
+    MSuperscript,
+    MSubSup,
+    MSubSupPr,
+    MPre,
+    MPrePr,
     MNary,
     MNaryPr,
     MFraction,
+    MFractionPr,
     MFunc,
     MFName,
     MNum,
     MDen,
+    MCtrlPr,
+    MMatrix,
+    MMatrixRow,
+    MElement,
+    MAcc,
+    MAccPr,
+    MBar,
+    MBarPr,
+    MGroupChr,
+    MGroupChrPr,
+    MLimLow,
+    MLimUpp,
+    MLim,
     WPInline,
     WPAnchor,
     WBookmarkEnd,
+    WBreak,
     WDrawing,
+    WInstrText,
     WParagraph,
+    WParagraphPr,
     WRun,
+    WRunPr,
+    WTab,
+    WTabs,
     WText,
-    ABlip { rel: String },
-    MChr { value: String },
-    WBookmarkStart { anchor: String },
+    WTable,
+    WTablePr,
+    WTableGrid,
+    WTableRow,
+    WTableCell,
+    WTableCellPr,
+    WSdt,
+    WSdtPr,
+    WSdtContent,
+    WNumPr,
+    WFootnoteRef,
+    WSectionPr,
+    WTitlePg,
+    WSuppressLineNumbers,
+    WDocument,
+    WBody,
+    WFootnotes,
+    WSeparator,
+    WContinuationSeparator,
+    ABlip {
+        rel: String,
+    },
+    AsvgSvgBlip {
+        rel: String,
+    },
+    MChr {
+        value: String,
+    },
+    MPos {
+        value: String,
+    },
+    MSepChr {
+        value: String,
+    },
+    MBegChr {
+        value: String,
+    },
+    MEndChr {
+        value: String,
+    },
+    MType {
+        value: String,
+    },
+    WBookmarkStart {
+        anchor: String,
+    },
+    WTextDirection {
+        value: String,
+    },
+    WColor {
+        value: String,
+    },
+    WHighlight {
+        value: String,
+    },
+    WVertAlign {
+        value: String,
+    },
+    WParagraphStyle {
+        value: String,
+    },
+    WLevel {
+        value: String,
+    },
+    WNumId {
+        value: String,
+    },
+    WOutlineLevel {
+        value: String,
+    },
+    WTableLayout {
+        value: String,
+    },
+    WFootnote {
+        id: String,
+    },
+    WFootnoteReference {
+        id: String,
+    },
+    WRunStyle {
+        value: String,
+    },
+    WTabStop {
+        leader: String,
+    },
     WHyperlink(Link),
+    WFieldSimple {
+        instr: String,
+    },
+    WPDocPr {
+        description: Option<String>,
+    },
+    WPExtent {
+        width: Option<String>,
+    },
+    WRunFonts {
+        ascii: Option<String>,
+    },
+    WFieldChar(FieldChar),
+    WSpacing(Spacing),
+    WGridColumn(Width),
+    WTableWidth(Width),
+    WTableCellWidth(Width),
+    WBidi(bool),
+    WRtl(bool),
+    WPageSize {
+        landscape: bool,
+        width: Option<u64>,
+    },
+    WPageMargins {
+        left: Option<u64>,
+        right: Option<u64>,
+    },
+    WLineNumbers {
+        count_by: Option<u64>,
+        restart: Option<String>,
+    },
     Content(String),
-    Unknown { id: String },
+    Unknown {
+        id: String,
+    },
 }
 
 #[derive(Debug, PartialEq)]
@@ -43,6 +185,55 @@ pub enum Link {
     Relationship(String),
 }
 
+#[derive(Debug, PartialEq)]
+pub enum FieldChar {
+    Begin,
+    Separate,
+    End,
+}
+
+/// Width of a table or of its column, from `w:w` and `w:type`
+#[derive(Debug, Default, PartialEq)]
+pub struct Width {
+    /// Twentieths of a point, or fiftieths of a percent when the type is "pct"
+    pub value: Option<String>,
+    /// One of "dxa", "pct", "auto" or "nil"
+    pub kind: Option<String>,
+}
+
+impl Width {
+    fn attributes(&self) -> Vec<OwnedAttribute> {
+        self.value
+            .iter()
+            .map(|value| owned_attr("w", "w", value))
+            .chain(self.kind.iter().map(|kind| owned_attr("w", "type", kind)))
+            .collect()
+    }
+}
+
+impl From<&Vec<OwnedAttribute>> for Width {
+    fn from(atts: &Vec<OwnedAttribute>) -> Self {
+        let find = |name| {
+            atts.iter()
+                .find(|&a| normalize(&a.name) == name)
+                .map(|a| a.value.clone())
+        };
+        Width {
+            value: find("w:w"),
+            kind: find("w:type"),
+        }
+    }
+}
+
+/// Line spacing of a paragraph, other `w:spacing` attributes are not used
+#[derive(Debug, Default, PartialEq)]
+pub struct Spacing {
+    /// Twentieths of a point, or 240ths of a line when the rule is "auto"
+    pub line: Option<String>,
+    /// One of "auto", "exact" or "atLeast"
+    pub rule: Option<String>,
+}
+
 impl Tag {
     pub fn a_blip(&self) -> Option<&String> {
         if let Tag::ABlip { rel } = self {
@@ -52,6 +243,15 @@ impl Tag {
         }
     }
 
+    #[allow(dead_code)]
+    pub fn asvg_svg_blip(&self) -> Option<&String> {
+        if let Tag::AsvgSvgBlip { rel } = self {
+            Some(rel)
+        } else {
+            None
+        }
+    }
+
     #[allow(dead_code)]
     pub fn m_chr(&self) -> Option<&String> {
         if let Tag::MChr { value } = self {
@@ -70,6 +270,206 @@ impl Tag {
         }
     }
 
+    pub fn w_text_direction(&self) -> Option<&String> {
+        if let Tag::WTextDirection { value } = self {
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    pub fn w_color(&self) -> Option<&String> {
+        if let Tag::WColor { value } = self {
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    pub fn w_highlight(&self) -> Option<&String> {
+        if let Tag::WHighlight { value } = self {
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    pub fn w_vert_align(&self) -> Option<&String> {
+        if let Tag::WVertAlign { value } = self {
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    /// Only fonts that are named outright, rather than through the theme
+    pub fn w_run_fonts(&self) -> Option<&String> {
+        if let Tag::WRunFonts { ascii: Some(ascii) } = self {
+            Some(ascii)
+        } else {
+            None
+        }
+    }
+
+    pub fn w_table_width(&self) -> Option<&Width> {
+        if let Tag::WTableWidth(value) = self {
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    pub fn w_table_cell_width(&self) -> Option<&Width> {
+        if let Tag::WTableCellWidth(value) = self {
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    pub fn w_spacing(&self) -> Option<&Spacing> {
+        if let Tag::WSpacing(value) = self {
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    pub fn w_bidi(&self) -> Option<bool> {
+        if let Tag::WBidi(value) = self {
+            Some(*value)
+        } else {
+            None
+        }
+    }
+
+    pub fn w_rtl(&self) -> Option<bool> {
+        if let Tag::WRtl(value) = self {
+            Some(*value)
+        } else {
+            None
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn m_pos(&self) -> Option<&String> {
+        if let Tag::MPos { value } = self {
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    pub fn w_tab_stop(&self) -> Option<&String> {
+        if let Tag::WTabStop { leader } = self {
+            Some(leader)
+        } else {
+            None
+        }
+    }
+
+    pub fn w_paragraph_style(&self) -> Option<&String> {
+        if let Tag::WParagraphStyle { value } = self {
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn m_sep_chr(&self) -> Option<&String> {
+        if let Tag::MSepChr { value } = self {
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn m_beg_chr(&self) -> Option<&String> {
+        if let Tag::MBegChr { value } = self {
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn m_end_chr(&self) -> Option<&String> {
+        if let Tag::MEndChr { value } = self {
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn m_type(&self) -> Option<&String> {
+        if let Tag::MType { value } = self {
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    pub fn w_level(&self) -> Option<&String> {
+        if let Tag::WLevel { value } = self {
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    pub fn w_num_id(&self) -> Option<&String> {
+        if let Tag::WNumId { value } = self {
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    pub fn w_run_style(&self) -> Option<&String> {
+        if let Tag::WRunStyle { value } = self {
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn w_footnote_reference(&self) -> Option<&String> {
+        if let Tag::WFootnoteReference { id } = self {
+            Some(id)
+        } else {
+            None
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn w_footnote(&self) -> Option<&String> {
+        if let Tag::WFootnote { id } = self {
+            Some(id)
+        } else {
+            None
+        }
+    }
+
+    pub fn w_table_layout(&self) -> Option<&String> {
+        if let Tag::WTableLayout { value } = self {
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    pub fn w_outline_level(&self) -> Option<&String> {
+        if let Tag::WOutlineLevel { value } = self {
+            Some(value)
+        } else {
+            None
+        }
+    }
+
     pub fn w_hyperlink(&self) -> Option<&Link> {
         if let Tag::WHyperlink(value) = self {
             Some(value)
@@ -98,32 +498,145 @@ impl Tag {
             MoMathPara => (owned_name("m", "oMathPara"), vec![]),
             MoMath => (owned_name("m", "oMath"), vec![]),
             MDelim => (owned_name("m", "d"), vec![]),
+            MDelimPr => (owned_name("m", "dPr"), vec![]),
             MRad => (owned_name("m", "rad"), vec![]),
             MDeg => (owned_name("m", "deg"), vec![]),
             MRun => (owned_name("m", "r"), vec![]),
             MText => (owned_name("m", "t"), vec![]),
             MSub => (owned_name("m", "sub"), vec![]),
             MSup => (owned_name("m", "sup"), vec![]),
+            MSubscript => (owned_name("m", "sSub"), vec![]),
+            MSuperscript => (owned_name("m", "sSup"), vec![]),
+            MSubSup => (owned_name("m", "sSubSup"), vec![]),
+            MSubSupPr => (owned_name("m", "sSubSupPr"), vec![]),
+            MPre => (owned_name("m", "sPre"), vec![]),
+            MPrePr => (owned_name("m", "sPrePr"), vec![]),
             MNary => (owned_name("m", "nary"), vec![]),
             MNaryPr => (owned_name("m", "naryPr"), vec![]),
             MFraction => (owned_name("m", "f"), vec![]),
+            MFractionPr => (owned_name("m", "fPr"), vec![]),
             MFunc => (owned_name("m", "func"), vec![]),
             MFName => (owned_name("m", "fName"), vec![]),
             MNum => (owned_name("m", "num"), vec![]),
             MDen => (owned_name("m", "den"), vec![]),
+            MCtrlPr => (owned_name("m", "ctrlPr"), vec![]),
+            MMatrix => (owned_name("m", "m"), vec![]),
+            MMatrixRow => (owned_name("m", "mr"), vec![]),
+            MElement => (owned_name("m", "e"), vec![]),
+            MAcc => (owned_name("m", "acc"), vec![]),
+            MAccPr => (owned_name("m", "accPr"), vec![]),
+            MBar => (owned_name("m", "bar"), vec![]),
+            MBarPr => (owned_name("m", "barPr"), vec![]),
+            MGroupChr => (owned_name("m", "groupChr"), vec![]),
+            MGroupChrPr => (owned_name("m", "groupChrPr"), vec![]),
+            MLimLow => (owned_name("m", "limLow"), vec![]),
+            MLimUpp => (owned_name("m", "limUpp"), vec![]),
+            MLim => (owned_name("m", "lim"), vec![]),
             WPInline => (owned_name("wp", "inline"), vec![]),
             WPAnchor => (owned_name("wp", "anchor"), vec![]),
             WBookmarkEnd => (owned_name("w", "bookmarkEnd"), vec![]),
+            WBreak => (owned_name("w", "br"), vec![]),
             WDrawing => (owned_name("w", "drawing"), vec![]),
+            WInstrText => (owned_name("w", "instrText"), vec![]),
             WParagraph => (owned_name("w", "p"), vec![]),
+            WParagraphPr => (owned_name("w", "pPr"), vec![]),
             WRun => (owned_name("w", "r"), vec![]),
+            WRunPr => (owned_name("w", "rPr"), vec![]),
+            WTab => (owned_name("w", "tab"), vec![]),
+            WTabs => (owned_name("w", "tabs"), vec![]),
             WText => (owned_name("w", "t"), vec![]),
+            WTable => (owned_name("w", "tbl"), vec![]),
+            WTablePr => (owned_name("w", "tblPr"), vec![]),
+            WTableGrid => (owned_name("w", "tblGrid"), vec![]),
+            WTableRow => (owned_name("w", "tr"), vec![]),
+            WTableCell => (owned_name("w", "tc"), vec![]),
+            WTableCellPr => (owned_name("w", "tcPr"), vec![]),
+            WSdt => (owned_name("w", "sdt"), vec![]),
+            WSdtPr => (owned_name("w", "sdtPr"), vec![]),
+            WSdtContent => (owned_name("w", "sdtContent"), vec![]),
+            WNumPr => (owned_name("w", "numPr"), vec![]),
+            WFootnoteRef => (owned_name("w", "footnoteRef"), vec![]),
+            WSectionPr => (owned_name("w", "sectPr"), vec![]),
+            WTitlePg => (owned_name("w", "titlePg"), vec![]),
+            WSuppressLineNumbers => (owned_name("w", "suppressLineNumbers"), vec![]),
+            WDocument => (owned_name("w", "document"), vec![]),
+            WBody => (owned_name("w", "body"), vec![]),
+            WFootnotes => (owned_name("w", "footnotes"), vec![]),
+            WSeparator => (owned_name("w", "separator"), vec![]),
+            WContinuationSeparator => (owned_name("w", "continuationSeparator"), vec![]),
             ABlip { rel } => (owned_name("a", "blip"), vec![owned_attr("r", "id", rel)]),
+            AsvgSvgBlip { rel } => (
+                owned_name("asvg", "svgBlip"),
+                vec![owned_attr("r", "embed", rel)],
+            ),
             MChr { value } => (owned_name("m", "chr"), vec![owned_attr("m", "val", value)]),
             WBookmarkStart { anchor } => (
                 owned_name("w", "bookmarkStart"),
                 vec![owned_attr("w", "anchor", anchor)],
             ),
+            WTextDirection { value } => (
+                owned_name("w", "textDirection"),
+                vec![owned_attr("w", "val", value)],
+            ),
+            WColor { value } => (
+                owned_name("w", "color"),
+                vec![owned_attr("w", "val", value)],
+            ),
+            WHighlight { value } => (
+                owned_name("w", "highlight"),
+                vec![owned_attr("w", "val", value)],
+            ),
+            WVertAlign { value } => (
+                owned_name("w", "vertAlign"),
+                vec![owned_attr("w", "val", value)],
+            ),
+            MPos { value } => (owned_name("m", "pos"), vec![owned_attr("m", "val", value)]),
+            WTabStop { leader } => (
+                owned_name("w", "tab"),
+                vec![
+                    owned_attr("w", "val", "right"),
+                    owned_attr("w", "leader", leader),
+                ],
+            ),
+            WParagraphStyle { value } => (
+                owned_name("w", "pStyle"),
+                vec![owned_attr("w", "val", value)],
+            ),
+            MSepChr { value } => (
+                owned_name("m", "sepChr"),
+                vec![owned_attr("m", "val", value)],
+            ),
+            MBegChr { value } => (
+                owned_name("m", "begChr"),
+                vec![owned_attr("m", "val", value)],
+            ),
+            MEndChr { value } => (
+                owned_name("m", "endChr"),
+                vec![owned_attr("m", "val", value)],
+            ),
+            MType { value } => (owned_name("m", "type"), vec![owned_attr("m", "val", value)]),
+            WLevel { value } => (owned_name("w", "ilvl"), vec![owned_attr("w", "val", value)]),
+            WNumId { value } => (
+                owned_name("w", "numId"),
+                vec![owned_attr("w", "val", value)],
+            ),
+            WRunStyle { value } => (
+                owned_name("w", "rStyle"),
+                vec![owned_attr("w", "val", value)],
+            ),
+            WFootnoteReference { id } => (
+                owned_name("w", "footnoteReference"),
+                vec![owned_attr("w", "id", id)],
+            ),
+            WFootnote { id } => (owned_name("w", "footnote"), vec![owned_attr("w", "id", id)]),
+            WTableLayout { value } => (
+                owned_name("w", "tblLayout"),
+                vec![owned_attr("w", "type", value)],
+            ),
+            WOutlineLevel { value } => (
+                owned_name("w", "outlineLvl"),
+                vec![owned_attr("w", "val", value)],
+            ),
             WHyperlink(link) => (
                 owned_name("w", "hyperlink"),
                 vec![match link {
@@ -131,6 +644,90 @@ impl Tag {
                     Link::Relationship(rel) => owned_attr("r", "id", rel),
                 }],
             ),
+            WFieldSimple { instr } => (
+                owned_name("w", "fldSimple"),
+                vec![owned_attr("w", "instr", instr)],
+            ),
+            WFieldChar(kind) => (
+                owned_name("w", "fldChar"),
+                vec![owned_attr(
+                    "w",
+                    "fldCharType",
+                    match kind {
+                        FieldChar::Begin => "begin",
+                        FieldChar::Separate => "separate",
+                        FieldChar::End => "end",
+                    },
+                )],
+            ),
+            WGridColumn(width) => (owned_name("w", "gridCol"), width.attributes()),
+            WTableWidth(width) => (owned_name("w", "tblW"), width.attributes()),
+            WTableCellWidth(width) => (owned_name("w", "tcW"), width.attributes()),
+            WSpacing(Spacing { line, rule }) => (
+                owned_name("w", "spacing"),
+                line.iter()
+                    .map(|line| owned_attr("w", "line", line))
+                    .chain(rule.iter().map(|rule| owned_attr("w", "lineRule", rule)))
+                    .collect(),
+            ),
+            WPDocPr { description } => (
+                owned_name("wp", "docPr"),
+                description
+                    .iter()
+                    .map(|description| OwnedAttribute {
+                        name: OwnedName::local("descr"),
+                        value: description.clone(),
+                    })
+                    .collect(),
+            ),
+            WPExtent { width } => (
+                owned_name("wp", "extent"),
+                width
+                    .iter()
+                    .map(|width| OwnedAttribute {
+                        name: OwnedName::local("cx"),
+                        value: width.clone(),
+                    })
+                    .collect(),
+            ),
+            WRunFonts { ascii } => (
+                owned_name("w", "rFonts"),
+                ascii
+                    .iter()
+                    .map(|ascii| owned_attr("w", "ascii", ascii))
+                    .collect(),
+            ),
+            WBidi(true) => (owned_name("w", "bidi"), vec![]),
+            WBidi(false) => (owned_name("w", "bidi"), vec![owned_attr("w", "val", "0")]),
+            WRtl(true) => (owned_name("w", "rtl"), vec![]),
+            WRtl(false) => (owned_name("w", "rtl"), vec![owned_attr("w", "val", "0")]),
+            WPageSize { landscape, width } => (
+                owned_name("w", "pgSz"),
+                width
+                    .iter()
+                    .map(|width| owned_attr("w", "w", &width.to_string()))
+                    .chain(landscape.then(|| owned_attr("w", "orient", "landscape")))
+                    .collect(),
+            ),
+            WPageMargins { left, right } => (
+                owned_name("w", "pgMar"),
+                left.iter()
+                    .map(|left| owned_attr("w", "left", &left.to_string()))
+                    .chain(right.map(|right| owned_attr("w", "right", &right.to_string())))
+                    .collect(),
+            ),
+            WLineNumbers { count_by, restart } => (
+                owned_name("w", "lnNumType"),
+                count_by
+                    .iter()
+                    .map(|count_by| owned_attr("w", "countBy", &count_by.to_string()))
+                    .chain(
+                        restart
+                            .iter()
+                            .map(|restart| owned_attr("w", "restart", restart)),
+                    )
+                    .collect(),
+            ),
             Content(content) => (
                 owned_name("docx2latex", "content"),
                 vec![owned_attr("docx2latex", "characters", content)],
@@ -168,6 +765,18 @@ impl TryFrom<(&OwnedName, &Vec<OwnedAttribute>)> for Tag {
         let (name, atts) = value;
         let id = normalize(name);
         let tag = match id.as_str() {
+            "asvg:svgBlip" => {
+                if let Some(rel_id) = atts.iter().find(|&a| normalize(&a.name) == "r:embed") {
+                    Tag::AsvgSvgBlip {
+                        rel: rel_id.value.clone(),
+                    }
+                } else {
+                    return Err(InputError::MissingAttributes {
+                        id,
+                        missing: vec!["r:embed"],
+                    });
+                }
+            }
             "a:graphic" => Tag::AGraphic,
             "a:graphicData" => Tag::AGraphicData,
             "a:blip" => {
@@ -187,12 +796,19 @@ impl TryFrom<(&OwnedName, &Vec<OwnedAttribute>)> for Tag {
             "m:oMathPara" => Tag::MoMathPara,
             "m:oMath" => Tag::MoMath,
             "m:d" => Tag::MDelim,
+            "m:dPr" => Tag::MDelimPr,
             "m:rad" => Tag::MRad,
             "m:deg" => Tag::MDeg,
             "m:r" => Tag::MRun,
             "m:t" => Tag::MText,
             "m:sub" => Tag::MSub,
             "m:sup" => Tag::MSup,
+            "m:sSub" => Tag::MSubscript,
+            "m:sSup" => Tag::MSuperscript,
+            "m:sSubSup" => Tag::MSubSup,
+            "m:sSubSupPr" => Tag::MSubSupPr,
+            "m:sPre" => Tag::MPre,
+            "m:sPrePr" => Tag::MPrePr,
             "m:nary" => Tag::MNary,
             "m:naryPr" => Tag::MNaryPr,
             "m:chr" => {
@@ -208,38 +824,392 @@ impl TryFrom<(&OwnedName, &Vec<OwnedAttribute>)> for Tag {
                 }
             }
             "m:f" => Tag::MFraction,
+            "m:fPr" => Tag::MFractionPr,
             "m:func" => Tag::MFunc,
             "m:fName" => Tag::MFName,
             "m:num" => Tag::MNum,
             "m:den" => Tag::MDen,
+            "m:ctrlPr" => Tag::MCtrlPr,
+            "m:m" => Tag::MMatrix,
+            "m:mr" => Tag::MMatrixRow,
+            "m:e" => Tag::MElement,
+            "m:acc" => Tag::MAcc,
+            "m:accPr" => Tag::MAccPr,
+            "m:bar" => Tag::MBar,
+            "m:barPr" => Tag::MBarPr,
+            "m:groupChr" => Tag::MGroupChr,
+            "m:groupChrPr" => Tag::MGroupChrPr,
+            "m:limLow" => Tag::MLimLow,
+            "m:limUpp" => Tag::MLimUpp,
+            "m:lim" => Tag::MLim,
             "wp:inline" => Tag::WPInline,
             "wp:anchor" => Tag::WPAnchor,
+            "wp:docPr" => Tag::WPDocPr {
+                description: atts
+                    .iter()
+                    .find(|&a| normalize(&a.name) == "descr")
+                    .map(|a| a.value.clone()),
+            },
+            "wp:extent" => Tag::WPExtent {
+                width: atts
+                    .iter()
+                    .find(|&a| normalize(&a.name) == "cx")
+                    .map(|a| a.value.clone()),
+            },
+            "w:rFonts" => Tag::WRunFonts {
+                ascii: atts
+                    .iter()
+                    .find(|&a| normalize(&a.name) == "w:ascii")
+                    .map(|a| a.value.clone()),
+            },
             "w:p" => Tag::WParagraph,
+            "w:pPr" => Tag::WParagraphPr,
             "w:r" => Tag::WRun,
+            "w:rPr" => Tag::WRunPr,
+            "w:tab" => {
+                // a tab stop from w:tabs always has a w:val, a tab character in a run never does
+                if atts.iter().any(|a| normalize(&a.name) == "w:val") {
+                    let leader = atts.iter().find(|&a| normalize(&a.name) == "w:leader");
+                    Tag::WTabStop {
+                        leader: leader.map_or("none".to_string(), |a| a.value.clone()),
+                    }
+                } else {
+                    Tag::WTab
+                }
+            }
+            "w:tabs" => Tag::WTabs,
             "w:t" => Tag::WText,
-            "w:hyperlink" => {
-                if let Some(rel_id) = atts.iter().find(|&a| normalize(&a.name) == "r:id") {
-                    Tag::WHyperlink(Link::Relationship(rel_id.value.clone()))
-                } else if let Some(anchor) = atts.iter().find(|&a| normalize(&a.name) == "w:anchor")
-                {
-                    Tag::WHyperlink(Link::Anchor(anchor.value.clone()))
+            "w:tbl" => Tag::WTable,
+            "w:tblPr" => Tag::WTablePr,
+            "w:tblGrid" => Tag::WTableGrid,
+            "w:gridCol" => Tag::WGridColumn(Width::from(atts)),
+            "w:tblW" => Tag::WTableWidth(Width::from(atts)),
+            "w:tcW" => Tag::WTableCellWidth(Width::from(atts)),
+            "w:tr" => Tag::WTableRow,
+            "w:tc" => Tag::WTableCell,
+            "w:tcPr" => Tag::WTableCellPr,
+            "w:sdt" => Tag::WSdt,
+            "w:sdtPr" => Tag::WSdtPr,
+            "w:sdtContent" => Tag::WSdtContent,
+            "w:numPr" => Tag::WNumPr,
+            "w:footnoteRef" => Tag::WFootnoteRef,
+            "w:sectPr" => Tag::WSectionPr,
+            "w:titlePg" => Tag::WTitlePg,
+            "w:suppressLineNumbers" => Tag::WSuppressLineNumbers,
+            "w:document" => Tag::WDocument,
+            "w:body" => Tag::WBody,
+            "w:footnotes" => Tag::WFootnotes,
+            "w:separator" => Tag::WSeparator,
+            "w:continuationSeparator" => Tag::WContinuationSeparator,
+            "w:textDirection" => {
+                if let Some(value) = atts.iter().find(|&a| normalize(&a.name) == "w:val") {
+                    Tag::WTextDirection {
+                        value: value.value.clone(),
+                    }
                 } else {
                     return Err(InputError::MissingAttributes {
                         id,
-                        missing: vec!["r:id", "w:anchor"],
+                        missing: vec!["w:val"],
                     });
                 }
             }
-            "w:bookmarkStart" => {
-                let anchor = atts
+            "w:color" => {
+                if let Some(value) = atts.iter().find(|&a| normalize(&a.name) == "w:val") {
+                    Tag::WColor {
+                        value: value.value.clone(),
+                    }
+                } else {
+                    return Err(InputError::MissingAttributes {
+                        id,
+                        missing: vec!["w:val"],
+                    });
+                }
+            }
+            "w:highlight" => {
+                if let Some(value) = atts.iter().find(|&a| normalize(&a.name) == "w:val") {
+                    Tag::WHighlight {
+                        value: value.value.clone(),
+                    }
+                } else {
+                    return Err(InputError::MissingAttributes {
+                        id,
+                        missing: vec!["w:val"],
+                    });
+                }
+            }
+            "w:vertAlign" => {
+                if let Some(value) = atts.iter().find(|&a| normalize(&a.name) == "w:val") {
+                    Tag::WVertAlign {
+                        value: value.value.clone(),
+                    }
+                } else {
+                    return Err(InputError::MissingAttributes {
+                        id,
+                        missing: vec!["w:val"],
+                    });
+                }
+            }
+            "m:pos" => {
+                if let Some(value) = atts.iter().find(|&a| normalize(&a.name) == "m:val") {
+                    Tag::MPos {
+                        value: value.value.clone(),
+                    }
+                } else {
+                    return Err(InputError::MissingAttributes {
+                        id,
+                        missing: vec!["m:val"],
+                    });
+                }
+            }
+            "w:pStyle" => {
+                if let Some(value) = atts.iter().find(|&a| normalize(&a.name) == "w:val") {
+                    Tag::WParagraphStyle {
+                        value: value.value.clone(),
+                    }
+                } else {
+                    return Err(InputError::MissingAttributes {
+                        id,
+                        missing: vec!["w:val"],
+                    });
+                }
+            }
+            "m:sepChr" => {
+                if let Some(value) = atts.iter().find(|&a| normalize(&a.name) == "m:val") {
+                    Tag::MSepChr {
+                        value: value.value.clone(),
+                    }
+                } else {
+                    return Err(InputError::MissingAttributes {
+                        id,
+                        missing: vec!["m:val"],
+                    });
+                }
+            }
+            "m:begChr" => {
+                if let Some(value) = atts.iter().find(|&a| normalize(&a.name) == "m:val") {
+                    Tag::MBegChr {
+                        value: value.value.clone(),
+                    }
+                } else {
+                    return Err(InputError::MissingAttributes {
+                        id,
+                        missing: vec!["m:val"],
+                    });
+                }
+            }
+            "m:endChr" => {
+                if let Some(value) = atts.iter().find(|&a| normalize(&a.name) == "m:val") {
+                    Tag::MEndChr {
+                        value: value.value.clone(),
+                    }
+                } else {
+                    return Err(InputError::MissingAttributes {
+                        id,
+                        missing: vec!["m:val"],
+                    });
+                }
+            }
+            "m:type" => {
+                if let Some(value) = atts.iter().find(|&a| normalize(&a.name) == "m:val") {
+                    Tag::MType {
+                        value: value.value.clone(),
+                    }
+                } else {
+                    return Err(InputError::MissingAttributes {
+                        id,
+                        missing: vec!["m:val"],
+                    });
+                }
+            }
+            "w:ilvl" => {
+                if let Some(value) = atts.iter().find(|&a| normalize(&a.name) == "w:val") {
+                    Tag::WLevel {
+                        value: value.value.clone(),
+                    }
+                } else {
+                    return Err(InputError::MissingAttributes {
+                        id,
+                        missing: vec!["w:val"],
+                    });
+                }
+            }
+            "w:numId" => {
+                if let Some(value) = atts.iter().find(|&a| normalize(&a.name) == "w:val") {
+                    Tag::WNumId {
+                        value: value.value.clone(),
+                    }
+                } else {
+                    return Err(InputError::MissingAttributes {
+                        id,
+                        missing: vec!["w:val"],
+                    });
+                }
+            }
+            "w:rStyle" => {
+                if let Some(value) = atts.iter().find(|&a| normalize(&a.name) == "w:val") {
+                    Tag::WRunStyle {
+                        value: value.value.clone(),
+                    }
+                } else {
+                    return Err(InputError::MissingAttributes {
+                        id,
+                        missing: vec!["w:val"],
+                    });
+                }
+            }
+            "w:footnoteReference" => {
+                if let Some(value) = atts.iter().find(|&a| normalize(&a.name) == "w:id") {
+                    Tag::WFootnoteReference {
+                        id: value.value.clone(),
+                    }
+                } else {
+                    return Err(InputError::MissingAttributes {
+                        id,
+                        missing: vec!["w:id"],
+                    });
+                }
+            }
+            "w:footnote" => {
+                if let Some(value) = atts.iter().find(|&a| normalize(&a.name) == "w:id") {
+                    Tag::WFootnote {
+                        id: value.value.clone(),
+                    }
+                } else {
+                    return Err(InputError::MissingAttributes {
+                        id,
+                        missing: vec!["w:id"],
+                    });
+                }
+            }
+            // w:type is optional, and autofit when left out
+            "w:tblLayout" => Tag::WTableLayout {
+                value: atts
+                    .iter()
+                    .find(|&a| normalize(&a.name) == "w:type")
+                    .map_or_else(|| "autofit".to_string(), |value| value.value.clone()),
+            },
+            "w:outlineLvl" => {
+                if let Some(value) = atts.iter().find(|&a| normalize(&a.name) == "w:val") {
+                    Tag::WOutlineLevel {
+                        value: value.value.clone(),
+                    }
+                } else {
+                    return Err(InputError::MissingAttributes {
+                        id,
+                        missing: vec!["w:val"],
+                    });
+                }
+            }
+            "w:hyperlink" => {
+                if let Some(rel_id) = atts.iter().find(|&a| normalize(&a.name) == "r:id") {
+                    Tag::WHyperlink(Link::Relationship(rel_id.value.clone()))
+                } else if let Some(anchor) = atts.iter().find(|&a| normalize(&a.name) == "w:anchor")
+                {
+                    Tag::WHyperlink(Link::Anchor(anchor.value.clone()))
+                } else {
+                    return Err(InputError::MissingAttributes {
+                        id,
+                        missing: vec!["r:id", "w:anchor"],
+                    });
+                }
+            }
+            "w:bookmarkStart" => {
+                // Word names bookmarks with w:name, which is what w:hyperlink's w:anchor refers to
+                let anchor = atts
                     .iter()
-                    .find(|&a| normalize(&a.name) == "w:anchor")
+                    .find(|&a| normalize(&a.name) == "w:name")
+                    .or_else(|| atts.iter().find(|&a| normalize(&a.name) == "w:anchor"))
                     .map(|a| a.value.clone())
                     .unwrap_or("".to_string());
                 Tag::WBookmarkStart { anchor }
             }
             "w:bookmarkEnd" => Tag::WBookmarkEnd,
+            "w:br" => {
+                // page and column breaks are not line breaks
+                let kind = atts.iter().find(|&a| normalize(&a.name) == "w:type");
+                match kind.map(|a| a.value.as_str()) {
+                    None | Some("textWrapping") => Tag::WBreak,
+                    Some(_) => Tag::Unknown { id },
+                }
+            }
             "w:drawing" => Tag::WDrawing,
+            "w:instrText" => Tag::WInstrText,
+            "w:fldSimple" => {
+                if let Some(instr) = atts.iter().find(|&a| normalize(&a.name) == "w:instr") {
+                    Tag::WFieldSimple {
+                        instr: instr.value.clone(),
+                    }
+                } else {
+                    return Err(InputError::MissingAttributes {
+                        id,
+                        missing: vec!["w:instr"],
+                    });
+                }
+            }
+            "w:fldChar" => {
+                let kind = atts.iter().find(|&a| normalize(&a.name) == "w:fldCharType");
+                match kind.map(|a| a.value.as_str()) {
+                    Some("begin") => Tag::WFieldChar(FieldChar::Begin),
+                    Some("separate") => Tag::WFieldChar(FieldChar::Separate),
+                    Some("end") => Tag::WFieldChar(FieldChar::End),
+                    Some(_) => Tag::Unknown { id },
+                    None => {
+                        return Err(InputError::MissingAttributes {
+                            id,
+                            missing: vec!["w:fldCharType"],
+                        })
+                    }
+                }
+            }
+            "w:spacing" => {
+                let find = |name| {
+                    atts.iter()
+                        .find(|&a| normalize(&a.name) == name)
+                        .map(|a| a.value.clone())
+                };
+                Tag::WSpacing(Spacing {
+                    line: find("w:line"),
+                    rule: find("w:lineRule"),
+                })
+            }
+            "w:bidi" => {
+                let value = atts.iter().find(|&a| normalize(&a.name) == "w:val");
+                Tag::WBidi(
+                    !value.is_some_and(|a| matches!(a.value.as_str(), "0" | "false" | "off")),
+                )
+            }
+            "w:rtl" => {
+                let value = atts.iter().find(|&a| normalize(&a.name) == "w:val");
+                Tag::WRtl(!value.is_some_and(|a| matches!(a.value.as_str(), "0" | "false" | "off")))
+            }
+            "w:pgSz" => {
+                let find = |name: &str| atts.iter().find(|&a| normalize(&a.name) == name);
+                let twips = |name: &str| find(name).and_then(|a| a.value.parse::<u64>().ok());
+                // some writers only swap the width and the height of the page
+                let wide = twips("w:w").zip(twips("w:h")).is_some_and(|(w, h)| w > h);
+                Tag::WPageSize {
+                    landscape: find("w:orient").is_some_and(|a| a.value == "landscape") || wide,
+                    width: twips("w:w"),
+                }
+            }
+            "w:pgMar" => {
+                let twips = |name: &str| {
+                    atts.iter()
+                        .find(|&a| normalize(&a.name) == name)
+                        .and_then(|a| a.value.parse::<u64>().ok())
+                };
+                Tag::WPageMargins {
+                    left: twips("w:left"),
+                    right: twips("w:right"),
+                }
+            }
+            "w:lnNumType" => {
+                let value = |name: &str| atts.iter().find(|&a| normalize(&a.name) == name);
+                Tag::WLineNumbers {
+                    count_by: value("w:countBy").and_then(|a| a.value.parse().ok()),
+                    restart: value("w:restart").map(|a| a.value.clone()),
+                }
+            }
             _ => Tag::Unknown { id },
         };
         Ok(tag)
@@ -296,31 +1266,119 @@ mod test {
     #[case(Tag::MoMathPara, (owned_name("m", "oMathPara"), vec![]))]
     #[case(Tag::MoMath, (owned_name("m", "oMath"), vec![]))]
     #[case(Tag::MDelim, (owned_name("m", "d"), vec![]))]
+    #[case(Tag::MDelimPr, (owned_name("m", "dPr"), vec![]))]
     #[case(Tag::MRad, (owned_name("m", "rad"), vec![]))]
     #[case(Tag::MDeg, (owned_name("m", "deg"), vec![]))]
     #[case(Tag::MRun, (owned_name("m", "r"), vec![]))]
     #[case(Tag::MText, (owned_name("m", "t"), vec![]))]
     #[case(Tag::MSub, (owned_name("m", "sub"), vec![]))]
     #[case(Tag::MSup, (owned_name("m", "sup"), vec![]))]
+    #[case(Tag::MSubscript, (owned_name("m", "sSub"), vec![]))]
+    #[case(Tag::MSuperscript, (owned_name("m", "sSup"), vec![]))]
+    #[case(Tag::MSubSup, (owned_name("m", "sSubSup"), vec![]))]
+    #[case(Tag::MSubSupPr, (owned_name("m", "sSubSupPr"), vec![]))]
+    #[case(Tag::MPre, (owned_name("m", "sPre"), vec![]))]
+    #[case(Tag::MPrePr, (owned_name("m", "sPrePr"), vec![]))]
     #[case(Tag::MNary, (owned_name("m", "nary"), vec![]))]
     #[case(Tag::MNaryPr, (owned_name("m", "naryPr"), vec![]))]
     #[case(Tag::MFraction, (owned_name("m", "f"), vec![]))]
+    #[case(Tag::MFractionPr, (owned_name("m", "fPr"), vec![]))]
     #[case(Tag::MFunc, (owned_name("m", "func"), vec![]))]
     #[case(Tag::MFName, (owned_name("m", "fName"), vec![]))]
     #[case(Tag::MNum, (owned_name("m", "num"), vec![]))]
     #[case(Tag::MDen, (owned_name("m", "den"), vec![]))]
+    #[case(Tag::MCtrlPr, (owned_name("m", "ctrlPr"), vec![]))]
+    #[case(Tag::MMatrix, (owned_name("m", "m"), vec![]))]
+    #[case(Tag::MMatrixRow, (owned_name("m", "mr"), vec![]))]
+    #[case(Tag::MElement, (owned_name("m", "e"), vec![]))]
+    #[case(Tag::MAcc, (owned_name("m", "acc"), vec![]))]
+    #[case(Tag::MAccPr, (owned_name("m", "accPr"), vec![]))]
+    #[case(Tag::MBar, (owned_name("m", "bar"), vec![]))]
+    #[case(Tag::MBarPr, (owned_name("m", "barPr"), vec![]))]
+    #[case(Tag::MGroupChr, (owned_name("m", "groupChr"), vec![]))]
+    #[case(Tag::MGroupChrPr, (owned_name("m", "groupChrPr"), vec![]))]
+    #[case(Tag::MLimLow, (owned_name("m", "limLow"), vec![]))]
+    #[case(Tag::MLimUpp, (owned_name("m", "limUpp"), vec![]))]
+    #[case(Tag::MLim, (owned_name("m", "lim"), vec![]))]
     #[case(Tag::WPInline, (owned_name("wp", "inline"), vec![]))]
     #[case(Tag::WPAnchor, (owned_name("wp", "anchor"), vec![]))]
     #[case(Tag::WBookmarkEnd, (owned_name("w", "bookmarkEnd"), vec![]))]
+    #[case(Tag::WBreak, (owned_name("w", "br"), vec![]))]
     #[case(Tag::WDrawing, (owned_name("w", "drawing"), vec![]))]
+    #[case(Tag::WInstrText, (owned_name("w", "instrText"), vec![]))]
     #[case(Tag::WParagraph, (owned_name("w", "p"), vec![]))]
+    #[case(Tag::WParagraphPr, (owned_name("w", "pPr"), vec![]))]
     #[case(Tag::WRun, (owned_name("w", "r"), vec![]))]
+    #[case(Tag::WRunPr, (owned_name("w", "rPr"), vec![]))]
+    #[case(Tag::WTab, (owned_name("w", "tab"), vec![]))]
+    #[case(Tag::WTabs, (owned_name("w", "tabs"), vec![]))]
     #[case(Tag::WText, (owned_name("w", "t"), vec![]))]
+    #[case(Tag::WTable, (owned_name("w", "tbl"), vec![]))]
+    #[case(Tag::WTablePr, (owned_name("w", "tblPr"), vec![]))]
+    #[case(Tag::WTableGrid, (owned_name("w", "tblGrid"), vec![]))]
+    #[case(Tag::WGridColumn(Width::default()), (owned_name("w", "gridCol"), vec![]))]
+    #[case(Tag::WTableWidth(Width { value: Some("2500".to_string()), kind: Some("pct".to_string()) }), (owned_name("w", "tblW"), vec![owned_attr("w", "w", "2500"), owned_attr("w", "type", "pct")]))]
+    #[case(Tag::WTableCellWidth(Width { value: Some("1500".to_string()), kind: Some("pct".to_string()) }), (owned_name("w", "tcW"), vec![owned_attr("w", "w", "1500"), owned_attr("w", "type", "pct")]))]
+    #[case(Tag::WTableRow, (owned_name("w", "tr"), vec![]))]
+    #[case(Tag::WTableCell, (owned_name("w", "tc"), vec![]))]
+    #[case(Tag::WTableCellPr, (owned_name("w", "tcPr"), vec![]))]
+    #[case(Tag::WSdt, (owned_name("w", "sdt"), vec![]))]
+    #[case(Tag::WSdtPr, (owned_name("w", "sdtPr"), vec![]))]
+    #[case(Tag::WSdtContent, (owned_name("w", "sdtContent"), vec![]))]
+    #[case(Tag::WNumPr, (owned_name("w", "numPr"), vec![]))]
+    #[case(Tag::WFootnoteRef, (owned_name("w", "footnoteRef"), vec![]))]
+    #[case(Tag::WSectionPr, (owned_name("w", "sectPr"), vec![]))]
+    #[case(Tag::WTitlePg, (owned_name("w", "titlePg"), vec![]))]
+    #[case(Tag::WSuppressLineNumbers, (owned_name("w", "suppressLineNumbers"), vec![]))]
+    #[case(Tag::WDocument, (owned_name("w", "document"), vec![]))]
+    #[case(Tag::WBody, (owned_name("w", "body"), vec![]))]
+    #[case(Tag::WFootnotes, (owned_name("w", "footnotes"), vec![]))]
+    #[case(Tag::WSeparator, (owned_name("w", "separator"), vec![]))]
+    #[case(Tag::WContinuationSeparator, (owned_name("w", "continuationSeparator"), vec![]))]
     #[case(Tag::ABlip { rel: "RelId".to_string() }, (owned_name("a", "blip"), vec![owned_attr("r", "id", "RelId")]))]
+    #[case(Tag::AsvgSvgBlip { rel: "RelId".to_string() }, (owned_name("asvg", "svgBlip"), vec![owned_attr("r", "embed", "RelId")]))]
     #[case(Tag::MChr { value: "X".to_string() }, (owned_name("m", "chr"), vec![owned_attr("m", "val", "X")]))]
     #[case(Tag::WBookmarkStart { anchor: "Anchor".to_string() }, (owned_name("w", "bookmarkStart"), vec![owned_attr("w", "anchor", "Anchor")]))]
+    #[case(Tag::WTextDirection { value: "btLr".to_string() }, (owned_name("w", "textDirection"), vec![owned_attr("w", "val", "btLr")]))]
+    #[case(Tag::WColor { value: "FF0000".to_string() }, (owned_name("w", "color"), vec![owned_attr("w", "val", "FF0000")]))]
+    #[case(Tag::WHighlight { value: "yellow".to_string() }, (owned_name("w", "highlight"), vec![owned_attr("w", "val", "yellow")]))]
+    #[case(Tag::WVertAlign { value: "superscript".to_string() }, (owned_name("w", "vertAlign"), vec![owned_attr("w", "val", "superscript")]))]
+    #[case(Tag::MPos { value: "top".to_string() }, (owned_name("m", "pos"), vec![owned_attr("m", "val", "top")]))]
+    #[case(Tag::WTabStop { leader: "dot".to_string() }, (owned_name("w", "tab"), vec![owned_attr("w", "val", "right"), owned_attr("w", "leader", "dot")]))]
+    #[case(Tag::WParagraphStyle { value: "Title".to_string() }, (owned_name("w", "pStyle"), vec![owned_attr("w", "val", "Title")]))]
+    #[case(Tag::MSepChr { value: "|".to_string() }, (owned_name("m", "sepChr"), vec![owned_attr("m", "val", "|")]))]
+    #[case(Tag::MBegChr { value: "[".to_string() }, (owned_name("m", "begChr"), vec![owned_attr("m", "val", "[")]))]
+    #[case(Tag::MEndChr { value: "]".to_string() }, (owned_name("m", "endChr"), vec![owned_attr("m", "val", "]")]))]
+    #[case(Tag::MType { value: "lin".to_string() }, (owned_name("m", "type"), vec![owned_attr("m", "val", "lin")]))]
+    #[case(Tag::WLevel { value: "1".to_string() }, (owned_name("w", "ilvl"), vec![owned_attr("w", "val", "1")]))]
+    #[case(Tag::WNumId { value: "3".to_string() }, (owned_name("w", "numId"), vec![owned_attr("w", "val", "3")]))]
+    #[case(Tag::WRunStyle { value: "FootnoteReference".to_string() }, (owned_name("w", "rStyle"), vec![owned_attr("w", "val", "FootnoteReference")]))]
+    #[case(Tag::WFootnoteReference { id: "2".to_string() }, (owned_name("w", "footnoteReference"), vec![owned_attr("w", "id", "2")]))]
+    #[case(Tag::WFootnote { id: "2".to_string() }, (owned_name("w", "footnote"), vec![owned_attr("w", "id", "2")]))]
+    #[case(Tag::WTableLayout { value: "fixed".to_string() }, (owned_name("w", "tblLayout"), vec![owned_attr("w", "type", "fixed")]))]
+    #[case(Tag::WOutlineLevel { value: "0".to_string() }, (owned_name("w", "outlineLvl"), vec![owned_attr("w", "val", "0")]))]
     #[case(Tag::WHyperlink(Link::Anchor("Anchor".to_string())), (owned_name("w", "hyperlink"), vec![owned_attr("w", "anchor", "Anchor")]))]
     #[case(Tag::WHyperlink(Link::Relationship("RelId".to_string())), (owned_name("w", "hyperlink"), vec![owned_attr("r", "id", "RelId")]))]
+    #[case(Tag::WFieldSimple { instr: "PAGE".to_string() }, (owned_name("w", "fldSimple"), vec![owned_attr("w", "instr", "PAGE")]))]
+    #[case(Tag::WFieldChar(FieldChar::Begin), (owned_name("w", "fldChar"), vec![owned_attr("w", "fldCharType", "begin")]))]
+    #[case(Tag::WFieldChar(FieldChar::Separate), (owned_name("w", "fldChar"), vec![owned_attr("w", "fldCharType", "separate")]))]
+    #[case(Tag::WFieldChar(FieldChar::End), (owned_name("w", "fldChar"), vec![owned_attr("w", "fldCharType", "end")]))]
+    #[case(Tag::WSpacing(Spacing { line: Some("360".to_string()), rule: Some("auto".to_string()) }), (owned_name("w", "spacing"), vec![owned_attr("w", "line", "360"), owned_attr("w", "lineRule", "auto")]))]
+    #[case(Tag::WSpacing(Spacing::default()), (owned_name("w", "spacing"), vec![]))]
+    #[case(Tag::WPDocPr { description: None }, (owned_name("wp", "docPr"), vec![]))]
+    #[case(Tag::WPExtent { width: None }, (owned_name("wp", "extent"), vec![]))]
+    #[case(Tag::WRunFonts { ascii: Some("Consolas".to_string()) }, (owned_name("w", "rFonts"), vec![owned_attr("w", "ascii", "Consolas")]))]
+    #[case(Tag::WRunFonts { ascii: None }, (owned_name("w", "rFonts"), vec![]))]
+    #[case(Tag::WBidi(true), (owned_name("w", "bidi"), vec![]))]
+    #[case(Tag::WBidi(false), (owned_name("w", "bidi"), vec![owned_attr("w", "val", "0")]))]
+    #[case(Tag::WRtl(true), (owned_name("w", "rtl"), vec![]))]
+    #[case(Tag::WRtl(false), (owned_name("w", "rtl"), vec![owned_attr("w", "val", "0")]))]
+    #[case(Tag::WPageSize { landscape: true, width: None }, (owned_name("w", "pgSz"), vec![owned_attr("w", "orient", "landscape")]))]
+    #[case(Tag::WPageSize { landscape: false, width: Some(11906) }, (owned_name("w", "pgSz"), vec![owned_attr("w", "w", "11906")]))]
+    #[case(Tag::WPageMargins { left: Some(1440), right: Some(1800) }, (owned_name("w", "pgMar"), vec![owned_attr("w", "left", "1440"), owned_attr("w", "right", "1800")]))]
+    #[case(Tag::WPageMargins { left: None, right: None }, (owned_name("w", "pgMar"), vec![]))]
+    #[case(Tag::WLineNumbers { count_by: Some(5), restart: Some("continuous".to_string()) }, (owned_name("w", "lnNumType"), vec![owned_attr("w", "countBy", "5"), owned_attr("w", "restart", "continuous")]))]
+    #[case(Tag::WLineNumbers { count_by: None, restart: None }, (owned_name("w", "lnNumType"), vec![]))]
     fn to_owned_works(#[case] input: Tag, #[case] output: (OwnedName, Vec<OwnedAttribute>)) {
         let (e_name, e_attrs) = &output;
         let owned = input.to_owned();
@@ -406,6 +1464,25 @@ mod test {
         assert!(extracted.is_none());
     }
 
+    #[test]
+    fn asvgsvgblip_extracts_asvgsvgblip() {
+        let tag = Tag::AsvgSvgBlip {
+            rel: "RelId".to_string(),
+        };
+        let extracted = tag.asvg_svg_blip();
+        assert!(extracted.is_some());
+        assert_eq!(extracted.unwrap(), "RelId");
+    }
+
+    #[test]
+    fn asvgsvgblip_rejects_other() {
+        let tag = Tag::ABlip {
+            rel: "RelId".to_string(),
+        };
+        let extracted = tag.asvg_svg_blip();
+        assert!(extracted.is_none());
+    }
+
     #[test]
     fn mchr_extracts_mchr() {
         let tag = Tag::MChr {
@@ -445,190 +1522,1225 @@ mod test {
     }
 
     #[test]
-    fn whyperlink_extracts_whyperlink_anchor() {
-        let anchor = Tag::WHyperlink(Link::Anchor("Anchor".to_string()));
-        let extracted = anchor.w_hyperlink();
+    fn wtextdirection_extracts_wtextdirection() {
+        let tag = Tag::WTextDirection {
+            value: "btLr".to_string(),
+        };
+        let extracted = tag.w_text_direction();
         assert!(extracted.is_some());
-        assert!(matches!(extracted.unwrap(), Link::Anchor(_)));
-        if let Some(Link::Anchor(anchor)) = extracted {
-            assert_eq!(anchor, "Anchor");
-        }
+        assert_eq!(extracted.unwrap(), "btLr");
     }
 
     #[test]
-    fn whyperlink_extracts_whyperlink_relationship() {
-        let rel = Tag::WHyperlink(Link::Relationship("RelId".to_string()));
-        let extracted = rel.w_hyperlink();
+    fn wtextdirection_rejects_other() {
+        let tag = Tag::Unknown {
+            id: "Junk".to_string(),
+        };
+        let extracted = tag.w_text_direction();
+        assert!(extracted.is_none());
+    }
+
+    #[test]
+    fn wcolor_extracts_wcolor() {
+        let tag = Tag::WColor {
+            value: "FF0000".to_string(),
+        };
+        let extracted = tag.w_color();
         assert!(extracted.is_some());
-        assert!(matches!(extracted.unwrap(), Link::Relationship(_)));
-        if let Some(Link::Relationship(rel)) = extracted {
-            assert_eq!(rel, "RelId");
-        }
+        assert_eq!(extracted.unwrap(), "FF0000");
     }
 
     #[test]
-    fn whyperlink_rejects_other() {
+    fn wcolor_rejects_other() {
         let tag = Tag::Unknown {
             id: "Junk".to_string(),
         };
-        let extracted = tag.w_hyperlink();
+        let extracted = tag.w_color();
         assert!(extracted.is_none());
     }
 
     #[test]
-    fn content_extracts_content() {
-        let tag = Tag::Content("Content".to_string());
-        let extracted = tag.content();
+    fn whighlight_extracts_whighlight() {
+        let tag = Tag::WHighlight {
+            value: "yellow".to_string(),
+        };
+        let extracted = tag.w_highlight();
         assert!(extracted.is_some());
-        assert_eq!(extracted.unwrap(), "Content");
+        assert_eq!(extracted.unwrap(), "yellow");
     }
 
     #[test]
-    fn content_rejects_other() {
+    fn whighlight_rejects_other() {
         let tag = Tag::Unknown {
             id: "Junk".to_string(),
         };
-        let extracted = tag.content();
+        let extracted = tag.w_highlight();
         assert!(extracted.is_none());
     }
 
-    fn owned(raw: &'static str) -> OwnedName {
-        let parts: Vec<_> = raw.split(':').collect();
-        OwnedName {
-            local_name: parts[1].to_string(),
-            namespace: None,
-            prefix: Some(parts[0].to_string()),
-        }
-    }
-
-    #[rstest]
-    #[case(Tag::AGraphic, owned_name("a", "graphic"))]
-    #[case(Tag::AGraphicData, owned_name("a", "graphicData"))]
-    #[case(Tag::PicPic, owned_name("pic", "pic"))]
-    #[case(Tag::PicBlipFill, owned_name("pic", "blipFill"))]
-    #[case(Tag::MoMathPara, owned_name("m", "oMathPara"))]
-    #[case(Tag::MoMath, owned_name("m", "oMath"))]
-    #[case(Tag::MDelim, owned_name("m", "d"))]
-    #[case(Tag::MRad, owned_name("m", "rad"))]
-    #[case(Tag::MDeg, owned_name("m", "deg"))]
-    #[case(Tag::MRun, owned_name("m", "r"))]
-    #[case(Tag::MText, owned_name("m", "t"))]
-    #[case(Tag::MSub, owned_name("m", "sub"))]
-    #[case(Tag::MSup, owned_name("m", "sup"))]
-    #[case(Tag::MNary, owned_name("m", "nary"))]
-    #[case(Tag::MNaryPr, owned_name("m", "naryPr"))]
-    #[case(Tag::MFraction, owned_name("m", "f"))]
-    #[case(Tag::MFunc, owned_name("m", "func"))]
-    #[case(Tag::MFName, owned_name("m", "fName"))]
-    #[case(Tag::MNum, owned_name("m", "num"))]
-    #[case(Tag::MDen, owned_name("m", "den"))]
-    #[case(Tag::WPInline, owned_name("wp", "inline"))]
-    #[case(Tag::WPAnchor, owned_name("wp", "anchor"))]
-    #[case(Tag::WBookmarkEnd, owned_name("w", "bookmarkEnd"))]
-    #[case(Tag::WDrawing, owned_name("w", "drawing"))]
-    #[case(Tag::WParagraph, owned_name("w", "p"))]
-    #[case(Tag::WRun, owned_name("w", "r"))]
-    #[case(Tag::WText, owned_name("w", "t"))]
-    fn converts_empty_tags(#[case] tag: Tag, #[case] owned: OwnedName) {
-        let actual = Tag::try_from((&owned, &vec![])).expect("Input was constructed manually");
-        assert_eq!(actual, tag);
+    #[test]
+    fn wvertalign_extracts_wvertalign() {
+        let tag = Tag::WVertAlign {
+            value: "superscript".to_string(),
+        };
+        let extracted = tag.w_vert_align();
+        assert!(extracted.is_some());
+        assert_eq!(extracted.unwrap(), "superscript");
     }
 
     #[test]
-    fn converts_ablip_with_attribute() {
-        let name = owned("a:blip");
-        let attribute = OwnedAttribute {
-            name: owned("r:embed"),
-            value: "RelId".to_string(),
+    fn wrunfonts_extracts_wrunfonts() {
+        let tag = Tag::WRunFonts {
+            ascii: Some("Consolas".to_string()),
         };
-
-        let actual = Tag::try_from((&name, &vec![attribute]));
-        assert!(actual.is_ok());
-        let actual = actual.unwrap();
-
-        assert!(matches!(actual, Tag::ABlip { rel: _ }));
-        if let Tag::ABlip { rel } = actual {
-            assert_eq!(rel, "RelId");
-        }
+        let extracted = tag.w_run_fonts();
+        assert!(extracted.is_some());
+        assert_eq!(extracted.unwrap(), "Consolas");
     }
 
     #[test]
-    fn rejects_ablip_without_attribute() {
-        let name = owned("a:blip");
+    fn wrunfonts_rejects_theme_fonts() {
+        let tag = Tag::WRunFonts { ascii: None };
+        let extracted = tag.w_run_fonts();
+        assert!(extracted.is_none());
+    }
 
-        let actual = Tag::try_from((&name, &vec![]));
+    #[test]
+    fn wvertalign_rejects_other() {
+        let tag = Tag::Unknown {
+            id: "Junk".to_string(),
+        };
+        let extracted = tag.w_vert_align();
+        assert!(extracted.is_none());
+    }
+
+    #[test]
+    fn mpos_extracts_mpos() {
+        let tag = Tag::MPos {
+            value: "top".to_string(),
+        };
+        let extracted = tag.m_pos();
+        assert!(extracted.is_some());
+        assert_eq!(extracted.unwrap(), "top");
+    }
+
+    #[test]
+    fn mpos_rejects_other() {
+        let tag = Tag::Unknown {
+            id: "Junk".to_string(),
+        };
+        let extracted = tag.m_pos();
+        assert!(extracted.is_none());
+    }
+
+    #[test]
+    fn wtabstop_extracts_wtabstop() {
+        let tag = Tag::WTabStop {
+            leader: "dot".to_string(),
+        };
+        let extracted = tag.w_tab_stop();
+        assert!(extracted.is_some());
+        assert_eq!(extracted.unwrap(), "dot");
+    }
+
+    #[test]
+    fn wbidi_extracts_wbidi() {
+        let tag = Tag::WBidi(true);
+        assert_eq!(tag.w_bidi(), Some(true));
+    }
+
+    #[test]
+    fn wbidi_rejects_other() {
+        let tag = Tag::WTab;
+        assert!(tag.w_bidi().is_none());
+    }
+
+    #[test]
+    fn wrtl_extracts_wrtl() {
+        let tag = Tag::WRtl(false);
+        assert_eq!(tag.w_rtl(), Some(false));
+    }
+
+    #[test]
+    fn wrtl_rejects_other() {
+        let tag = Tag::WBidi(true);
+        assert!(tag.w_rtl().is_none());
+    }
+
+    #[test]
+    fn wtabstop_rejects_other() {
+        let tag = Tag::WTab;
+        let extracted = tag.w_tab_stop();
+        assert!(extracted.is_none());
+    }
+
+    #[test]
+    fn wparagraphstyle_extracts_wparagraphstyle() {
+        let tag = Tag::WParagraphStyle {
+            value: "Title".to_string(),
+        };
+        let extracted = tag.w_paragraph_style();
+        assert!(extracted.is_some());
+        assert_eq!(extracted.unwrap(), "Title");
+    }
+
+    #[test]
+    fn wparagraphstyle_rejects_other() {
+        let tag = Tag::Unknown {
+            id: "Junk".to_string(),
+        };
+        let extracted = tag.w_paragraph_style();
+        assert!(extracted.is_none());
+    }
+
+    #[test]
+    fn msepchr_extracts_msepchr() {
+        let tag = Tag::MSepChr {
+            value: "|".to_string(),
+        };
+        let extracted = tag.m_sep_chr();
+        assert!(extracted.is_some());
+        assert_eq!(extracted.unwrap(), "|");
+    }
+
+    #[test]
+    fn msepchr_rejects_other() {
+        let tag = Tag::Unknown {
+            id: "Junk".to_string(),
+        };
+        let extracted = tag.m_sep_chr();
+        assert!(extracted.is_none());
+    }
+
+    #[test]
+    fn mbegchr_extracts_mbegchr() {
+        let tag = Tag::MBegChr {
+            value: "[".to_string(),
+        };
+        let extracted = tag.m_beg_chr();
+        assert!(extracted.is_some());
+        assert_eq!(extracted.unwrap(), "[");
+    }
+
+    #[test]
+    fn mbegchr_rejects_other() {
+        let tag = Tag::Unknown {
+            id: "Junk".to_string(),
+        };
+        let extracted = tag.m_beg_chr();
+        assert!(extracted.is_none());
+    }
+
+    #[test]
+    fn mendchr_extracts_mendchr() {
+        let tag = Tag::MEndChr {
+            value: "]".to_string(),
+        };
+        let extracted = tag.m_end_chr();
+        assert!(extracted.is_some());
+        assert_eq!(extracted.unwrap(), "]");
+    }
+
+    #[test]
+    fn mendchr_rejects_other() {
+        let tag = Tag::Unknown {
+            id: "Junk".to_string(),
+        };
+        let extracted = tag.m_end_chr();
+        assert!(extracted.is_none());
+    }
+
+    #[test]
+    fn mtype_extracts_mtype() {
+        let tag = Tag::MType {
+            value: "lin".to_string(),
+        };
+        let extracted = tag.m_type();
+        assert!(extracted.is_some());
+        assert_eq!(extracted.unwrap(), "lin");
+    }
+
+    #[test]
+    fn mtype_rejects_other() {
+        let tag = Tag::Unknown {
+            id: "Junk".to_string(),
+        };
+        let extracted = tag.m_type();
+        assert!(extracted.is_none());
+    }
+
+    #[test]
+    fn wlevel_extracts_wlevel() {
+        let tag = Tag::WLevel {
+            value: "1".to_string(),
+        };
+        let extracted = tag.w_level();
+        assert!(extracted.is_some());
+        assert_eq!(extracted.unwrap(), "1");
+    }
+
+    #[test]
+    fn wlevel_rejects_other() {
+        let tag = Tag::Unknown {
+            id: "Junk".to_string(),
+        };
+        let extracted = tag.w_level();
+        assert!(extracted.is_none());
+    }
+
+    #[test]
+    fn wnumid_extracts_wnumid() {
+        let tag = Tag::WNumId {
+            value: "3".to_string(),
+        };
+        let extracted = tag.w_num_id();
+        assert!(extracted.is_some());
+        assert_eq!(extracted.unwrap(), "3");
+    }
+
+    #[test]
+    fn wnumid_rejects_other() {
+        let tag = Tag::Unknown {
+            id: "Junk".to_string(),
+        };
+        let extracted = tag.w_num_id();
+        assert!(extracted.is_none());
+    }
+
+    #[test]
+    fn wrunstyle_extracts_wrunstyle() {
+        let tag = Tag::WRunStyle {
+            value: "FootnoteReference".to_string(),
+        };
+        let extracted = tag.w_run_style();
+        assert!(extracted.is_some());
+        assert_eq!(extracted.unwrap(), "FootnoteReference");
+    }
+
+    #[test]
+    fn wrunstyle_rejects_other() {
+        let tag = Tag::Unknown {
+            id: "Junk".to_string(),
+        };
+        let extracted = tag.w_run_style();
+        assert!(extracted.is_none());
+    }
+
+    #[test]
+    fn wfootnotereference_extracts_wfootnotereference() {
+        let tag = Tag::WFootnoteReference {
+            id: "2".to_string(),
+        };
+        let extracted = tag.w_footnote_reference();
+        assert!(extracted.is_some());
+        assert_eq!(extracted.unwrap(), "2");
+    }
+
+    #[test]
+    fn wfootnotereference_rejects_other() {
+        let tag = Tag::Unknown {
+            id: "Junk".to_string(),
+        };
+        let extracted = tag.w_footnote_reference();
+        assert!(extracted.is_none());
+    }
+
+    #[test]
+    fn wfootnote_extracts_wfootnote() {
+        let tag = Tag::WFootnote {
+            id: "2".to_string(),
+        };
+        let extracted = tag.w_footnote();
+        assert!(extracted.is_some());
+        assert_eq!(extracted.unwrap(), "2");
+    }
+
+    #[test]
+    fn wfootnote_rejects_other() {
+        let tag = Tag::Unknown {
+            id: "Junk".to_string(),
+        };
+        let extracted = tag.w_footnote();
+        assert!(extracted.is_none());
+    }
+
+    #[test]
+    fn wtablelayout_extracts_wtablelayout() {
+        let tag = Tag::WTableLayout {
+            value: "fixed".to_string(),
+        };
+        let extracted = tag.w_table_layout();
+        assert!(extracted.is_some());
+        assert_eq!(extracted.unwrap(), "fixed");
+    }
+
+    #[test]
+    fn wtablelayout_rejects_other() {
+        let tag = Tag::Unknown {
+            id: "Junk".to_string(),
+        };
+        let extracted = tag.w_table_layout();
+        assert!(extracted.is_none());
+    }
+
+    #[test]
+    fn woutlinelevel_extracts_woutlinelevel() {
+        let tag = Tag::WOutlineLevel {
+            value: "0".to_string(),
+        };
+        let extracted = tag.w_outline_level();
+        assert!(extracted.is_some());
+        assert_eq!(extracted.unwrap(), "0");
+    }
+
+    #[test]
+    fn woutlinelevel_rejects_other() {
+        let tag = Tag::Unknown {
+            id: "Junk".to_string(),
+        };
+        let extracted = tag.w_outline_level();
+        assert!(extracted.is_none());
+    }
+
+    #[test]
+    fn whyperlink_extracts_whyperlink_anchor() {
+        let anchor = Tag::WHyperlink(Link::Anchor("Anchor".to_string()));
+        let extracted = anchor.w_hyperlink();
+        assert!(extracted.is_some());
+        assert!(matches!(extracted.unwrap(), Link::Anchor(_)));
+        if let Some(Link::Anchor(anchor)) = extracted {
+            assert_eq!(anchor, "Anchor");
+        }
+    }
+
+    #[test]
+    fn whyperlink_extracts_whyperlink_relationship() {
+        let rel = Tag::WHyperlink(Link::Relationship("RelId".to_string()));
+        let extracted = rel.w_hyperlink();
+        assert!(extracted.is_some());
+        assert!(matches!(extracted.unwrap(), Link::Relationship(_)));
+        if let Some(Link::Relationship(rel)) = extracted {
+            assert_eq!(rel, "RelId");
+        }
+    }
+
+    #[test]
+    fn whyperlink_rejects_other() {
+        let tag = Tag::Unknown {
+            id: "Junk".to_string(),
+        };
+        let extracted = tag.w_hyperlink();
+        assert!(extracted.is_none());
+    }
+
+    #[test]
+    fn content_extracts_content() {
+        let tag = Tag::Content("Content".to_string());
+        let extracted = tag.content();
+        assert!(extracted.is_some());
+        assert_eq!(extracted.unwrap(), "Content");
+    }
+
+    #[test]
+    fn content_rejects_other() {
+        let tag = Tag::Unknown {
+            id: "Junk".to_string(),
+        };
+        let extracted = tag.content();
+        assert!(extracted.is_none());
+    }
+
+    fn owned(raw: &'static str) -> OwnedName {
+        let parts: Vec<_> = raw.split(':').collect();
+        OwnedName {
+            local_name: parts[1].to_string(),
+            namespace: None,
+            prefix: Some(parts[0].to_string()),
+        }
+    }
+
+    #[rstest]
+    #[case(Tag::AGraphic, owned_name("a", "graphic"))]
+    #[case(Tag::AGraphicData, owned_name("a", "graphicData"))]
+    #[case(Tag::PicPic, owned_name("pic", "pic"))]
+    #[case(Tag::PicBlipFill, owned_name("pic", "blipFill"))]
+    #[case(Tag::MoMathPara, owned_name("m", "oMathPara"))]
+    #[case(Tag::MoMath, owned_name("m", "oMath"))]
+    #[case(Tag::MDelim, owned_name("m", "d"))]
+    #[case(Tag::MDelimPr, owned_name("m", "dPr"))]
+    #[case(Tag::MRad, owned_name("m", "rad"))]
+    #[case(Tag::MDeg, owned_name("m", "deg"))]
+    #[case(Tag::MRun, owned_name("m", "r"))]
+    #[case(Tag::MText, owned_name("m", "t"))]
+    #[case(Tag::MSub, owned_name("m", "sub"))]
+    #[case(Tag::MSup, owned_name("m", "sup"))]
+    #[case(Tag::MSubscript, owned_name("m", "sSub"))]
+    #[case(Tag::MSuperscript, owned_name("m", "sSup"))]
+    #[case(Tag::MSubSup, owned_name("m", "sSubSup"))]
+    #[case(Tag::MSubSupPr, owned_name("m", "sSubSupPr"))]
+    #[case(Tag::MPre, owned_name("m", "sPre"))]
+    #[case(Tag::MPrePr, owned_name("m", "sPrePr"))]
+    #[case(Tag::MNary, owned_name("m", "nary"))]
+    #[case(Tag::MNaryPr, owned_name("m", "naryPr"))]
+    #[case(Tag::MFraction, owned_name("m", "f"))]
+    #[case(Tag::MFractionPr, owned_name("m", "fPr"))]
+    #[case(Tag::MFunc, owned_name("m", "func"))]
+    #[case(Tag::MFName, owned_name("m", "fName"))]
+    #[case(Tag::MNum, owned_name("m", "num"))]
+    #[case(Tag::MDen, owned_name("m", "den"))]
+    #[case(Tag::MCtrlPr, owned_name("m", "ctrlPr"))]
+    #[case(Tag::MMatrix, owned_name("m", "m"))]
+    #[case(Tag::MMatrixRow, owned_name("m", "mr"))]
+    #[case(Tag::MElement, owned_name("m", "e"))]
+    #[case(Tag::MAcc, owned_name("m", "acc"))]
+    #[case(Tag::MAccPr, owned_name("m", "accPr"))]
+    #[case(Tag::MBar, owned_name("m", "bar"))]
+    #[case(Tag::MBarPr, owned_name("m", "barPr"))]
+    #[case(Tag::MGroupChr, owned_name("m", "groupChr"))]
+    #[case(Tag::MGroupChrPr, owned_name("m", "groupChrPr"))]
+    #[case(Tag::MLimLow, owned_name("m", "limLow"))]
+    #[case(Tag::MLimUpp, owned_name("m", "limUpp"))]
+    #[case(Tag::MLim, owned_name("m", "lim"))]
+    #[case(Tag::WPInline, owned_name("wp", "inline"))]
+    #[case(Tag::WPAnchor, owned_name("wp", "anchor"))]
+    #[case(Tag::WBookmarkEnd, owned_name("w", "bookmarkEnd"))]
+    #[case(Tag::WBreak, owned_name("w", "br"))]
+    #[case(Tag::WDrawing, owned_name("w", "drawing"))]
+    #[case(Tag::WInstrText, owned_name("w", "instrText"))]
+    #[case(Tag::WParagraph, owned_name("w", "p"))]
+    #[case(Tag::WParagraphPr, owned_name("w", "pPr"))]
+    #[case(Tag::WRun, owned_name("w", "r"))]
+    #[case(Tag::WRunPr, owned_name("w", "rPr"))]
+    #[case(Tag::WTab, owned_name("w", "tab"))]
+    #[case(Tag::WTabs, owned_name("w", "tabs"))]
+    #[case(Tag::WText, owned_name("w", "t"))]
+    #[case(Tag::WTable, owned_name("w", "tbl"))]
+    #[case(Tag::WTablePr, owned_name("w", "tblPr"))]
+    #[case(Tag::WTableGrid, owned_name("w", "tblGrid"))]
+    #[case(Tag::WGridColumn(Width::default()), owned_name("w", "gridCol"))]
+    #[case(Tag::WTableRow, owned_name("w", "tr"))]
+    #[case(Tag::WTableCell, owned_name("w", "tc"))]
+    #[case(Tag::WTableCellPr, owned_name("w", "tcPr"))]
+    #[case(Tag::WSdt, owned_name("w", "sdt"))]
+    #[case(Tag::WSdtPr, owned_name("w", "sdtPr"))]
+    #[case(Tag::WSdtContent, owned_name("w", "sdtContent"))]
+    #[case(Tag::WNumPr, owned_name("w", "numPr"))]
+    #[case(Tag::WFootnoteRef, owned_name("w", "footnoteRef"))]
+    #[case(Tag::WSectionPr, owned_name("w", "sectPr"))]
+    #[case(Tag::WTitlePg, owned_name("w", "titlePg"))]
+    #[case(Tag::WSuppressLineNumbers, owned_name("w", "suppressLineNumbers"))]
+    #[case(Tag::WDocument, owned_name("w", "document"))]
+    #[case(Tag::WBody, owned_name("w", "body"))]
+    #[case(Tag::WFootnotes, owned_name("w", "footnotes"))]
+    #[case(Tag::WSeparator, owned_name("w", "separator"))]
+    #[case(Tag::WContinuationSeparator, owned_name("w", "continuationSeparator"))]
+    fn converts_empty_tags(#[case] tag: Tag, #[case] owned: OwnedName) {
+        let actual = Tag::try_from((&owned, &vec![])).expect("Input was constructed manually");
+        assert_eq!(actual, tag);
+    }
+
+    #[test]
+    fn converts_ablip_with_attribute() {
+        let name = owned("a:blip");
+        let attribute = OwnedAttribute {
+            name: owned("r:embed"),
+            value: "RelId".to_string(),
+        };
+
+        let actual = Tag::try_from((&name, &vec![attribute]));
+        assert!(actual.is_ok());
+        let actual = actual.unwrap();
+
+        assert!(matches!(actual, Tag::ABlip { rel: _ }));
+        if let Tag::ABlip { rel } = actual {
+            assert_eq!(rel, "RelId");
+        }
+    }
+
+    #[test]
+    fn rejects_ablip_without_attribute() {
+        let name = owned("a:blip");
+
+        let actual = Tag::try_from((&name, &vec![]));
+
+        assert!(actual.is_err());
+        let actual = actual.unwrap_err();
+        let InputError::MissingAttributes { id, missing } = actual;
+
+        assert_eq!(id, "a:blip");
+        assert_eq!(missing, vec!["r:embed"]);
+    }
+
+    #[test]
+    fn converts_asvgsvgblip_with_attribute() {
+        let name = owned("asvg:svgBlip");
+        let attribute = OwnedAttribute {
+            name: owned("r:embed"),
+            value: "RelId".to_string(),
+        };
+
+        let actual = Tag::try_from((&name, &vec![attribute]));
+        assert!(actual.is_ok());
+        assert_eq!(
+            actual.unwrap(),
+            Tag::AsvgSvgBlip {
+                rel: "RelId".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_asvgsvgblip_without_attribute() {
+        let name = owned("asvg:svgBlip");
+
+        let actual = Tag::try_from((&name, &vec![]));
+
+        assert!(actual.is_err());
+        let actual = actual.unwrap_err();
+        let InputError::MissingAttributes { id, missing } = actual;
+
+        assert_eq!(id, "asvg:svgBlip");
+        assert_eq!(missing, vec!["r:embed"]);
+    }
+
+    #[test]
+    fn converts_mchr_with_attribute() {
+        let name = owned("m:chr");
+        let attribute = OwnedAttribute {
+            name: owned("m:val"),
+            value: "X".to_string(),
+        };
+
+        let actual = Tag::try_from((&name, &vec![attribute]));
+        assert!(actual.is_ok());
+        let actual = actual.unwrap();
+
+        assert!(matches!(actual, Tag::MChr { value: _ }));
+        if let Tag::MChr { value } = actual {
+            assert_eq!(value, "X");
+        }
+    }
+
+    #[test]
+    fn rejects_mchr_with_no_attribute() {
+        let name = owned("m:chr");
+
+        let actual = Tag::try_from((&name, &vec![]));
+        assert!(actual.is_err());
+        let actual = actual.unwrap_err();
+        let InputError::MissingAttributes { id, missing } = actual;
+
+        assert_eq!(id, "m:chr");
+        assert_eq!(missing, vec!["m:val"]);
+    }
+
+    #[test]
+    fn converts_wbookmarkstart_with_attribute() {
+        let name = owned("w:bookmarkStart");
+        let attribute = OwnedAttribute {
+            name: owned("w:anchor"),
+            value: "Anchor".to_string(),
+        };
+
+        let actual = Tag::try_from((&name, &vec![attribute]));
+        assert!(actual.is_ok());
+        let actual = actual.unwrap();
+
+        assert!(matches!(actual, Tag::WBookmarkStart { anchor: _ }));
+        if let Tag::WBookmarkStart { anchor } = actual {
+            assert_eq!(anchor, "Anchor");
+        }
+    }
+
+    #[test]
+    fn converts_wbookmarkstart_with_name() {
+        let name = owned("w:bookmarkStart");
+        let attributes = vec![
+            OwnedAttribute {
+                name: owned("w:id"),
+                value: "0".to_string(),
+            },
+            OwnedAttribute {
+                name: owned("w:name"),
+                value: "_Toc1".to_string(),
+            },
+        ];
+
+        let actual = Tag::try_from((&name, &attributes));
+        assert!(actual.is_ok());
+        let actual = actual.unwrap();
+
+        assert!(matches!(actual, Tag::WBookmarkStart { anchor: _ }));
+        if let Tag::WBookmarkStart { anchor } = actual {
+            assert_eq!(anchor, "_Toc1");
+        }
+    }
+
+    #[test]
+    fn accepts_wbookmarkstart_with_no_attribute() {
+        let name = owned("w:bookmarkStart");
+
+        let actual = Tag::try_from((&name, &vec![]));
+        assert!(actual.is_ok());
+        let actual = actual.unwrap();
+
+        assert!(matches!(actual, Tag::WBookmarkStart { anchor: _ }));
+        if let Tag::WBookmarkStart { anchor } = actual {
+            assert_eq!(anchor, "");
+        }
+    }
+
+    #[test]
+    fn converts_wtextdirection_with_attribute() {
+        let name = owned("w:textDirection");
+        let attribute = OwnedAttribute {
+            name: owned("w:val"),
+            value: "btLr".to_string(),
+        };
+
+        let actual = Tag::try_from((&name, &vec![attribute]));
+        assert!(actual.is_ok());
+        let actual = actual.unwrap();
+
+        assert!(matches!(actual, Tag::WTextDirection { value: _ }));
+        if let Tag::WTextDirection { value } = actual {
+            assert_eq!(value, "btLr");
+        }
+    }
+
+    #[test]
+    fn rejects_wtextdirection_with_no_attribute() {
+        let name = owned("w:textDirection");
+
+        let actual = Tag::try_from((&name, &vec![]));
+        assert!(actual.is_err());
+        let actual = actual.unwrap_err();
+        let InputError::MissingAttributes { id, missing } = actual;
+
+        assert_eq!(id, "w:textDirection");
+        assert_eq!(missing, vec!["w:val"]);
+    }
+
+    #[test]
+    fn converts_wcolor_with_attribute() {
+        let name = owned("w:color");
+        let attribute = OwnedAttribute {
+            name: owned("w:val"),
+            value: "FF0000".to_string(),
+        };
+
+        let actual = Tag::try_from((&name, &vec![attribute]));
+        assert!(actual.is_ok());
+        let actual = actual.unwrap();
+
+        assert!(matches!(actual, Tag::WColor { value: _ }));
+        if let Tag::WColor { value } = actual {
+            assert_eq!(value, "FF0000");
+        }
+    }
+
+    #[test]
+    fn rejects_wcolor_with_no_attribute() {
+        let name = owned("w:color");
+
+        let actual = Tag::try_from((&name, &vec![]));
+        assert!(actual.is_err());
+        let actual = actual.unwrap_err();
+        let InputError::MissingAttributes { id, missing } = actual;
+
+        assert_eq!(id, "w:color");
+        assert_eq!(missing, vec!["w:val"]);
+    }
+
+    #[test]
+    fn converts_whighlight_with_attribute() {
+        let name = owned("w:highlight");
+        let attribute = OwnedAttribute {
+            name: owned("w:val"),
+            value: "yellow".to_string(),
+        };
+
+        let actual = Tag::try_from((&name, &vec![attribute]));
+        assert!(actual.is_ok());
+        let actual = actual.unwrap();
+
+        assert!(matches!(actual, Tag::WHighlight { value: _ }));
+        if let Tag::WHighlight { value } = actual {
+            assert_eq!(value, "yellow");
+        }
+    }
+
+    #[test]
+    fn rejects_whighlight_with_no_attribute() {
+        let name = owned("w:highlight");
+
+        let actual = Tag::try_from((&name, &vec![]));
+        assert!(actual.is_err());
+        let actual = actual.unwrap_err();
+        let InputError::MissingAttributes { id, missing } = actual;
+
+        assert_eq!(id, "w:highlight");
+        assert_eq!(missing, vec!["w:val"]);
+    }
+
+    #[test]
+    fn converts_wvertalign_with_attribute() {
+        let name = owned("w:vertAlign");
+        let attribute = OwnedAttribute {
+            name: owned("w:val"),
+            value: "superscript".to_string(),
+        };
+
+        let actual = Tag::try_from((&name, &vec![attribute]));
+        assert!(actual.is_ok());
+        let actual = actual.unwrap();
+
+        assert!(matches!(actual, Tag::WVertAlign { value: _ }));
+        if let Tag::WVertAlign { value } = actual {
+            assert_eq!(value, "superscript");
+        }
+    }
+
+    #[test]
+    fn rejects_wvertalign_with_no_attribute() {
+        let name = owned("w:vertAlign");
+
+        let actual = Tag::try_from((&name, &vec![]));
+        assert!(actual.is_err());
+        let actual = actual.unwrap_err();
+        let InputError::MissingAttributes { id, missing } = actual;
+
+        assert_eq!(id, "w:vertAlign");
+        assert_eq!(missing, vec!["w:val"]);
+    }
+
+    #[test]
+    fn converts_mpos_with_attribute() {
+        let name = owned("m:pos");
+        let attribute = OwnedAttribute {
+            name: owned("m:val"),
+            value: "top".to_string(),
+        };
+
+        let actual = Tag::try_from((&name, &vec![attribute]));
+        assert!(actual.is_ok());
+        let actual = actual.unwrap();
+
+        assert!(matches!(actual, Tag::MPos { value: _ }));
+        if let Tag::MPos { value } = actual {
+            assert_eq!(value, "top");
+        }
+    }
+
+    #[test]
+    fn rejects_mpos_with_no_attribute() {
+        let name = owned("m:pos");
+
+        let actual = Tag::try_from((&name, &vec![]));
+        assert!(actual.is_err());
+        let actual = actual.unwrap_err();
+        let InputError::MissingAttributes { id, missing } = actual;
+
+        assert_eq!(id, "m:pos");
+        assert_eq!(missing, vec!["m:val"]);
+    }
+
+    #[test]
+    fn converts_wtab_with_position_to_tab_stop() {
+        let name = owned("w:tab");
+        let attributes = vec![
+            OwnedAttribute {
+                name: owned("w:val"),
+                value: "left".to_string(),
+            },
+            OwnedAttribute {
+                name: owned("w:pos"),
+                value: "720".to_string(),
+            },
+        ];
+
+        let actual = Tag::try_from((&name, &attributes));
+        assert!(actual.is_ok());
+        assert_eq!(
+            actual.unwrap(),
+            Tag::WTabStop {
+                leader: "none".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn converts_wparagraphstyle_with_attribute() {
+        let name = owned("w:pStyle");
+        let attribute = OwnedAttribute {
+            name: owned("w:val"),
+            value: "Title".to_string(),
+        };
+
+        let actual = Tag::try_from((&name, &vec![attribute]));
+        assert!(actual.is_ok());
+        let actual = actual.unwrap();
+
+        assert!(matches!(actual, Tag::WParagraphStyle { value: _ }));
+        if let Tag::WParagraphStyle { value } = actual {
+            assert_eq!(value, "Title");
+        }
+    }
+
+    #[test]
+    fn rejects_wparagraphstyle_with_no_attribute() {
+        let name = owned("w:pStyle");
+
+        let actual = Tag::try_from((&name, &vec![]));
+        assert!(actual.is_err());
+        let actual = actual.unwrap_err();
+        let InputError::MissingAttributes { id, missing } = actual;
+
+        assert_eq!(id, "w:pStyle");
+        assert_eq!(missing, vec!["w:val"]);
+    }
+
+    #[test]
+    fn converts_msepchr_with_attribute() {
+        let name = owned("m:sepChr");
+        let attribute = OwnedAttribute {
+            name: owned("m:val"),
+            value: "|".to_string(),
+        };
+
+        let actual = Tag::try_from((&name, &vec![attribute]));
+        assert!(actual.is_ok());
+        let actual = actual.unwrap();
+
+        assert!(matches!(actual, Tag::MSepChr { value: _ }));
+        if let Tag::MSepChr { value } = actual {
+            assert_eq!(value, "|");
+        }
+    }
+
+    #[test]
+    fn rejects_msepchr_with_no_attribute() {
+        let name = owned("m:sepChr");
+
+        let actual = Tag::try_from((&name, &vec![]));
+        assert!(actual.is_err());
+        let actual = actual.unwrap_err();
+        let InputError::MissingAttributes { id, missing } = actual;
+
+        assert_eq!(id, "m:sepChr");
+        assert_eq!(missing, vec!["m:val"]);
+    }
+
+    #[test]
+    fn converts_mbegchr_with_attribute() {
+        let name = owned("m:begChr");
+        let attribute = OwnedAttribute {
+            name: owned("m:val"),
+            value: "[".to_string(),
+        };
+
+        let actual = Tag::try_from((&name, &vec![attribute]));
+        assert!(actual.is_ok());
+        let actual = actual.unwrap();
+
+        assert!(matches!(actual, Tag::MBegChr { value: _ }));
+        if let Tag::MBegChr { value } = actual {
+            assert_eq!(value, "[");
+        }
+    }
+
+    #[test]
+    fn rejects_mbegchr_with_no_attribute() {
+        let name = owned("m:begChr");
+
+        let actual = Tag::try_from((&name, &vec![]));
+        assert!(actual.is_err());
+        let actual = actual.unwrap_err();
+        let InputError::MissingAttributes { id, missing } = actual;
+
+        assert_eq!(id, "m:begChr");
+        assert_eq!(missing, vec!["m:val"]);
+    }
+
+    #[test]
+    fn converts_mendchr_with_attribute() {
+        let name = owned("m:endChr");
+        let attribute = OwnedAttribute {
+            name: owned("m:val"),
+            value: "]".to_string(),
+        };
+
+        let actual = Tag::try_from((&name, &vec![attribute]));
+        assert!(actual.is_ok());
+        let actual = actual.unwrap();
+
+        assert!(matches!(actual, Tag::MEndChr { value: _ }));
+        if let Tag::MEndChr { value } = actual {
+            assert_eq!(value, "]");
+        }
+    }
 
+    #[test]
+    fn rejects_mendchr_with_no_attribute() {
+        let name = owned("m:endChr");
+
+        let actual = Tag::try_from((&name, &vec![]));
         assert!(actual.is_err());
         let actual = actual.unwrap_err();
         let InputError::MissingAttributes { id, missing } = actual;
 
-        assert_eq!(id, "a:blip");
-        assert_eq!(missing, vec!["r:embed"]);
+        assert_eq!(id, "m:endChr");
+        assert_eq!(missing, vec!["m:val"]);
     }
 
     #[test]
-    fn converts_mchr_with_attribute() {
-        let name = owned("m:chr");
+    fn converts_mtype_with_attribute() {
+        let name = owned("m:type");
         let attribute = OwnedAttribute {
             name: owned("m:val"),
-            value: "X".to_string(),
+            value: "lin".to_string(),
         };
 
         let actual = Tag::try_from((&name, &vec![attribute]));
         assert!(actual.is_ok());
         let actual = actual.unwrap();
 
-        assert!(matches!(actual, Tag::MChr { value: _ }));
-        if let Tag::MChr { value } = actual {
-            assert_eq!(value, "X");
+        assert!(matches!(actual, Tag::MType { value: _ }));
+        if let Tag::MType { value } = actual {
+            assert_eq!(value, "lin");
         }
     }
 
     #[test]
-    fn rejects_mchr_with_no_attribute() {
-        let name = owned("m:chr");
+    fn rejects_mtype_with_no_attribute() {
+        let name = owned("m:type");
 
         let actual = Tag::try_from((&name, &vec![]));
         assert!(actual.is_err());
         let actual = actual.unwrap_err();
         let InputError::MissingAttributes { id, missing } = actual;
 
-        assert_eq!(id, "m:chr");
+        assert_eq!(id, "m:type");
         assert_eq!(missing, vec!["m:val"]);
     }
 
     #[test]
-    fn converts_wbookmarkstart_with_attribute() {
-        let name = owned("w:bookmarkStart");
+    fn converts_wlevel_with_attribute() {
+        let name = owned("w:ilvl");
         let attribute = OwnedAttribute {
-            name: owned("w:anchor"),
-            value: "Anchor".to_string(),
+            name: owned("w:val"),
+            value: "1".to_string(),
         };
 
         let actual = Tag::try_from((&name, &vec![attribute]));
         assert!(actual.is_ok());
         let actual = actual.unwrap();
 
-        assert!(matches!(actual, Tag::WBookmarkStart { anchor: _ }));
-        if let Tag::WBookmarkStart { anchor } = actual {
-            assert_eq!(anchor, "Anchor");
+        assert!(matches!(actual, Tag::WLevel { value: _ }));
+        if let Tag::WLevel { value } = actual {
+            assert_eq!(value, "1");
         }
     }
 
     #[test]
-    fn accepts_wbookmarkstart_with_no_attribute() {
-        let name = owned("w:bookmarkStart");
+    fn rejects_wlevel_with_no_attribute() {
+        let name = owned("w:ilvl");
 
         let actual = Tag::try_from((&name, &vec![]));
+        assert!(actual.is_err());
+        let actual = actual.unwrap_err();
+        let InputError::MissingAttributes { id, missing } = actual;
+
+        assert_eq!(id, "w:ilvl");
+        assert_eq!(missing, vec!["w:val"]);
+    }
+
+    #[test]
+    fn converts_wnumid_with_attribute() {
+        let name = owned("w:numId");
+        let attribute = OwnedAttribute {
+            name: owned("w:val"),
+            value: "3".to_string(),
+        };
+
+        let actual = Tag::try_from((&name, &vec![attribute]));
         assert!(actual.is_ok());
         let actual = actual.unwrap();
 
-        assert!(matches!(actual, Tag::WBookmarkStart { anchor: _ }));
-        if let Tag::WBookmarkStart { anchor } = actual {
-            assert_eq!(anchor, "");
+        assert!(matches!(actual, Tag::WNumId { value: _ }));
+        if let Tag::WNumId { value } = actual {
+            assert_eq!(value, "3");
+        }
+    }
+
+    #[test]
+    fn rejects_wnumid_with_no_attribute() {
+        let name = owned("w:numId");
+
+        let actual = Tag::try_from((&name, &vec![]));
+        assert!(actual.is_err());
+        let actual = actual.unwrap_err();
+        let InputError::MissingAttributes { id, missing } = actual;
+
+        assert_eq!(id, "w:numId");
+        assert_eq!(missing, vec!["w:val"]);
+    }
+
+    #[test]
+    fn converts_wrunstyle_with_attribute() {
+        let name = owned("w:rStyle");
+        let attribute = OwnedAttribute {
+            name: owned("w:val"),
+            value: "FootnoteReference".to_string(),
+        };
+
+        let actual = Tag::try_from((&name, &vec![attribute]));
+        assert!(actual.is_ok());
+        let actual = actual.unwrap();
+
+        assert!(matches!(actual, Tag::WRunStyle { value: _ }));
+        if let Tag::WRunStyle { value } = actual {
+            assert_eq!(value, "FootnoteReference");
+        }
+    }
+
+    #[test]
+    fn rejects_wrunstyle_with_no_attribute() {
+        let name = owned("w:rStyle");
+
+        let actual = Tag::try_from((&name, &vec![]));
+        assert!(actual.is_err());
+        let actual = actual.unwrap_err();
+        let InputError::MissingAttributes { id, missing } = actual;
+
+        assert_eq!(id, "w:rStyle");
+        assert_eq!(missing, vec!["w:val"]);
+    }
+
+    #[test]
+    fn converts_wfootnotereference_with_attribute() {
+        let name = owned("w:footnoteReference");
+        let attribute = OwnedAttribute {
+            name: owned("w:id"),
+            value: "2".to_string(),
+        };
+
+        let actual = Tag::try_from((&name, &vec![attribute]));
+        assert!(actual.is_ok());
+        let actual = actual.unwrap();
+
+        assert!(matches!(actual, Tag::WFootnoteReference { id: _ }));
+        if let Tag::WFootnoteReference { id } = actual {
+            assert_eq!(id, "2");
+        }
+    }
+
+    #[test]
+    fn rejects_wfootnotereference_with_no_attribute() {
+        let name = owned("w:footnoteReference");
+
+        let actual = Tag::try_from((&name, &vec![]));
+        assert!(actual.is_err());
+        let actual = actual.unwrap_err();
+        let InputError::MissingAttributes { id, missing } = actual;
+
+        assert_eq!(id, "w:footnoteReference");
+        assert_eq!(missing, vec!["w:id"]);
+    }
+
+    #[test]
+    fn converts_wfootnote_with_attribute() {
+        let name = owned("w:footnote");
+        let attribute = OwnedAttribute {
+            name: owned("w:id"),
+            value: "2".to_string(),
+        };
+
+        let actual = Tag::try_from((&name, &vec![attribute]));
+        assert!(actual.is_ok());
+        let actual = actual.unwrap();
+
+        assert!(matches!(actual, Tag::WFootnote { id: _ }));
+        if let Tag::WFootnote { id } = actual {
+            assert_eq!(id, "2");
+        }
+    }
+
+    #[test]
+    fn rejects_wfootnote_with_no_attribute() {
+        let name = owned("w:footnote");
+
+        let actual = Tag::try_from((&name, &vec![]));
+        assert!(actual.is_err());
+        let actual = actual.unwrap_err();
+        let InputError::MissingAttributes { id, missing } = actual;
+
+        assert_eq!(id, "w:footnote");
+        assert_eq!(missing, vec!["w:id"]);
+    }
+
+    #[test]
+    fn converts_wtablelayout_with_attribute() {
+        let name = owned("w:tblLayout");
+        let attribute = OwnedAttribute {
+            name: owned("w:type"),
+            value: "fixed".to_string(),
+        };
+
+        let actual = Tag::try_from((&name, &vec![attribute]));
+        assert!(actual.is_ok());
+        let actual = actual.unwrap();
+
+        assert!(matches!(actual, Tag::WTableLayout { value: _ }));
+        if let Tag::WTableLayout { value } = actual {
+            assert_eq!(value, "fixed");
+        }
+    }
+
+    #[test]
+    fn converts_wtablelayout_with_no_attribute_to_autofit() {
+        let name = owned("w:tblLayout");
+
+        let actual = Tag::try_from((&name, &vec![]));
+        assert_eq!(
+            actual.unwrap(),
+            Tag::WTableLayout {
+                value: "autofit".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn converts_woutlinelevel_with_attribute() {
+        let name = owned("w:outlineLvl");
+        let attribute = OwnedAttribute {
+            name: owned("w:val"),
+            value: "0".to_string(),
+        };
+
+        let actual = Tag::try_from((&name, &vec![attribute]));
+        assert!(actual.is_ok());
+        let actual = actual.unwrap();
+
+        assert!(matches!(actual, Tag::WOutlineLevel { value: _ }));
+        if let Tag::WOutlineLevel { value } = actual {
+            assert_eq!(value, "0");
         }
     }
 
+    #[test]
+    fn rejects_woutlinelevel_with_no_attribute() {
+        let name = owned("w:outlineLvl");
+
+        let actual = Tag::try_from((&name, &vec![]));
+        assert!(actual.is_err());
+        let actual = actual.unwrap_err();
+        let InputError::MissingAttributes { id, missing } = actual;
+
+        assert_eq!(id, "w:outlineLvl");
+        assert_eq!(missing, vec!["w:val"]);
+    }
+
     #[test]
     fn converts_whyperlink_with_relationship() {
         let name = owned("w:hyperlink");
@@ -678,6 +2790,297 @@ mod test {
         assert_eq!(missing, vec!["r:id", "w:anchor"]);
     }
 
+    #[test]
+    fn treats_page_break_as_unknown() {
+        let name = owned("w:br");
+        let attribute = OwnedAttribute {
+            name: owned("w:type"),
+            value: "page".to_string(),
+        };
+
+        let actual = Tag::try_from((&name, &vec![attribute]));
+        assert!(actual.is_ok());
+        let actual = actual.unwrap();
+
+        assert!(matches!(actual, Tag::Unknown { id: _ }));
+        if let Tag::Unknown { id } = actual {
+            assert_eq!(id, "w:br");
+        }
+    }
+
+    #[test]
+    fn converts_wfldsimple_with_attribute() {
+        let name = owned("w:fldSimple");
+        let attribute = OwnedAttribute {
+            name: owned("w:instr"),
+            value: " CITATION Smi20 \\l 1033 ".to_string(),
+        };
+
+        let actual = Tag::try_from((&name, &vec![attribute]));
+        assert!(actual.is_ok());
+        let actual = actual.unwrap();
+
+        assert!(matches!(actual, Tag::WFieldSimple { instr: _ }));
+        if let Tag::WFieldSimple { instr } = actual {
+            assert_eq!(instr, " CITATION Smi20 \\l 1033 ");
+        }
+    }
+
+    #[test]
+    fn rejects_wfldsimple_with_no_attribute() {
+        let name = owned("w:fldSimple");
+
+        let actual = Tag::try_from((&name, &vec![]));
+        assert!(actual.is_err());
+        let actual = actual.unwrap_err();
+        let InputError::MissingAttributes { id, missing } = actual;
+
+        assert_eq!(id, "w:fldSimple");
+        assert_eq!(missing, vec!["w:instr"]);
+    }
+
+    #[rstest]
+    #[case("begin", FieldChar::Begin)]
+    #[case("separate", FieldChar::Separate)]
+    #[case("end", FieldChar::End)]
+    fn converts_wfldchar_with_attribute(#[case] value: &str, #[case] kind: FieldChar) {
+        let name = owned("w:fldChar");
+        let attribute = OwnedAttribute {
+            name: owned("w:fldCharType"),
+            value: value.to_string(),
+        };
+
+        let actual = Tag::try_from((&name, &vec![attribute]));
+        assert!(actual.is_ok());
+        assert_eq!(actual.unwrap(), Tag::WFieldChar(kind));
+    }
+
+    #[test]
+    fn rejects_wfldchar_with_no_attribute() {
+        let name = owned("w:fldChar");
+
+        let actual = Tag::try_from((&name, &vec![]));
+        assert!(actual.is_err());
+        let actual = actual.unwrap_err();
+        let InputError::MissingAttributes { id, missing } = actual;
+
+        assert_eq!(id, "w:fldChar");
+        assert_eq!(missing, vec!["w:fldCharType"]);
+    }
+
+    #[test]
+    fn converts_wpdocpr_with_description() {
+        let name = owned("wp:docPr");
+        let attributes = vec![
+            OwnedAttribute {
+                name: OwnedName::local("id"),
+                value: "1".to_string(),
+            },
+            OwnedAttribute {
+                name: OwnedName::local("descr"),
+                value: "A cat".to_string(),
+            },
+        ];
+
+        let actual = Tag::try_from((&name, &attributes));
+        assert!(actual.is_ok());
+        assert_eq!(
+            actual.unwrap(),
+            Tag::WPDocPr {
+                description: Some("A cat".to_string())
+            }
+        );
+    }
+
+    #[test]
+    fn converts_wrunfonts_with_ascii() {
+        let name = owned("w:rFonts");
+        let attributes = vec![
+            owned_attr("w", "ascii", "Consolas"),
+            owned_attr("w", "hAnsi", "Consolas"),
+        ];
+
+        let actual = Tag::try_from((&name, &attributes));
+        assert!(actual.is_ok());
+        assert_eq!(
+            actual.unwrap(),
+            Tag::WRunFonts {
+                ascii: Some("Consolas".to_string())
+            }
+        );
+    }
+
+    #[test]
+    fn converts_wpextent_with_width() {
+        let name = owned("wp:extent");
+        let attributes = vec![
+            OwnedAttribute {
+                name: OwnedName::local("cx"),
+                value: "914400".to_string(),
+            },
+            OwnedAttribute {
+                name: OwnedName::local("cy"),
+                value: "457200".to_string(),
+            },
+        ];
+
+        let actual = Tag::try_from((&name, &attributes));
+        assert!(actual.is_ok());
+        assert_eq!(
+            actual.unwrap(),
+            Tag::WPExtent {
+                width: Some("914400".to_string())
+            }
+        );
+    }
+
+    #[test]
+    fn converts_wtablewidth() {
+        let name = owned("w:tblW");
+        let attributes = vec![
+            OwnedAttribute {
+                name: owned("w:w"),
+                value: "5000".to_string(),
+            },
+            OwnedAttribute {
+                name: owned("w:type"),
+                value: "pct".to_string(),
+            },
+        ];
+
+        let actual = Tag::try_from((&name, &attributes));
+        assert!(actual.is_ok());
+        assert_eq!(
+            actual.unwrap(),
+            Tag::WTableWidth(Width {
+                value: Some("5000".to_string()),
+                kind: Some("pct".to_string()),
+            })
+        );
+    }
+
+    #[test]
+    fn converts_wspacing_ignoring_other_attributes() {
+        let name = owned("w:spacing");
+        let attributes = vec![
+            OwnedAttribute {
+                name: owned("w:after"),
+                value: "160".to_string(),
+            },
+            OwnedAttribute {
+                name: owned("w:line"),
+                value: "280".to_string(),
+            },
+            OwnedAttribute {
+                name: owned("w:lineRule"),
+                value: "exact".to_string(),
+            },
+        ];
+
+        let actual = Tag::try_from((&name, &attributes));
+        assert!(actual.is_ok());
+        assert_eq!(
+            actual.unwrap(),
+            Tag::WSpacing(Spacing {
+                line: Some("280".to_string()),
+                rule: Some("exact".to_string()),
+            })
+        );
+    }
+
+    #[rstest]
+    #[case(vec![], Tag::WBidi(true))]
+    #[case(vec![OwnedAttribute { name: owned("w:val"), value: "1".to_string() }], Tag::WBidi(true))]
+    #[case(vec![OwnedAttribute { name: owned("w:val"), value: "false".to_string() }], Tag::WBidi(false))]
+    fn converts_wbidi_toggle(#[case] attributes: Vec<OwnedAttribute>, #[case] expected: Tag) {
+        let name = owned("w:bidi");
+        let actual = Tag::try_from((&name, &attributes));
+        assert!(actual.is_ok());
+        assert_eq!(actual.unwrap(), expected);
+    }
+
+    #[rstest]
+    #[case(vec![], Tag::WRtl(true))]
+    #[case(vec![OwnedAttribute { name: owned("w:val"), value: "off".to_string() }], Tag::WRtl(false))]
+    fn converts_wrtl_toggle(#[case] attributes: Vec<OwnedAttribute>, #[case] expected: Tag) {
+        let name = owned("w:rtl");
+        let actual = Tag::try_from((&name, &attributes));
+        assert!(actual.is_ok());
+        assert_eq!(actual.unwrap(), expected);
+    }
+
+    #[rstest]
+    #[case(vec![("w:w", "11906"), ("w:h", "16838")], false)]
+    #[case(vec![("w:w", "16838"), ("w:h", "11906"), ("w:orient", "landscape")], true)]
+    #[case(vec![("w:w", "16838"), ("w:h", "11906")], true)]
+    #[case(vec![("w:orient", "landscape")], true)]
+    #[case(vec![("w:orient", "portrait")], false)]
+    fn converts_wpgsz_orientation(
+        #[case] attributes: Vec<(&'static str, &str)>,
+        #[case] landscape: bool,
+    ) {
+        let name = owned("w:pgSz");
+        let attributes: Vec<_> = attributes
+            .into_iter()
+            .map(|(name, value)| OwnedAttribute {
+                name: owned(name),
+                value: value.to_string(),
+            })
+            .collect();
+        let actual = Tag::try_from((&name, &attributes));
+        assert!(matches!(actual, Ok(Tag::WPageSize { landscape: l, .. }) if l == landscape));
+    }
+
+    #[test]
+    fn converts_wpgsz_width() {
+        let name = owned("w:pgSz");
+        let attributes = vec![owned_attr("w", "w", "11906"), owned_attr("w", "h", "16838")];
+        let actual = Tag::try_from((&name, &attributes));
+        assert_eq!(
+            actual.unwrap(),
+            Tag::WPageSize {
+                landscape: false,
+                width: Some(11906)
+            }
+        );
+    }
+
+    #[test]
+    fn converts_wpgmar() {
+        let name = owned("w:pgMar");
+        let attributes = vec![
+            owned_attr("w", "top", "1440"),
+            owned_attr("w", "right", "1800"),
+            owned_attr("w", "bottom", "1440"),
+            owned_attr("w", "left", "1440"),
+        ];
+        let actual = Tag::try_from((&name, &attributes));
+        assert_eq!(
+            actual.unwrap(),
+            Tag::WPageMargins {
+                left: Some(1440),
+                right: Some(1800)
+            }
+        );
+    }
+
+    #[test]
+    fn converts_wlnnumtype() {
+        let name = owned("w:lnNumType");
+        let attributes = vec![
+            owned_attr("w", "countBy", "1"),
+            owned_attr("w", "restart", "newSection"),
+        ];
+        let actual = Tag::try_from((&name, &attributes));
+        assert_eq!(
+            actual.unwrap(),
+            Tag::WLineNumbers {
+                count_by: Some(1),
+                restart: Some("newSection".to_string())
+            }
+        );
+    }
+
     #[test]
     fn accepts_unknown_tags() {
         let name = owned("alien:tag");